@@ -0,0 +1,34 @@
+/// See readme before changing anything!
+
+/// Audio is analysed at this sample rate, whatever the file was recorded with.
+const ANALYSIS_SAMPLE_RATE = 44100;
+
+/// Bytes at the start of the file that are kept, to read the sample rate the file was recorded with.
+const HEADER_BYTES = 256 * 1024;
+
+/// Downloads and decodes an audio file. Returns the sample rate, the samples of each channel and the start of the file.
+export async function decode_audio_url(url) {
+    const response = await fetch(url, { credentials: "include" });
+    if (!response.ok) {
+        throw new Error(`Got status ${response.status} while loading ${url}`);
+    }
+    return decode(await response.arrayBuffer());
+}
+
+/// Decodes an audio file chosen by the user, e.g. before it is uploaded.
+export async function decode_audio_file(file) {
+    return decode(await file.arrayBuffer());
+}
+
+async function decode(data) {
+    // Decoding detaches the buffer, so the header is copied first.
+    const header = new Uint8Array(data.slice(0, HEADER_BYTES));
+    // An offline context decodes without a user gesture and without an audio device.
+    const context = new OfflineAudioContext(1, 1, ANALYSIS_SAMPLE_RATE);
+    const buffer = await context.decodeAudioData(data);
+    const channels = [];
+    for (let channel = 0; channel < buffer.numberOfChannels; channel++) {
+        channels.push(buffer.getChannelData(channel));
+    }
+    return { sample_rate: buffer.sampleRate, channels, header };
+}
@@ -0,0 +1,202 @@
+/// See readme before changing anything!
+
+// Mixers by name, e.g. one for the material and one for the submissions. Their state is kept here between the calls from wasm.
+const mixers = new Map();
+
+/// Time to start all tracks together, in seconds.
+const START_DELAY = 0.05;
+
+/// Loads and decodes the tracks, so they can be played in sync. Returns the duration of the longest track in seconds.
+export async function load_mixer(name, urls) {
+    close_mixer(name);
+
+    const context = new AudioContext();
+    try {
+        const buffers = await Promise.all(urls.map(async (url) => {
+            const response = await fetch(url, { credentials: "include" });
+            if (!response.ok) {
+                throw new Error(`Got status ${response.status} while loading ${url}`);
+            }
+            return await context.decodeAudioData(await response.arrayBuffer());
+        }));
+
+        const gains = buffers.map(() => context.createGain());
+        const pans = buffers.map((_, index) => {
+            const pan = context.createStereoPanner();
+            gains[index].connect(pan);
+            pan.connect(context.destination);
+            return pan;
+        });
+
+        const mixer = {
+            context,
+            buffers,
+            gains,
+            pans,
+            // Seconds each track starts after the start of the mix.
+            delays: buffers.map(() => 0),
+            sources: [],
+            // Position in the mix when the playback was started or paused, in seconds.
+            offset: 0,
+            // Time of the audio context the playback was started at, null while paused.
+            started_at: null,
+            duration: 0,
+        };
+        mixer.duration = duration(mixer);
+        mixers.set(name, mixer);
+        return mixer.duration;
+    } catch (error) {
+        context.close();
+        throw error;
+    }
+}
+
+/// Plays all tracks from the position in seconds.
+export function mixer_play(name, from) {
+    const mixer = mixers.get(name);
+    if (!mixer) {
+        return;
+    }
+    stop_sources(mixer);
+    mixer.context.resume();
+
+    const at = mixer.context.currentTime + START_DELAY;
+    mixer.offset = Math.min(Math.max(from, 0), mixer.duration);
+    mixer.sources = mixer.buffers.map((buffer, index) => {
+        const source = mixer.context.createBufferSource();
+        source.buffer = buffer;
+        source.connect(mixer.gains[index]);
+        start_source(source, at, mixer.offset - mixer.delays[index]);
+        return source;
+    });
+    mixer.started_at = at;
+}
+
+/// Pauses all tracks at the current position.
+export function mixer_pause(name) {
+    const mixer = mixers.get(name);
+    if (!mixer) {
+        return;
+    }
+    mixer.offset = mixer_position(name);
+    mixer.started_at = null;
+    stop_sources(mixer);
+}
+
+/// Current position in seconds, at most the duration.
+export function mixer_position(name) {
+    const mixer = mixers.get(name);
+    if (!mixer) {
+        return 0;
+    }
+    if (mixer.started_at === null) {
+        return mixer.offset;
+    }
+    const played = Math.max(mixer.context.currentTime - mixer.started_at, 0);
+    return Math.min(mixer.offset + played, mixer.duration);
+}
+
+/// Sets the volume of each track, between 0 and 1, in the order of the urls.
+export function mixer_set_gains(name, gains) {
+    const mixer = mixers.get(name);
+    if (!mixer) {
+        return;
+    }
+    const now = mixer.context.currentTime;
+    mixer.gains.forEach((gain, index) => {
+        // A short ramp avoids clicks.
+        gain.gain.setTargetAtTime(gains[index] ?? 0, now, 0.02);
+    });
+}
+
+/// Sets the position of each track in the stereo field, from -1 (left) to 1 (right), in the order of the urls.
+export function mixer_set_pans(name, pans) {
+    const mixer = mixers.get(name);
+    if (!mixer) {
+        return;
+    }
+    const now = mixer.context.currentTime;
+    mixer.pans.forEach((pan, index) => {
+        pan.pan.setTargetAtTime(pans[index] ?? 0, now, 0.02);
+    });
+}
+
+/// Sets the seconds each track starts after the start of the mix, negative to skip its beginning.
+/// Returns the new duration, a playing mix continues at the same position.
+export function mixer_set_delays(name, delays) {
+    const mixer = mixers.get(name);
+    if (!mixer) {
+        return 0;
+    }
+    const playing = mixer.started_at !== null;
+    const position = mixer_position(name);
+    mixer.delays = mixer.buffers.map((_, index) => delays[index] ?? 0);
+    mixer.duration = duration(mixer);
+    if (playing) {
+        mixer_play(name, position);
+    } else {
+        mixer.offset = Math.min(position, mixer.duration);
+    }
+    return mixer.duration;
+}
+
+/// Renders the mix as it is heard, faster than real time. Returns the sample rate and the samples of both channels.
+export async function mixer_render(name, gains, pans) {
+    const mixer = mixers.get(name);
+    if (!mixer) {
+        throw new Error(`The mixer ${name} is not loaded`);
+    }
+    const sample_rate = mixer.context.sampleRate;
+    const length = Math.max(1, Math.ceil(mixer.duration * sample_rate));
+    const context = new OfflineAudioContext(2, length, sample_rate);
+    mixer.buffers.forEach((buffer, index) => {
+        const source = context.createBufferSource();
+        source.buffer = buffer;
+        const gain = context.createGain();
+        gain.gain.value = gains[index] ?? 0;
+        const pan = context.createStereoPanner();
+        pan.pan.value = pans[index] ?? 0;
+        source.connect(gain);
+        gain.connect(pan);
+        pan.connect(context.destination);
+        start_source(source, 0, -mixer.delays[index]);
+    });
+    const rendered = await context.startRendering();
+    return { sample_rate, channels: [rendered.getChannelData(0), rendered.getChannelData(1)] };
+}
+
+/// Stops the playback and frees the decoded tracks.
+export function close_mixer(name) {
+    const mixer = mixers.get(name);
+    if (!mixer) {
+        return;
+    }
+    stop_sources(mixer);
+    mixer.context.close();
+    mixers.delete(name);
+}
+
+/// Starts the source at the time of its context, `position` seconds into its buffer. Negative positions start it later.
+function start_source(source, at, position) {
+    if (position < 0) {
+        source.start(at - position);
+    } else if (position < source.buffer.duration) {
+        source.start(at, position);
+    }
+}
+
+function duration(mixer) {
+    return Math.max(0, ...mixer.buffers.map((buffer, index) => buffer.duration + mixer.delays[index]));
+}
+
+function stop_sources(current) {
+    current.sources.forEach((source) => {
+        // Sources that were never started can not be stopped.
+        try {
+            source.stop();
+        } catch {
+            // Nothing to stop.
+        }
+    });
+    current.sources = [];
+}
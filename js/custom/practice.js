@@ -0,0 +1,187 @@
+/// See readme before changing anything!
+
+// Loop and pitch shifter of each audio element, by element.
+const players = new WeakMap();
+
+/// How often the delay lines of the pitch shifter jump back, in seconds. Shorter periods sound rougher, longer ones echo more.
+const SHIFT_PERIOD = 0.1;
+
+function player(id) {
+    const element = document.getElementById(id);
+    if (!element) {
+        throw new Error(`No audio element ${id}`);
+    }
+    if (!players.has(element)) {
+        players.set(element, { element, loop: null, shifter: null, frame: null });
+        element.addEventListener("play", () => watch_loop(players.get(element)));
+    }
+    return players.get(element);
+}
+
+/// Current position of the element in seconds.
+export function media_time(id) {
+    return player(id).element.currentTime;
+}
+
+export function seek_media(id, seconds) {
+    const { element } = player(id);
+    element.currentTime = Math.min(Math.max(seconds, 0), element.duration || 0);
+}
+
+/// Plays a paused element and pauses a playing one.
+export function toggle_media(id) {
+    const { element } = player(id);
+    if (element.paused) {
+        element.play();
+    } else {
+        element.pause();
+    }
+}
+
+/// Changes the speed, keeping the pitch.
+export function set_tempo(id, rate) {
+    const { element } = player(id);
+    element.preservesPitch = true;
+    element.playbackRate = rate;
+}
+
+/// Repeats the part between the positions in seconds while playing. NaN ends the loop.
+export function set_loop(id, start, end) {
+    const current = player(id);
+    current.loop = Number.isNaN(start) || Number.isNaN(end) ? null : { start, end };
+    if (current.loop && !current.element.paused) {
+        watch_loop(current);
+    }
+}
+
+/// Checks every frame whether the end of the loop was reached, the `timeupdate` event is too rare for that.
+function watch_loop(current) {
+    if (current.frame !== null) {
+        return;
+    }
+    const check = () => {
+        current.frame = null;
+        if (!current.loop || current.element.paused) {
+            return;
+        }
+        if (current.element.currentTime >= current.loop.end || current.element.currentTime < current.loop.start - 0.5) {
+            current.element.currentTime = current.loop.start;
+        }
+        current.frame = requestAnimationFrame(check);
+    };
+    check();
+}
+
+/// Transposes the element by the semitones without changing its speed.
+/// The element is routed through the Web Audio API for this, so its source has to allow cross origin access.
+export function set_transposition(id, semitones) {
+    const current = player(id);
+    if (!current.shifter) {
+        if (semitones === 0) {
+            return;
+        }
+        request_cross_origin(current.element);
+        current.shifter = create_shifter(current.element);
+    }
+    current.shifter.set_ratio(Math.pow(2, semitones / 12));
+}
+
+/// Reloads the source with credentials in cross origin mode, else the Web Audio API only gets silence.
+/// Only done for transposition, so players without it work with backends that don't allow cross origin requests.
+function request_cross_origin(element) {
+    if (element.crossOrigin === "use-credentials") {
+        return;
+    }
+    const position = element.currentTime;
+    const playing = !element.paused;
+    element.crossOrigin = "use-credentials";
+    element.addEventListener("loadedmetadata", () => {
+        element.currentTime = position;
+        if (playing) {
+            element.play();
+        }
+    }, { once: true });
+    element.load();
+}
+
+/// A pitch shifter from two delay lines whose delay time changes steadily, so the sound is played faster or slower.
+/// When a delay line has to jump back, it is silent and the other one is heard.
+function create_shifter(element) {
+    const context = new AudioContext();
+    const source = context.createMediaElementSource(element);
+    const sample_rate = context.sampleRate;
+    const length = Math.round(SHIFT_PERIOD * sample_rate);
+    const half = length / 2;
+
+    // Delay times falling (higher pitch) or rising (lower pitch) from 1 to 0 and back during a period,
+    // and the volume of the delay line, silent when its delay time jumps.
+    const falling = context.createBuffer(1, length, sample_rate);
+    const rising = context.createBuffer(1, length, sample_rate);
+    const fade = context.createBuffer(1, length, sample_rate);
+    const falling_data = falling.getChannelData(0);
+    const rising_data = rising.getChannelData(0);
+    const fade_data = fade.getChannelData(0);
+    for (let index = 0; index < length; index++) {
+        falling_data[index] = 1 - index / length;
+        rising_data[index] = index / length;
+        fade_data[index] = Math.sqrt(index < half ? index / half : (length - index) / half);
+    }
+
+    const looped = (buffer) => {
+        const node = context.createBufferSource();
+        node.buffer = buffer;
+        node.loop = true;
+        return node;
+    };
+
+    const dry = context.createGain();
+    const wet = context.createGain();
+    wet.gain.value = 0;
+    source.connect(dry).connect(context.destination);
+    wet.connect(context.destination);
+
+    const lines = [0, 1].map(() => {
+        const up = context.createGain();
+        const down = context.createGain();
+        const depth = context.createGain();
+        const delay = context.createDelay(1);
+        const volume = context.createGain();
+        const ramps = [looped(falling), looped(rising)];
+        ramps[0].connect(up).connect(depth);
+        ramps[1].connect(down).connect(depth);
+        depth.connect(delay.delayTime);
+        const fader = looped(fade);
+        volume.gain.value = 0;
+        fader.connect(volume.gain);
+        source.connect(delay).connect(volume).connect(wet);
+        return { up, down, depth, sources: [...ramps, fader] };
+    });
+
+    // The second delay line is half a period behind, so one of them is always heard.
+    const start = context.currentTime + 0.05;
+    lines.forEach((line, index) => {
+        line.sources.forEach((node) => node.start(start + index * SHIFT_PERIOD / 2));
+    });
+
+    return {
+        set_ratio(ratio) {
+            const now = context.currentTime;
+            const shifting = Math.abs(ratio - 1) > 1e-6;
+            dry.gain.setTargetAtTime(shifting ? 0 : 1, now, 0.01);
+            wet.gain.setTargetAtTime(shifting ? 1 : 0, now, 0.01);
+            lines.forEach((line) => {
+                // The delay changes by the depth each period, so the sound is played at 1 ± depth / period.
+                line.depth.gain.setTargetAtTime(Math.abs(ratio - 1) * SHIFT_PERIOD, now, 0.01);
+                line.up.gain.setTargetAtTime(ratio > 1 ? 1 : 0, now, 0.01);
+                line.down.gain.setTargetAtTime(ratio > 1 ? 0 : 1, now, 0.01);
+            });
+            context.resume();
+        },
+    };
+}
+
+/// Jumps to the position in seconds and plays from there, e.g. from a rehearsal marker.
+export function play_from(id, seconds) {
+    seek_media(id, seconds);
+    player(id).element.play();
+}
@@ -0,0 +1,271 @@
+/// See readme before changing anything!
+
+// Only one recording runs at a time, its state is kept here between the calls from wasm.
+let recording = null;
+// The recording being started, until it runs. Cancelling marks it, so the start stops after the step it waits for.
+let pending_start = null;
+
+/// Formats browsers record in, preferred first. Audio formats are only used for recordings without camera.
+const VIDEO_TYPES = ["video/webm;codecs=vp9,opus", "video/webm", "video/mp4"];
+const AUDIO_TYPES = ["audio/webm;codecs=opus", "audio/ogg;codecs=opus", "audio/mp4", "audio/webm"];
+
+function supported_type(types) {
+    return types.find((type) => MediaRecorder.isTypeSupported(type)) || "";
+}
+
+/// Seconds between the clicks of the count-in and of the latency test.
+const CLICK_INTERVAL = 1;
+/// Time to set everything up before the first click is played, in seconds.
+const LEAD_TIME = 0.3;
+/// Seconds the latency test may take longer than its clicks, e.g. if the browser never delivers any samples.
+const LOOPBACK_GRACE = 5;
+
+/// Asks for the microphone (and camera) and starts recording. The camera image is shown in the video element with the id, if there is one.
+/// If a playback url is given, it is played after a count-in of that many clicks while recording, to sing along to it.
+export async function start_recording(with_video, preview_id, playback_url, count_in_beats) {
+    cancel_recording();
+    const start = { cancelled: false };
+    pending_start = start;
+
+    const stream = await navigator.mediaDevices.getUserMedia({
+        audio: { echoCancellation: false, noiseSuppression: false, autoGainControl: false },
+        video: with_video,
+    });
+    if (start.cancelled) {
+        stream.getTracks().forEach((track) => track.stop());
+        throw new Error("The recording was cancelled while starting");
+    }
+
+    const audio_context = new AudioContext();
+    const analyser = audio_context.createAnalyser();
+    analyser.fftSize = 2048;
+    audio_context.createMediaStreamSource(stream).connect(analyser);
+
+    let playback = null;
+    if (playback_url) {
+        try {
+            const response = await fetch(playback_url, { credentials: "include" });
+            if (!response.ok) {
+                throw new Error(`Got status ${response.status} while loading the playback`);
+            }
+            playback = await audio_context.decodeAudioData(await response.arrayBuffer());
+        } catch (error) {
+            stream.getTracks().forEach((track) => track.stop());
+            audio_context.close();
+            throw error;
+        }
+        if (start.cancelled) {
+            stream.getTracks().forEach((track) => track.stop());
+            audio_context.close();
+            throw new Error("The recording was cancelled while starting");
+        }
+    }
+
+    const mime_type = supported_type(with_video ? VIDEO_TYPES : AUDIO_TYPES);
+    const recorder = new MediaRecorder(stream, mime_type ? { mimeType: mime_type } : {});
+    const chunks = [];
+    recorder.ondataavailable = (event) => {
+        if (event.data.size > 0) {
+            chunks.push(event.data);
+        }
+    };
+
+    const preview = preview_id ? document.getElementById(preview_id) : null;
+    if (preview) {
+        preview.srcObject = stream;
+        preview.muted = true;
+        preview.play();
+    }
+
+    const current = {
+        stream,
+        audio_context,
+        analyser,
+        recorder,
+        chunks,
+        preview,
+        samples: new Float32Array(analyser.fftSize),
+        sources: [],
+        recording_start: null,
+        playback_start: null,
+    };
+
+    await new Promise((resolve) => {
+        recorder.onstart = () => {
+            current.recording_start = audio_context.currentTime;
+            resolve();
+        };
+        recorder.start(1000);
+    });
+    if (start.cancelled) {
+        recorder.onstop = () => release(current);
+        recorder.stop();
+        throw new Error("The recording was cancelled while starting");
+    }
+    pending_start = null;
+    recording = current;
+
+    if (playback) {
+        const count_in_start = audio_context.currentTime + LEAD_TIME;
+        for (let beat = 0; beat < count_in_beats; beat++) {
+            current.sources.push(click(audio_context, count_in_start + beat * CLICK_INTERVAL, beat === 0));
+        }
+        const source = audio_context.createBufferSource();
+        source.buffer = playback;
+        source.connect(audio_context.destination);
+        current.playback_start = count_in_start + count_in_beats * CLICK_INTERVAL;
+        source.start(current.playback_start);
+        current.sources.push(source);
+    }
+}
+
+/// Seconds from the start of the recording until the playback started, NaN if there was no playback.
+export function playback_offset_seconds() {
+    if (!recording || recording.playback_start === null || recording.recording_start === null) {
+        return NaN;
+    }
+    return recording.playback_start - recording.recording_start;
+}
+
+/// Plays a short beep at the time of the audio context, the first beat of a bar higher.
+function click(audio_context, at, accent) {
+    const oscillator = audio_context.createOscillator();
+    const gain = audio_context.createGain();
+    oscillator.frequency.value = accent ? 1760 : 880;
+    gain.gain.setValueAtTime(0.8, at);
+    gain.gain.exponentialRampToValueAtTime(0.001, at + 0.05);
+    oscillator.connect(gain).connect(audio_context.destination);
+    oscillator.start(at);
+    oscillator.stop(at + 0.06);
+    return oscillator;
+}
+
+/// Loudness of the microphone right now, between 0 and 1.
+export function recording_level() {
+    if (!recording) {
+        return 0;
+    }
+    recording.analyser.getFloatTimeDomainData(recording.samples);
+    let peak = 0;
+    for (const sample of recording.samples) {
+        peak = Math.max(peak, Math.abs(sample));
+    }
+    return Math.min(peak, 1);
+}
+
+/// Stops the recording and returns it as Blob.
+export function stop_recording() {
+    const current = recording;
+    if (!current) {
+        return Promise.reject(new Error("Nothing is being recorded"));
+    }
+
+    return new Promise((resolve) => {
+        current.recorder.onstop = () => {
+            release(current);
+            resolve(new Blob(current.chunks, { type: current.recorder.mimeType || current.chunks[0]?.type || "" }));
+        };
+        current.recorder.stop();
+    });
+}
+
+/// Stops the recording and throws it away, also while it is still starting.
+export function cancel_recording() {
+    if (pending_start) {
+        pending_start.cancelled = true;
+        pending_start = null;
+    }
+    if (!recording) {
+        return;
+    }
+    const current = recording;
+    current.recorder.onstop = () => release(current);
+    if (current.recorder.state !== "inactive") {
+        current.recorder.stop();
+    } else {
+        release(current);
+    }
+}
+
+function release(current) {
+    current.sources.forEach((source) => source.stop());
+    current.stream.getTracks().forEach((track) => track.stop());
+    current.audio_context.close();
+    if (current.preview) {
+        current.preview.srcObject = null;
+    }
+    if (recording === current) {
+        recording = null;
+    }
+}
+
+/// Puts the blob into the file input of the form, as if the user had chosen it. Returns the file.
+export function attach_to_file_input(form_id, field_name, blob, file_name) {
+    const input = document.querySelector(`#${form_id} input[type=file][name=${field_name}]`);
+    if (!input) {
+        throw new Error(`No file input ${field_name} in form ${form_id}`);
+    }
+    const file = new File([blob], file_name, { type: blob.type });
+    const transfer = new DataTransfer();
+    transfer.items.add(file);
+    input.files = transfer.files;
+    return file;
+}
+
+/// Plays clicks and records them through the microphone, to measure the latency of the speakers or headphones.
+/// Returns the samples, their sample rate and the times the clicks were played at, in seconds since the first sample.
+export async function measure_loopback(clicks) {
+    const stream = await navigator.mediaDevices.getUserMedia({
+        audio: { echoCancellation: false, noiseSuppression: false, autoGainControl: false },
+    });
+    const audio_context = new AudioContext();
+    const buffer_size = 4096;
+    // Deprecated, but the only way to get at the raw samples without an extra worklet file.
+    const processor = audio_context.createScriptProcessor(buffer_size, 1, 1);
+    const chunks = [];
+    let capture_start = null;
+    let click_times = [];
+    let end = Infinity;
+
+    let timeout = null;
+
+    try {
+        await new Promise((resolve, reject) => {
+            timeout = setTimeout(() => {
+                reject(new Error("No samples were recorded during the latency test"));
+            }, (LEAD_TIME + clicks * CLICK_INTERVAL + LOOPBACK_GRACE) * 1000);
+            processor.onaudioprocess = (event) => {
+                if (capture_start === null) {
+                    capture_start = audio_context.currentTime - buffer_size / audio_context.sampleRate;
+                    const first_click = audio_context.currentTime + LEAD_TIME;
+                    for (let index = 0; index < clicks; index++) {
+                        const at = first_click + index * CLICK_INTERVAL;
+                        click(audio_context, at, false);
+                        click_times.push(at - capture_start);
+                    }
+                    end = first_click + clicks * CLICK_INTERVAL;
+                }
+                chunks.push(new Float32Array(event.inputBuffer.getChannelData(0)));
+                if (audio_context.currentTime > end) {
+                    resolve();
+                }
+            };
+            audio_context.createMediaStreamSource(stream).connect(processor);
+            processor.connect(audio_context.destination);
+        });
+    } finally {
+        clearTimeout(timeout);
+        processor.onaudioprocess = null;
+        processor.disconnect();
+        stream.getTracks().forEach((track) => track.stop());
+        audio_context.close();
+    }
+
+    const samples = [];
+    for (const chunk of chunks) {
+        for (const sample of chunk) {
+            samples.push(sample);
+        }
+    }
+    return { samples, sample_rate: audio_context.sampleRate, click_times };
+}
@@ -0,0 +1,31 @@
+/// See readme before changing anything!
+export function viewer_time_zone() {
+    return Intl.DateTimeFormat().resolvedOptions().timeZone || "UTC";
+}
+
+/// All time zones the browser knows, separated by new lines.
+export function supported_time_zones() {
+    try {
+        return Intl.supportedValuesOf("timeZone").join("\n");
+    } catch (error) {
+        return [viewer_time_zone(), "UTC"].join("\n");
+    }
+}
+
+/// Offset of the time zone to UTC in seconds at the given unix timestamp. Throws for unknown time zones.
+export function time_zone_offset_seconds(zone, unix_seconds) {
+    const date = new Date(unix_seconds * 1000);
+    const parts = new Intl.DateTimeFormat("en-US", {
+        timeZone: zone,
+        hourCycle: "h23",
+        year: "numeric",
+        month: "2-digit",
+        day: "2-digit",
+        hour: "2-digit",
+        minute: "2-digit",
+        second: "2-digit",
+    }).formatToParts(date);
+    const value = (type) => Number(parts.find((part) => part.type === type).value);
+    const wall = Date.UTC(value("year"), value("month") - 1, value("day"), value("hour"), value("minute"), value("second"));
+    return Math.round((wall - Math.floor(date.getTime() / 1000) * 1000) / 1000);
+}
@@ -0,0 +1,156 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr, Variant};
+
+/// # Overview
+/// Derives everything an enum needs to be chosen with an HTML `<select>`:
+/// - `SelectEnum` (from `crate::utilities::select_enum`) with the options in declaration order, their values and labels
+/// - `TryFrom<&str>`, matching the value and all aliases of a variant, with `()` as error
+/// - `Display` and `yew::ToHtml`, both showing the label
+///
+/// Only works for enums without fields inside of the frontend crate, as the generated code refers to `crate::utilities::select_enum::SelectEnum`
+/// and translates the labels with `crate::i18n::t`.
+///
+/// # Attributes
+/// Every variant can be annotated with `#[select(...)]`:
+/// - `value = "..."`: the stable value used in the `<option>` and sent to the backend. Defaults to the name of the variant.
+/// - `label = "..."`: id of the message in the translation catalogs that is shown to the user. Defaults to the name of the variant.
+/// - `alias = "..."`: additional values accepted by `TryFrom<&str>`. Can be repeated.
+///
+/// ```ignore
+/// #[derive(Copy, Clone, PartialEq, SelectEnum)]
+/// enum Category {
+///     #[select(value = "audio", label = "category-audio", alias = "Audio")]
+///     Audio,
+///     #[select(value = "sheet", label = "category-sheet")]
+///     SheetMusic,
+/// }
+/// ```
+#[proc_macro_derive(SelectEnum, attributes(select))]
+pub fn derive_select_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct SelectVariant {
+    ident: syn::Ident,
+    value: String,
+    label: String,
+    aliases: Vec<String>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "SelectEnum can only be derived for enums",
+            ))
+        }
+    };
+
+    let variants = data
+        .variants
+        .iter()
+        .map(parse_variant)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let idents = variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let values = variants.iter().map(|v| &v.value).collect::<Vec<_>>();
+    let labels = variants.iter().map(|v| &v.label).collect::<Vec<_>>();
+    let accepted = variants.iter().map(|v| {
+        let ident = &v.ident;
+        let value = &v.value;
+        let aliases = &v.aliases;
+        quote! { #value #( | #aliases )* => Ok(Self::#ident) }
+    });
+
+    Ok(quote! {
+        impl crate::utilities::select_enum::SelectEnum for #name {
+            fn options() -> Vec<Self> {
+                vec![ #( Self::#idents ),* ]
+            }
+
+            fn value(&self) -> String {
+                match self {
+                    #( Self::#idents => #values ),*
+                }
+                .to_string()
+            }
+
+            fn label(&self) -> String {
+                crate::i18n::t(match self {
+                    #( Self::#idents => #labels ),*
+                })
+            }
+        }
+
+        impl TryFrom<&str> for #name {
+            type Error = ();
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                match value {
+                    #( #accepted, )*
+                    _ => Err(()),
+                }
+            }
+        }
+
+        impl std::fmt::Display for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", crate::utilities::select_enum::SelectEnum::label(self))
+            }
+        }
+
+        impl yew::ToHtml for #name {
+            fn to_html(&self) -> yew::Html {
+                yew::ToHtml::to_html(&crate::utilities::select_enum::SelectEnum::label(self))
+            }
+        }
+    })
+}
+
+fn parse_variant(variant: &Variant) -> syn::Result<SelectVariant> {
+    if !matches!(variant.fields, Fields::Unit) {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "SelectEnum variants must not have fields",
+        ));
+    }
+
+    let name = variant.ident.to_string();
+    let mut result = SelectVariant {
+        ident: variant.ident.clone(),
+        value: name.clone(),
+        label: name,
+        aliases: Vec::new(),
+    };
+
+    for attribute in variant.attrs.iter().filter(|a| a.path().is_ident("select")) {
+        attribute.parse_nested_meta(|meta| {
+            let literal: LitStr = meta.value()?.parse()?;
+
+            if meta.path.is_ident("value") {
+                result.value = literal.value();
+            } else if meta.path.is_ident("label") {
+                result.label = literal.value();
+            } else if meta.path.is_ident("alias") {
+                result.aliases.push(literal.value());
+            } else {
+                return Err(meta.error("expected `value`, `label` or `alias`"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,214 @@
+use gloo_console::error;
+use gloo_utils::document;
+use web_sys::{Event, HtmlSelectElement};
+use yew::{html, Component, Context, Html, TargetCast};
+
+use crate::{
+    components::jwt_context::{get_token_data, switch_choir, ChoirMembership},
+    i18n::{t, t_with},
+    service::{
+        choir::{get_choir_branding, ChoirBranding},
+        config::is_hex_color,
+        CONFIG,
+    },
+    utilities::requests::fetch::FetchError,
+};
+
+const BRANDING_STYLE_ID: &str = "choirBrandingStyle";
+
+pub enum Msg {
+    BrandingLoaded(ChoirBranding),
+    BrandingLoadError(FetchError),
+    Switch(Event),
+}
+
+/// Shows name and logo of the choir the user is logged in for and lets users of several choirs switch between them.
+/// Applies the colors of the choir to the whole page, or the ones of the theme in the config if there is no choir.
+pub struct ChoirHeader {
+    choir_id: Option<i64>,
+    choirs: Vec<ChoirMembership>,
+    branding: Option<ChoirBranding>,
+}
+
+impl Component for ChoirHeader {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (choir_id, choirs) = match get_token_data() {
+            Ok(user) => (user.choir_id, user.choirs),
+            Err(_) => (None, Vec::new()),
+        };
+
+        match choir_id {
+            Some(choir_id) => ctx.link().send_future(async move {
+                match get_choir_branding(choir_id).await {
+                    Ok(branding) => Msg::BrandingLoaded(branding),
+                    Err(error) => Msg::BrandingLoadError(error),
+                }
+            }),
+            None => apply_primary_color(theme_color()),
+        }
+
+        let choirs = if CONFIG.get().expect("Config unset").features.choir_switcher {
+            choirs
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            choir_id,
+            choirs,
+            branding: None,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::BrandingLoaded(branding) => {
+                apply_branding(&branding);
+                self.branding = Some(branding);
+                true
+            }
+            Msg::BrandingLoadError(error) => {
+                error!(format!("Could not load the branding of the choir: {error}"));
+                apply_primary_color(theme_color());
+                false
+            }
+            Msg::Switch(event) => {
+                let select: HtmlSelectElement = event.target_unchecked_into();
+
+                match select.value().parse::<i64>() {
+                    Ok(choir_id) if Some(choir_id) != self.choir_id => switch_choir(choir_id),
+                    Ok(_) => {}
+                    Err(_) => error!("Selected choir id could not be parsed!"),
+                }
+
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let theme_logo = CONFIG.get().expect("Config unset").theme.logo_url.clone();
+        let logo_url = self
+            .branding
+            .as_ref()
+            .and_then(|branding| branding.logo_url.clone())
+            .or(theme_logo);
+
+        if self.branding.is_none() && logo_url.is_none() && self.choirs.len() < 2 {
+            return html! {};
+        }
+
+        html! {
+            <div class="container">
+                <div class="row mt-2 align-items-center">
+                    if let Some(logo_url) = logo_url {
+                        <div class="col-auto">
+                            <img src={ logo_url } alt="Logo" style="max-height: 3rem;"/>
+                        </div>
+                    }
+                    <div class="col">
+                        if let Some(branding) = &self.branding {
+                            <h4 class="mb-0">{ &branding.name }</h4>
+                        }
+                    </div>
+                    if self.choirs.len() > 1 {
+                        <div class="col-auto">
+                            <label for="selectChoir" class="visually-hidden">{ t("choir-select") }</label>
+                            <select id="selectChoir" class="form-control" onchange={ ctx.link().callback(Msg::Switch) }>
+                            {
+                                for self.choirs.iter().map(|choir| html! {
+                                    <option value={ choir.id.to_string() } selected={ Some(choir.id) == self.choir_id }>{ &choir.name }</option>
+                                })
+                            }
+                            </select>
+                        </div>
+                    }
+                </div>
+            </div>
+        }
+    }
+}
+
+/// Sets title and colors of the page to the ones of the choir.
+fn apply_branding(branding: &ChoirBranding) {
+    document().set_title(&t_with("choir-page-title", &[("choir", &branding.name)]));
+    apply_primary_color(branding.primary_color.clone().or_else(theme_color));
+}
+
+fn theme_color() -> Option<String> {
+    CONFIG
+        .get()
+        .expect("Config unset")
+        .theme
+        .primary_color
+        .clone()
+}
+
+/// Replaces the default red of the page with the given color.
+fn apply_primary_color(color: Option<String>) {
+    let css = match color.as_deref().and_then(branding_css) {
+        Some(css) => css,
+        None => return,
+    };
+
+    let document = document();
+
+    let style = match document.get_element_by_id(BRANDING_STYLE_ID) {
+        Some(style) => style,
+        None => {
+            let style = match document.create_element("style") {
+                Ok(style) => style,
+                Err(error) => {
+                    error!("Could not create style element for branding!");
+                    error!(error);
+                    return;
+                }
+            };
+            style.set_id(BRANDING_STYLE_ID);
+            if let Some(head) = document.head() {
+                if let Err(error) = head.append_child(&style) {
+                    error!("Could not add style element for branding!");
+                    error!(error);
+                }
+            }
+            style
+        }
+    };
+
+    style.set_text_content(Some(&css));
+}
+
+/// Builds the stylesheet that replaces the default red of the buttons and highlights with the color of the choir.
+/// Returns [None] for anything but a css hex color, so the backend cannot inject arbitrary css.
+fn branding_css(color: &str) -> Option<String> {
+    if !is_hex_color(color) {
+        return None;
+    }
+
+    Some(format!(
+        ".btn-danger, .btn-outline-danger:hover, .progress-bar {{ background-color: {color}; border-color: {color}; }}\n\
+        .btn-outline-danger {{ color: {color}; border-color: {color}; }}\n\
+        .btn-outline-danger:hover {{ color: #fff; }}\n\
+        .text-danger {{ color: {color} !important; }}"
+    ))
+}
+
+#[cfg(test)]
+mod choir_test {
+    use super::branding_css;
+
+    #[test]
+    fn hex_color_accepted() {
+        let css = branding_css("#8a1538").unwrap();
+        assert!(css.contains("background-color: #8a1538;"));
+    }
+
+    #[test]
+    fn other_css_rejected() {
+        assert_eq!(branding_css("red"), None);
+        assert_eq!(branding_css("#fff; } body { display: none"), None);
+    }
+}
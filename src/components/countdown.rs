@@ -0,0 +1,42 @@
+use gloo_timers::callback::Interval;
+use time::OffsetDateTime;
+use yew::{function_component, html, use_effect_with, use_state, Html, Properties};
+
+use crate::{
+    i18n::locale,
+    utilities::{
+        date::{format_datetime_human_readable, now},
+        relative_time::{format_remaining, Urgency},
+    },
+};
+
+/// Time between two updates of the countdown in milliseconds. Countdowns show minutes at most, so this is precise enough.
+const TICK_MILLIS: u32 = 30_000;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct CountdownProperties {
+    pub due: OffsetDateTime,
+}
+
+/// Shows the time until the deadline, colored by its urgency, and updates it while the page is open.
+/// The absolute date is shown when hovering it.
+#[function_component(Countdown)]
+pub fn countdown(props: &CountdownProperties) -> Html {
+    let current = use_state(now);
+
+    {
+        let current = current.clone();
+        use_effect_with((), move |_| {
+            let interval = Interval::new(TICK_MILLIS, move || current.set(now()));
+            move || drop(interval)
+        });
+    }
+
+    let urgency = Urgency::of(props.due - *current);
+
+    html! {
+        <span class={ urgency.css_class() } title={ format_datetime_human_readable(&props.due) }>
+            { format_remaining(props.due, *current, locale()) }
+        </span>
+    }
+}
@@ -3,7 +3,7 @@ use yew::{function_component, Callback, Children, Html, Properties};
 
 use yew::html;
 
-use crate::components::modal::Modal;
+use crate::{components::modal::Modal, i18n::t};
 
 #[derive(PartialEq, Properties)]
 pub struct DeleteModalProperties {
@@ -18,12 +18,12 @@ pub struct DeleteModalProperties {
 pub fn modal(props: &DeleteModalProperties) -> Html {
     let actions = vec![
         (
-            "Abbrechen".to_string(),
+            t("modal-cancel"),
             "btn btn-secondary".to_string(),
             props.on_cancel.clone(),
         ),
         (
-            "Löschen".to_string(),
+            t("modal-delete"),
             "btn btn-danger".to_string(),
             props.on_confirm.clone(),
         ),
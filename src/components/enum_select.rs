@@ -0,0 +1,58 @@
+use web_sys::HtmlSelectElement;
+use yew::{function_component, html, use_effect_with, Callback, Html, NodeRef, Properties};
+
+use crate::utilities::{callback::select_to_enum_callback_fallible, select_enum::SelectEnum};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct EnumSelectProperties<T: SelectEnum> {
+    pub id: String,
+    /// Name of the field in the form the select belongs to.
+    pub name: String,
+    pub selected: T,
+    #[prop_or_default]
+    pub on_input: Callback<Result<T, ()>>,
+    #[prop_or_default]
+    pub form: Option<String>,
+    #[prop_or(true)]
+    pub required: bool,
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+/// # Overview
+/// A ```<select>``` with one ```<option>``` for every value of a [SelectEnum].
+/// Changes are reported through ```on_input``` (see [select_to_enum_callback_fallible]).
+///
+/// Changing ```selected``` also changes the selection after the user already picked something else.
+#[function_component(EnumSelect)]
+pub fn enum_select<T: SelectEnum>(props: &EnumSelectProperties<T>) -> Html {
+    let select_ref = NodeRef::default();
+
+    {
+        let select_ref = select_ref.clone();
+        use_effect_with(props.selected.value(), move |value| {
+            if let Some(select) = select_ref.cast::<HtmlSelectElement>() {
+                select.set_value(value);
+            }
+        });
+    }
+
+    html! {
+        <select
+            ref={ select_ref }
+            id={ props.id.clone() }
+            name={ props.name.clone() }
+            class="form-control"
+            form={ props.form.clone() }
+            required={ props.required }
+            disabled={ props.disabled }
+            oninput={ select_to_enum_callback_fallible(props.on_input.clone()) }
+        >
+        {
+            for T::options().into_iter().map(|option| html! {
+                <option value={ option.value() } selected={ option == props.selected }>{ option.label() }</option>
+            })
+        }
+        </select>
+    }
+}
@@ -1,5 +1,3 @@
-use std::fmt::Display;
-
 use base64::{
     alphabet,
     engine::general_purpose::{self, GeneralPurpose},
@@ -15,51 +13,17 @@ use yew::prelude::*;
 
 use web_sys::{HtmlDocument, UrlSearchParams};
 
-use crate::service::CONFIG;
+use crate::{
+    components::view_as::get_view_as,
+    i18n::t,
+    service::{section::Section, CONFIG},
+};
 
 const JWT_ENGINE: GeneralPurpose =
     GeneralPurpose::new(&alphabet::STANDARD, general_purpose::NO_PAD);
 
 const EXPIRES_PAST: &str = "expires=Fri, 31 Dec 1999 23:59:59 GMT;";
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
-pub enum Section {
-    Soprano1,
-    Alto1,
-    Tenor1,
-    Bass1,
-    Soprano2,
-    Alto2,
-    Tenor2,
-    Bass2,
-    Conductor,
-    Instrument,
-}
-
-impl Display for Section {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s = match self {
-            Section::Soprano1 => "Sopran 1",
-            Section::Alto1 => "Alt 1",
-            Section::Tenor1 => "Tenor 1",
-            Section::Bass1 => "Bass 1",
-            Section::Soprano2 => "Sopran 2",
-            Section::Alto2 => "Alt 2",
-            Section::Tenor2 => "Tenor 2",
-            Section::Bass2 => "Bass 2",
-            Section::Conductor => "Dirigent",
-            Section::Instrument => "Instrument",
-        };
-        write!(f, "{}", s)
-    }
-}
-
-impl ToHtml for Section {
-    fn to_html(&self) -> Html {
-        html!(self)
-    }
-}
-
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
 pub struct PerformerData {
     pub section: Section,
@@ -67,6 +31,18 @@ pub struct PerformerData {
     pub name: String,
     pub is_admin: bool,
     pub exp: i64,
+    /// The choir the token was issued for. Projects, materials and submissions are scoped to it.
+    #[serde(default)]
+    pub choir_id: Option<i64>,
+    /// All choirs the user is a member of.
+    #[serde(default)]
+    pub choirs: Vec<ChoirMembership>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct ChoirMembership {
+    pub id: i64,
+    pub name: String,
 }
 
 #[derive(Properties, Debug, PartialEq)]
@@ -112,10 +88,26 @@ pub fn get_token() -> String {
     "".to_string()
 }
 
+/// Retrieves the data of the user the portal is rendered for.
+/// This is the authenticated user, unless an admin currently views the portal as another member (see [crate::components::view_as]).
+/// In that case, the member's identity is returned without admin rights.
+pub fn get_token_data() -> Result<PerformerData, ()> {
+    let user = get_authenticated_token_data()?;
+
+    if !user.is_admin {
+        return Ok(user);
+    }
+
+    match get_view_as() {
+        Some(identity) => Ok(identity.apply_to(user)),
+        None => Ok(user),
+    }
+}
+
 /// Retrieves the token data from the JWT from local storage or URL parameter that is used to authenticate the user.
 /// If retrieving out of a query parameter, sets it in the local storage.
 /// Redirects to the endpoint configured to get a new token if none was found or it is expired.
-pub fn get_token_data() -> Result<PerformerData, ()> {
+pub fn get_authenticated_token_data() -> Result<PerformerData, ()> {
     let token = get_token();
 
     let split_token = token.split('.').collect::<Vec<&str>>();
@@ -162,7 +154,7 @@ pub fn get_token_data() -> Result<PerformerData, ()> {
         }
     };
 
-    if data.exp < OffsetDateTime::now_local().unwrap().unix_timestamp() {
+    if data.exp < OffsetDateTime::now_utc().unix_timestamp() {
         error!("JWT expired! Redirecting...");
         redirect_to_login();
         return Err(());
@@ -204,10 +196,7 @@ fn unset_jwt_cookie(doc: &HtmlDocument, domain: &str) {
 fn show_cookie_set_error(err: JsValue) {
     error!("Could not set cookie. Error:");
     error!(err);
-    alert(
-        "Konnte kein Cookie setzen. Sind Cookies für diese Seite eingeschaltet? 
-    Cookies sind erforderlich, damit diese Seite funktioniert.",
-    )
+    alert(&t("jwt-cookie-failed"))
 }
 
 /// Tries to read a JWT from the "token" url parameter.
@@ -240,3 +229,16 @@ fn redirect_to_login() {
 
     window().location().set_href(auth_url).unwrap_throw();
 }
+
+/// Requests a token for another choir of the user from the authentication URL.
+/// The user is led back to this website with the new token afterwards.
+pub fn switch_choir(choir_id: i64) {
+    let auth_url = &CONFIG.get().unwrap().auth_url;
+    let separator = if auth_url.contains('?') { '&' } else { '?' };
+    let url = format!("{auth_url}{separator}choir={choir_id}");
+    info!(&format!(
+        "Switching to choir {choir_id}, redirecting to {url}"
+    ));
+
+    window().location().set_href(&url).unwrap_throw();
+}
@@ -1,12 +1,14 @@
 use yew::{function_component, html, Html};
 
+use crate::i18n::t;
+
 #[function_component(LoadingSpinner)]
 pub fn loading_spinner() -> Html {
     html! {
         <>
-            { "Lade..." }
+            { t("loading") }
             <div class="spinner-border text-danger" style="width: 1rem; height: 1rem;" role="status">
-                <span class="visually-hidden">{ "Lade-Animation" } </span>
+                <span class="visually-hidden">{ t("loading-animation") } </span>
             </div>
         </>
     }
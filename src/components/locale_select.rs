@@ -0,0 +1,30 @@
+use gloo_console::error;
+use yew::{function_component, html, Callback, Html};
+
+use crate::{
+    components::enum_select::EnumSelect,
+    i18n::{locale, set_locale, t, Locale},
+};
+
+/// Lets the user choose the language of the portal. The choice is kept in the local storage of the browser.
+#[function_component(LocaleSelect)]
+pub fn locale_select() -> Html {
+    let current = locale();
+
+    let on_input = Callback::from(move |selected: Result<Locale, ()>| match selected {
+        Ok(selected) if selected != current => set_locale(selected),
+        Ok(_) => {}
+        Err(_) => error!("Selected locale could not be parsed!"),
+    });
+
+    html! {
+        <div class="container">
+            <div class="row mt-2 justify-content-end">
+                <div class="col-auto">
+                    <label for="selectLocale" class="visually-hidden">{ t("locale-select") }</label>
+                    <EnumSelect<Locale> id="selectLocale" name="locale" selected={ current } on_input={ on_input }/>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -1,25 +1,36 @@
 use yew::{html, Html};
 
-use crate::service::material::{material_url, MaterialTo};
+use crate::{
+    components::material::{mixer::Mixer, practice::PracticePlayer},
+    i18n::t,
+    service::material::MaterialTo,
+};
 
 pub fn audio_list(id: i64, audio_elements: Vec<&MaterialTo>) -> Html {
     html! {
         <>
         <div class="row mt-2">
             <div class="col">
-                <h4>{ "Playbacks" }</h4>
+                <h4>{ t("material-audio-title") }</h4>
             </div>
         </div>
+        if audio_elements.len() > 1 {
+            <div class="row">
+                <div class="col">
+                    <Mixer project_id={ id } tracks={ audio_elements.iter().map(|audio| (*audio).clone()).collect::<Vec<_>>() } />
+                </div>
+            </div>
+        }
         <div class="row">
         {
             if audio_elements.is_empty() {
                 html!{
-                    <p>{ "Noch keine Audiodateien gefunden!" }</p>
+                    <p>{ t("material-audio-empty") }</p>
                 }
             } else {
                 html! {
-                    for audio_elements.iter().map(move |audio|  {
-                        audio_element(id, (*audio).clone())
+                    for audio_elements.iter().map(move |audio|  html! {
+                        <PracticePlayer project_id={ id } material={ (*audio).clone() } />
                     })
                 }
             }
@@ -28,13 +39,3 @@ pub fn audio_list(id: i64, audio_elements: Vec<&MaterialTo>) -> Html {
         </>
     }
 }
-
-fn audio_element(id: i64, audio: MaterialTo) -> Html {
-    html! {
-        <div class="col">
-            <h5> { &audio.title } </h5>
-            <audio controls=true id={ format!("audio-{}", audio.id)} src={ material_url(id, &audio.file_technical_name) }></audio>
-            <h6> <i> { &audio.file_name } </i> </h6>
-        </div>
-    }
-}
@@ -0,0 +1,49 @@
+use gloo_console::error;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use yew::{function_component, html, Html, Properties};
+
+use crate::{i18n::t, service::material::Marker, utilities::markers::format_marker_time};
+
+#[wasm_bindgen(module = "/js/custom/practice.js")]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn play_from(id: &str, seconds: f64) -> Result<(), JsValue>;
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ChaptersProperties {
+    /// Id of the audio or video element the markers belong to.
+    pub element_id: String,
+    pub markers: Vec<Marker>,
+}
+
+/// The rehearsal markers of a material, clicking one plays the material from there.
+#[function_component(Chapters)]
+pub fn chapters(props: &ChaptersProperties) -> Html {
+    if props.markers.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="list-group list-group-flush small mb-2" title={ t("markers-title") }>
+            { for props.markers.iter().map(|marker| {
+                let element_id = props.element_id.clone();
+                let seconds = marker.position_ms as f64 / 1000.0;
+                html! {
+                    <button
+                        type="button"
+                        class="list-group-item list-group-item-action py-1"
+                        onclick={ move |_| {
+                            if let Err(js_error) = play_from(&element_id, seconds) {
+                                error!(js_error);
+                            }
+                        }}
+                    >
+                        <span class="font-monospace me-2">{ format_marker_time(marker.position_ms) }</span>
+                        { &marker.name }
+                    </button>
+                }
+            }) }
+        </div>
+    }
+}
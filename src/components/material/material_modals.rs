@@ -1,17 +1,27 @@
+use gloo_console::error;
+use gloo_dialogs::alert;
 use wasm_bindgen::UnwrapThrowExt;
-use yew::{function_component, html, Callback, Component, Html, Properties};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Event, HtmlInputElement, HtmlTextAreaElement, InputEvent};
+use yew::{function_component, html, Callback, Component, Html, Properties, TargetCast};
 
 use crate::{
-    components::{modal::Modal, upload::Upload},
+    components::{enum_select::EnumSelect, modal::Modal, upload::Upload},
+    i18n::{t, t_with},
     pages::home::{get_input_text_content, get_selected_value},
     service::material::{
-        material_upload_url, update_material, MaterialCategory, MaterialTo, UpdateMaterial,
+        material_upload_url, update_markers, update_material, MaterialCategory, MaterialTo,
+        UpdateMaterial,
+    },
+    utilities::{
+        markers::{format_markers, parse_markers},
+        requests::fetch::FetchError,
     },
-    utilities::requests::fetch::FetchError,
 };
 
 pub const MODAL_MATERIAL_UPDATE: &str = "modalMaterialUpdate";
 pub const MODAL_MATERIAL_UPLOAD: &str = "modalMaterialUpload";
+pub const MODAL_MATERIAL_MARKERS: &str = "modalMaterialMarkers";
 const INPUT_UPDATE_MATERIAL_TITLE: &str = "inputUpdateMaterialTitle";
 const INPUT_UPDATE_MATERIAL_CATEGORY: &str = "inputUpdateMaterialCategory";
 
@@ -27,7 +37,7 @@ pub fn material_upload_modal(props: &MaterialUploadModalProperties) -> Html {
     let props = props.clone();
     html! {
         <Modal
-            title={"Übungsmaterial hochladen".to_string() }
+            title={ t("material-upload-title") }
             id={ MODAL_MATERIAL_UPLOAD.to_string() }
             actions = { vec![] }
         >
@@ -126,19 +136,19 @@ impl Component for MaterialChangeModal {
     fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
         let actions = vec![
             (
-                "Abbrechen".to_string(),
+                t("modal-cancel"),
                 "btn btn-secondary".to_string(),
                 ctx.link().callback(|_| Msg::Cancel),
             ),
             (
-                "Anpassen".to_string(),
+                t("material-update-save"),
                 "btn btn-danger".to_string(),
                 ctx.link().callback(|_| Msg::Confirm),
             ),
         ];
 
         html! {
-            <Modal id={MODAL_MATERIAL_UPDATE} title="Übungsmaterial anpassen" actions={ actions }>
+            <Modal id={MODAL_MATERIAL_UPDATE} title={ t("material-update-title") } actions={ actions }>
                 {
                     if let Some(material) = &ctx.props().material_to_change {
                         html!{
@@ -152,7 +162,7 @@ impl Component for MaterialChangeModal {
                             </div>
                         }
                     } else {
-                        html!{ { "Nicht zum Update ausgewählt!" } }
+                        html!{ { t("material-update-none-selected") } }
                     }
                 }
                 </Modal>
@@ -160,6 +170,179 @@ impl Component for MaterialChangeModal {
     }
 }
 
+#[derive(Clone, PartialEq, Properties)]
+pub struct MaterialMarkersModalProperties {
+    pub on_cancel: Callback<()>,
+    pub on_success: Callback<MaterialTo>,
+    pub on_error: Callback<FetchError>,
+    pub material: Option<MaterialTo>,
+}
+
+/// Edits the rehearsal markers of a material as text, one per line. Text files and Audacity label tracks can be imported.
+pub struct MaterialMarkersModal {
+    text: String,
+}
+
+pub enum MarkersMsg {
+    TextInput(InputEvent),
+    FileInput(Event),
+    FileRead(String),
+    FileReadFailed(String),
+    Cancel,
+    Confirm,
+    Success(MaterialTo),
+    Error(FetchError),
+}
+
+impl Component for MaterialMarkersModal {
+    type Message = MarkersMsg;
+
+    type Properties = MaterialMarkersModalProperties;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        Self {
+            text: markers_text(&ctx.props().material),
+        }
+    }
+
+    fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().material != old_props.material {
+            self.text = markers_text(&ctx.props().material);
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            MarkersMsg::TextInput(event) => {
+                let input: HtmlTextAreaElement = event.target_unchecked_into();
+                self.text = input.value();
+                true
+            }
+            MarkersMsg::FileInput(event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                    return false;
+                };
+                ctx.link().send_future(async move {
+                    match JsFuture::from(file.text()).await {
+                        Ok(content) => {
+                            MarkersMsg::FileRead(content.as_string().unwrap_or_default())
+                        }
+                        Err(error) => MarkersMsg::FileReadFailed(format!("{error:?}")),
+                    }
+                });
+                false
+            }
+            MarkersMsg::FileRead(content) => {
+                // Label tracks are shown in the simpler format, invalid files as they are, so the error can be found.
+                self.text = match parse_markers(&content) {
+                    Ok(markers) => format_markers(&markers),
+                    Err(_) => content,
+                };
+                true
+            }
+            MarkersMsg::FileReadFailed(message) => {
+                error!(format!("Could not read marker file: {message}"));
+                alert(&t("markers-file-failed"));
+                false
+            }
+            MarkersMsg::Cancel => {
+                ctx.props().on_cancel.emit(());
+                false
+            }
+            MarkersMsg::Confirm => {
+                let Some(material) = &ctx.props().material else {
+                    return false;
+                };
+                let markers = match parse_markers(&self.text) {
+                    Ok(markers) => markers,
+                    Err(problem) => {
+                        alert(&problem.message());
+                        return false;
+                    }
+                };
+                let material_id = material.id;
+                ctx.link().send_future(async move {
+                    match update_markers(material_id, markers).await {
+                        Ok(material) => MarkersMsg::Success(material),
+                        Err(error) => MarkersMsg::Error(error),
+                    }
+                });
+                false
+            }
+            MarkersMsg::Success(material) => {
+                ctx.props().on_success.emit(material);
+                false
+            }
+            MarkersMsg::Error(error) => {
+                ctx.props().on_error.emit(error);
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> Html {
+        let actions = vec![
+            (
+                t("modal-cancel"),
+                "btn btn-secondary".to_string(),
+                ctx.link().callback(|_| MarkersMsg::Cancel),
+            ),
+            (
+                t("material-update-save"),
+                "btn btn-danger".to_string(),
+                ctx.link().callback(|_| MarkersMsg::Confirm),
+            ),
+        ];
+
+        html! {
+            <Modal id={ MODAL_MATERIAL_MARKERS } title={ t("markers-edit-title") } actions={ actions }>
+                if let Some(material) = &ctx.props().material {
+                    <p><b>{ &material.title }</b></p>
+                    <label for="textareaMaterialMarkers" class="form-label">{ t("markers-input") }</label>
+                    <textarea
+                        id="textareaMaterialMarkers"
+                        class="form-control font-monospace"
+                        rows="8"
+                        placeholder={ t("markers-input-placeholder") }
+                        value={ self.text.clone() }
+                        oninput={ ctx.link().callback(MarkersMsg::TextInput) }
+                    />
+                    {
+                        match parse_markers(&self.text) {
+                            Ok(markers) => html! {
+                                <p class="form-text">{ t_with("markers-count", &[("count", &markers.len().to_string())]) }</p>
+                            },
+                            Err(problem) => html! {
+                                <p class="form-text text-danger">{ problem.message() }</p>
+                            },
+                        }
+                    }
+                    <label for="inputMaterialMarkersFile" class="form-label">{ t("markers-import") }</label>
+                    <input
+                        id="inputMaterialMarkersFile"
+                        type="file"
+                        class="form-control"
+                        accept=".txt,text/plain"
+                        onchange={ ctx.link().callback(MarkersMsg::FileInput) }
+                    />
+                    <p class="form-text mb-0">{ t("markers-import-help") }</p>
+                } else {
+                    { t("material-update-none-selected") }
+                }
+            </Modal>
+        }
+    }
+}
+
+fn markers_text(material: &Option<MaterialTo>) -> String {
+    material
+        .as_ref()
+        .map(|material| format_markers(&material.markers))
+        .unwrap_or_default()
+}
+
 #[derive(Clone, PartialEq, Properties)]
 struct InputMaterialTitleProperties {
     #[prop_or("inputMaterialTitle".to_string())]
@@ -176,13 +359,13 @@ fn input_material_title(props: &InputMaterialTitleProperties) -> Html {
     let props = props.clone();
     html! (
         <>
-        <label for={props.id.clone()}>{ "Name für die Datei" }</label>
+        <label for={props.id.clone()}>{ t("material-input-title") }</label>
         <input
             id={props.id}
             type="text"
             class="form-control"
             name={props.name}
-            placeholder="Titel der Datei"
+            placeholder={ t("material-input-title-placeholder") }
             value={ props.value.clone() }
             form={props.form}
         />
@@ -206,13 +389,8 @@ fn input_material_category(props: &InputMaterialCategoryProperties) -> Html {
     let props = props.clone();
     html! {
         <>
-        <label for={props.id.clone()}>{ "Art der Datei (Playback, ...)" }</label>
-        <select id={props.id} name={props.name} class="form-control" form={props.form}>
-            <option value="Audio" selected={props.selected == MaterialCategory::Audio}>{ "Audio" }</option>
-            <option value="Video" selected={props.selected == MaterialCategory::Video}>{ "Video" }</option>
-            <option value="Sheet" selected={props.selected == MaterialCategory::SheetMusic}>{ "Noten" }</option>
-            <option value="Other" selected={props.selected == MaterialCategory::Other}>{ "Sonstiges" }</option>
-        </select>
+        <label for={props.id.clone()}>{ t("material-input-category") }</label>
+        <EnumSelect<MaterialCategory> id={props.id} name={props.name} form={props.form} selected={props.selected} required=false/>
         </>
     }
 }
@@ -0,0 +1,303 @@
+use std::collections::BTreeMap;
+
+use gloo_console::error;
+use gloo_dialogs::alert;
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlInputElement, InputEvent};
+use yew::{html, Component, Context, Html, Properties, TargetCast};
+
+use crate::{
+    components::{
+        material::member_storage_key,
+        mixer_transport::{MixerTransport, TransportMsg},
+    },
+    i18n::{t, t_with},
+    service::material::{material_url, MaterialTo},
+};
+
+/// Name of the mixer in `mixer.js`, the submissions are mixed separately.
+const MIXER_NAME: &str = "material";
+
+/// Volume of a track nobody changed yet.
+const DEFAULT_VOLUME: f64 = 0.8;
+
+/// How a member wants to hear a track, e.g. their own part loud and the others soft.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrackSetting {
+    /// Between 0 and 1.
+    pub volume: f64,
+    pub muted: bool,
+    pub solo: bool,
+}
+
+impl Default for TrackSetting {
+    fn default() -> Self {
+        Self {
+            volume: DEFAULT_VOLUME,
+            muted: false,
+            solo: false,
+        }
+    }
+}
+
+/// Mix of a project, by material id.
+type Mix = BTreeMap<i64, TrackSetting>;
+
+pub enum Msg {
+    Transport(TransportMsg),
+    Loaded(Result<f64, String>),
+    Volume(i64, InputEvent),
+    Mute(i64),
+    Solo(i64),
+    Reset,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct MixerProperties {
+    pub project_id: i64,
+    /// Audio material, one track each.
+    pub tracks: Vec<MaterialTo>,
+}
+
+/// Plays all audio material of a project in sync, with volume, mute and solo per track.
+/// The mix is remembered per member and project in the browser.
+pub struct Mixer {
+    transport: MixerTransport,
+    mix: Mix,
+}
+
+impl Component for Mixer {
+    type Message = Msg;
+    type Properties = MixerProperties;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            transport: MixerTransport::new(MIXER_NAME),
+            mix: LocalStorage::get(member_storage_key("mix", ctx.props().project_id))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().tracks != old_props.tracks {
+            // The tracks have to be loaded again with the new material.
+            self.transport.close();
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Transport(TransportMsg::Play) if self.transport.is_unloaded() => {
+                let project_id = ctx.props().project_id;
+                let urls = ctx
+                    .props()
+                    .tracks
+                    .iter()
+                    .map(|track| material_url(project_id, &track.file_technical_name))
+                    .collect();
+                self.transport.load(ctx.link(), urls, Msg::Loaded);
+                true
+            }
+            Msg::Transport(msg) => self.transport.update(ctx.link(), msg, Msg::Transport),
+            Msg::Loaded(Ok(duration)) => {
+                self.transport.loaded(duration);
+                self.apply_gains(ctx);
+                ctx.link().send_message(Msg::Transport(TransportMsg::Play));
+                true
+            }
+            Msg::Loaded(Err(message)) => {
+                error!(format!("Could not load the tracks of the mixer: {message}"));
+                alert(&t("mixer-load-failed"));
+                self.transport.load_failed();
+                true
+            }
+            Msg::Volume(material_id, event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                self.setting(material_id).volume = input.value_as_number().clamp(0.0, 1.0);
+                self.mix_changed(ctx);
+                true
+            }
+            Msg::Mute(material_id) => {
+                let setting = self.setting(material_id);
+                setting.muted = !setting.muted;
+                self.mix_changed(ctx);
+                true
+            }
+            Msg::Solo(material_id) => {
+                let setting = self.setting(material_id);
+                setting.solo = !setting.solo;
+                self.mix_changed(ctx);
+                true
+            }
+            Msg::Reset => {
+                self.mix.clear();
+                self.mix_changed(ctx);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="card mb-2">
+                <div class="card-body">
+                    <h5 class="card-title">{ t("mixer-title") }</h5>
+                    { self.transport.view(ctx.link(), Msg::Transport, Html::default()) }
+                    <table class="table table-sm align-middle mt-2 mb-0">
+                        <tbody>
+                        { for ctx.props().tracks.iter().map(|track| self.track_row(ctx, track)) }
+                        </tbody>
+                    </table>
+                    <button type="button" class="btn btn-sm btn-link px-0" onclick={ ctx.link().callback(|_| Msg::Reset) }>
+                        { t("mixer-reset") }
+                    </button>
+                </div>
+            </div>
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.transport.close();
+    }
+}
+
+impl Mixer {
+    fn track_row(&self, ctx: &Context<Self>, track: &MaterialTo) -> Html {
+        let material_id = track.id;
+        let setting = self.mix.get(&material_id).copied().unwrap_or_default();
+
+        html! {
+            <tr>
+                <td>{ &track.title }</td>
+                <td class="w-50">
+                    <input
+                        type="range"
+                        class="form-range"
+                        min="0"
+                        max="1"
+                        step="0.05"
+                        value={ setting.volume.to_string() }
+                        title={ t_with("mixer-volume", &[("title", &track.title)]) }
+                        oninput={ ctx.link().callback(move |event| Msg::Volume(material_id, event)) }
+                    />
+                </td>
+                <td class="text-nowrap">
+                    <button
+                        type="button"
+                        class={ if setting.muted { "btn btn-sm btn-secondary me-1" } else { "btn btn-sm btn-outline-secondary me-1" } }
+                        onclick={ ctx.link().callback(move |_| Msg::Mute(material_id)) }
+                    >
+                        { t("mixer-mute") }
+                    </button>
+                    <button
+                        type="button"
+                        class={ if setting.solo { "btn btn-sm btn-warning" } else { "btn btn-sm btn-outline-warning" } }
+                        onclick={ ctx.link().callback(move |_| Msg::Solo(material_id)) }
+                    >
+                        { t("mixer-solo") }
+                    </button>
+                </td>
+            </tr>
+        }
+    }
+
+    fn setting(&mut self, material_id: i64) -> &mut TrackSetting {
+        self.mix.entry(material_id).or_default()
+    }
+
+    fn mix_changed(&mut self, ctx: &Context<Self>) {
+        self.apply_gains(ctx);
+        if let Err(error) =
+            LocalStorage::set(member_storage_key("mix", ctx.props().project_id), &self.mix)
+        {
+            error!(format!("Could not store the mix: {error}"));
+        }
+    }
+
+    fn apply_gains(&self, ctx: &Context<Self>) {
+        self.transport
+            .set_gains(&effective_gains(&ctx.props().tracks, &self.mix));
+    }
+}
+
+/// The volume each track is played with: if any track is solo, only the solo tracks are heard.
+fn effective_gains(tracks: &[MaterialTo], mix: &Mix) -> Vec<f64> {
+    let any_solo = tracks
+        .iter()
+        .any(|track| mix.get(&track.id).is_some_and(|setting| setting.solo));
+
+    tracks
+        .iter()
+        .map(|track| {
+            let setting = mix.get(&track.id).copied().unwrap_or_default();
+            if setting.muted || (any_solo && !setting.solo) {
+                0.0
+            } else {
+                setting.volume
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod mixer_test {
+    use time::macros::datetime;
+
+    use crate::service::material::{MaterialCategory, MaterialTo};
+
+    use super::{effective_gains, Mix, TrackSetting, DEFAULT_VOLUME};
+
+    fn track(id: i64) -> MaterialTo {
+        MaterialTo {
+            id,
+            project_id: 1,
+            title: format!("Track {id}"),
+            file_name: format!("track{id}.mp3"),
+            file_technical_name: format!("{id}.mp3"),
+            creator: 1,
+            upload_at: datetime!(2026-10-18 12:00 UTC),
+            category: MaterialCategory::Audio,
+            purge_at: None,
+            markers: Vec::new(),
+        }
+    }
+
+    fn setting(volume: f64, muted: bool, solo: bool) -> TrackSetting {
+        TrackSetting {
+            volume,
+            muted,
+            solo,
+        }
+    }
+
+    #[test]
+    fn untouched_tracks_play_at_default_volume() {
+        let tracks = [track(1), track(2)];
+        assert_eq!(
+            effective_gains(&tracks, &Mix::new()),
+            vec![DEFAULT_VOLUME, DEFAULT_VOLUME]
+        );
+    }
+
+    #[test]
+    fn muted_tracks_are_silent() {
+        let tracks = [track(1), track(2)];
+        let mix = Mix::from([
+            (1, setting(1.0, false, false)),
+            (2, setting(0.5, true, false)),
+        ]);
+        assert_eq!(effective_gains(&tracks, &mix), vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn solo_silences_other_tracks() {
+        let tracks = [track(1), track(2), track(3)];
+        let mix = Mix::from([
+            (2, setting(0.6, false, true)),
+            (3, setting(0.3, true, true)),
+        ]);
+        assert_eq!(effective_gains(&tracks, &mix), vec![0.0, 0.6, 0.0]);
+    }
+}
@@ -1,6 +1,9 @@
 mod audio;
+mod chapters;
 mod material_modals;
+mod mixer;
 mod other_and_all;
+mod practice;
 mod sheet;
 mod video;
 
@@ -14,11 +17,18 @@ use crate::{
     components::{
         admin_only::AdminOrOwner,
         delete_modal::DeleteModal,
+        jwt_context::get_token_data,
         material::material_modals::{
-            MaterialChangeModal, MaterialUploadModal, MODAL_MATERIAL_UPDATE, MODAL_MATERIAL_UPLOAD,
+            MaterialChangeModal, MaterialMarkersModal, MaterialUploadModal, MODAL_MATERIAL_MARKERS,
+            MODAL_MATERIAL_UPDATE, MODAL_MATERIAL_UPLOAD,
         },
+        trash::{TrashItem, TrashList},
+    },
+    i18n::t,
+    service::material::{
+        delete_material, deleted_material_by_project, material_by_project, purge_material,
+        restore_material, MaterialCategory, MaterialTo,
     },
-    service::material::{delete_material, material_by_project, MaterialCategory, MaterialTo},
     utilities::requests::fetch::FetchError,
 };
 
@@ -32,6 +42,9 @@ pub struct Material {
     pub material: Vec<MaterialTo>,
     change_selected_material: Option<MaterialTo>,
     delete_selected_material: Option<MaterialTo>,
+    markers_selected_material: Option<MaterialTo>,
+    /// Deleted material that can be restored. Only loaded for the owner of the project and admins.
+    deleted_material: Vec<MaterialTo>,
 }
 
 pub enum Msg {
@@ -41,6 +54,24 @@ pub enum Msg {
     MaterialFetchError(FetchError),
     Update(UpdateMessage),
     Delete(DeleteMessage),
+    Markers(MarkersMessage),
+    Trash(TrashMessage),
+}
+
+pub enum MarkersMessage {
+    ButtonClick(MaterialTo),
+    Success(MaterialTo),
+    Error(FetchError),
+    Cancel,
+}
+
+pub enum TrashMessage {
+    Loaded(Vec<MaterialTo>),
+    Restore(i64),
+    Restored(MaterialTo),
+    Purge(i64),
+    Purged(i64),
+    Error(FetchError),
 }
 
 pub enum UpdateMessage {
@@ -80,10 +111,20 @@ impl Component for Material {
             }
         });
 
+        let may_edit = get_token_data()
+            .map(|user| user.is_admin || user.user_id == ctx.props().project_owner)
+            .unwrap_or(false);
+
+        if may_edit {
+            load_deleted_material(ctx);
+        }
+
         Self {
             material: Vec::new(),
             change_selected_material: None,
             delete_selected_material: None,
+            markers_selected_material: None,
+            deleted_material: Vec::new(),
         }
     }
 
@@ -95,7 +136,7 @@ impl Component for Material {
                 <div class="row mt-2">
                     <div class="col text-end">
                         <button type="button" class="btn btn-danger" data-bs-toggle="modal" data-bs-target={format!("#{MODAL_MATERIAL_UPLOAD}")}>
-                            { "Übungsmaterial hinzufügen" }
+                            { t("material-add") }
                         </button>
                     </div>
                 </div>
@@ -131,18 +172,37 @@ impl Component for Material {
                     material_to_change={self.change_selected_material.clone()}
                 />
 
+                <MaterialMarkersModal
+                    on_cancel={ctx.link().callback(|_| Msg::Markers(MarkersMessage::Cancel))}
+                    on_error={ctx.link().callback(|error| Msg::Markers(MarkersMessage::Error(error)))}
+                    on_success={ctx.link().callback(|material| Msg::Markers(MarkersMessage::Success(material)))}
+                    material={self.markers_selected_material.clone()}
+                />
+
                 <DeleteModal id={MODAL_MATERIAL_DELETE}
-                    title="Material wirklich löschen?"
+                    title={ t("material-delete-title") }
                     on_cancel={ ctx.link().callback(|e| Msg::Delete(DeleteMessage::AbortClick(e))) }
                     on_confirm={ ctx.link().callback(|e| Msg::Delete(DeleteMessage::AcceptClick(e)))  }
                 >
                     if let Some(mat) = &self.delete_selected_material {
-                        <p> { "Beschreibung: " } { &mat.title } </p>
-                        <p> { "Dateiname: " } <i> { &mat.file_name } </i> </p>
+                        <p> { t("material-description") } { " " } { &mat.title } </p>
+                        <p> { t("material-file-name") } { " " } <i> { &mat.file_name } </i> </p>
                     } else {
-                        { "Kein zu löschendes Element ausgewählt!" }
+                        { t("material-none-selected") }
                     }
                 </DeleteModal>
+
+                <hr class="bg-secondary border-1 border-top border-secondary"/>
+                <h5>{ t("material-trash") }</h5>
+                <TrashList
+                    items={ self.deleted_material.iter().map(|material| TrashItem {
+                        id: material.id,
+                        name: format!("{} ({})", material.title, material.file_name),
+                        purge_at: material.purge_at,
+                    }).collect::<Vec<_>>() }
+                    on_restore={ ctx.link().callback(|id| Msg::Trash(TrashMessage::Restore(id))) }
+                    on_purge={ ctx.link().callback(|id| Msg::Trash(TrashMessage::Purge(id))) }
+                />
             </AdminOrOwner>
             </>
         }
@@ -156,7 +216,7 @@ impl Component for Material {
                 true
             }
             Msg::MaterialUploadError(text) => {
-                alert("Ein Fehler ist aufgetreten. Bitte versuche es erneut und wende dich dann an den/die Administrator*in");
+                alert(&t("material-upload-failed"));
                 error!(format!(
                     "got non successful status from metadata upload. Error text: {}",
                     text
@@ -198,6 +258,7 @@ impl Component for Material {
                     };
 
                     self.material.retain(|entry| entry.id != item.id);
+                    load_deleted_material(ctx);
 
                     true
                 }
@@ -205,23 +266,85 @@ impl Component for Material {
                     match error {
                         FetchError::StatusCode(code) => {
                             if code == 404 {
-                                alert("Die Datei wurde bereits gelöscht!");
+                                alert(&t("material-already-deleted"));
                                 warn!("Could not delete material, got 404.");
                                 return false;
                             }
-                            alert(&format!("Could not delete material, got status {code}"));
-                            warn!(format!(
-                                "Konnte die Datei nicht löschen, Statuscode {}",
-                                code
-                            ))
+                            warn!(format!("Could not delete material, got status {code}"))
                         }
                         _ => warn!("Could not delete material!"),
                     }
-                    alert("Die Datei konnte nicht gelöscht werden! Details siehe Konsole.");
+                    alert(&t("material-delete-failed"));
                     warn!(format!("{:?}", error));
                     false
                 }
             },
+            Msg::Markers(message) => match message {
+                MarkersMessage::ButtonClick(material) => {
+                    self.markers_selected_material = Some(material);
+                    true
+                }
+                MarkersMessage::Success(updated) => {
+                    self.material.retain(|entry| entry.id != updated.id);
+                    self.material.push(updated);
+                    sort_material(&mut self.material);
+                    self.markers_selected_material = None;
+                    true
+                }
+                MarkersMessage::Error(error) => {
+                    alert(&t("material-update-failed"));
+                    error!(error.to_string());
+                    self.markers_selected_material = None;
+                    true
+                }
+                MarkersMessage::Cancel => {
+                    self.markers_selected_material = None;
+                    true
+                }
+            },
+            Msg::Trash(message) => match message {
+                TrashMessage::Loaded(material) => {
+                    self.deleted_material = material;
+                    true
+                }
+                TrashMessage::Restore(material_id) => {
+                    ctx.link().send_future(async move {
+                        match restore_material(material_id).await {
+                            Ok(material) => Msg::Trash(TrashMessage::Restored(material)),
+                            Err(error) => Msg::Trash(TrashMessage::Error(error)),
+                        }
+                    });
+                    false
+                }
+                TrashMessage::Restored(material) => {
+                    self.deleted_material
+                        .retain(|entry| entry.id != material.id);
+                    self.material.push(material);
+                    sort_material(&mut self.material);
+                    true
+                }
+                TrashMessage::Purge(material_id) => {
+                    ctx.link().send_future(async move {
+                        match purge_material(material_id).await {
+                            Ok(()) => Msg::Trash(TrashMessage::Purged(material_id)),
+                            Err(error) => Msg::Trash(TrashMessage::Error(error)),
+                        }
+                    });
+                    false
+                }
+                TrashMessage::Purged(material_id) => {
+                    self.deleted_material
+                        .retain(|entry| entry.id != material_id);
+                    true
+                }
+                TrashMessage::Error(error) => {
+                    error!(format!(
+                        "Could not change the trash of the material: {error}"
+                    ));
+                    alert(&t("trash-failed"));
+                    false
+                }
+            },
             Msg::MaterialFetchSuccess(material) => {
                 self.material = material;
                 sort_material(&mut self.material);
@@ -229,7 +352,7 @@ impl Component for Material {
             }
             Msg::MaterialFetchError(error) => {
                 error!(format!("Could not fetch material! Error: {}", error));
-                alert("Fehler: Konnte das Material nicht laden. Überprüfe Deine Internetverbindung, lade die Seite neu und wende Dich ansonsten an den/die Administrator*in.");
+                alert(&t("material-load-failed"));
                 false
             }
             Msg::Update(message) => match message {
@@ -247,7 +370,7 @@ impl Component for Material {
                     true
                 }
                 UpdateMessage::Error(error) => {
-                    alert("Konnte die neuen Daten nicht speichern! Überprüfe deine Internetverbindung, versuche es erneut und wende dich dann an den/die Administrator*in. Details siehe Konsole.");
+                    alert(&t("material-update-failed"));
                     error!(error.to_string());
                     self.change_selected_material = None;
                     true
@@ -277,7 +400,25 @@ pub fn update_button(props: &MaterialUpdateButtonProperties) -> Html {
 
     html! {
     <AdminOrOwner owner_id={props.owner_id}>
-        <button type="button" class="btn btn-outline-danger btn-sm" data-bs-toggle="modal" data-bs-target={format!("#{MODAL_MATERIAL_UPDATE}")} onclick={props.onclick}> { "Ändern" } </button>
+        <button type="button" class="btn btn-outline-danger btn-sm" data-bs-toggle="modal" data-bs-target={format!("#{MODAL_MATERIAL_UPDATE}")} onclick={props.onclick}> { t("material-change") } </button>
+    </AdminOrOwner>
+    }
+}
+
+/// Opens the editor of the rehearsal markers, only for audio and video material.
+#[derive(Clone, PartialEq, Properties)]
+pub struct MaterialMarkersButtonProperties {
+    pub owner_id: i64,
+    pub onclick: Callback<MouseEvent>,
+}
+
+#[function_component(MaterialMarkersButton)]
+pub fn markers_button(props: &MaterialMarkersButtonProperties) -> Html {
+    let props = props.clone();
+
+    html! {
+    <AdminOrOwner owner_id={props.owner_id}>
+        <button type="button" class="btn btn-outline-danger btn-sm" data-bs-toggle="modal" data-bs-target={format!("#{MODAL_MATERIAL_MARKERS}")} onclick={props.onclick}> { t("material-markers") } </button>
     </AdminOrOwner>
     }
 }
@@ -294,7 +435,25 @@ pub fn delete_button(props: &MaterialDeleteButtonProperties) -> Html {
 
     html! {
     <AdminOrOwner owner_id={props.owner_id}>
-        <button type="button" class="btn btn-danger btn-sm" data-bs-toggle="modal" data-bs-target={format!("#{MODAL_MATERIAL_DELETE}")} onclick={props.onclick}> { "Löschen" } </button>
+        <button type="button" class="btn btn-danger btn-sm" data-bs-toggle="modal" data-bs-target={format!("#{MODAL_MATERIAL_DELETE}")} onclick={props.onclick}> { t("material-delete") } </button>
     </AdminOrOwner>
     }
 }
+
+/// Key of a setting kept in the browser per member, so members sharing a device each keep theirs.
+pub(crate) fn member_storage_key(name: &str, id: i64) -> String {
+    let user_id = get_token_data()
+        .map(|user| user.user_id)
+        .unwrap_or_default();
+    format!("{name}-{user_id}-{id}")
+}
+
+fn load_deleted_material(ctx: &yew::Context<Material>) {
+    let project_id = ctx.props().id;
+    ctx.link().send_future(async move {
+        match deleted_material_by_project(project_id).await {
+            Ok(material) => Msg::Trash(TrashMessage::Loaded(material)),
+            Err(error) => Msg::Trash(TrashMessage::Error(error)),
+        }
+    });
+}
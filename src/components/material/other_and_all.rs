@@ -1,36 +1,42 @@
 use yew::{html, Context, Html};
 
 use crate::{
-    components::material::{AdminOrOwner, MaterialDeleteButton, MaterialUpdateButton},
-    service::material::{material_url, MaterialTo},
+    components::material::{
+        AdminOrOwner, MaterialDeleteButton, MaterialMarkersButton, MaterialUpdateButton,
+    },
+    i18n::t,
+    service::material::{material_url, MaterialCategory, MaterialTo},
 };
 
-use super::{DeleteMessage, Material, Msg, UpdateMessage};
+use super::{DeleteMessage, MarkersMessage, Material, Msg, UpdateMessage};
 
 pub fn other_and_all_list(ctx: &Context<Material>, elements: Vec<&MaterialTo>) -> Html {
     html! {
         <div class="row mt-2">
             <div class="col">
-                <h4>{ "Alle Dateien" }</h4>
+                <h4>{ t("material-all-title") }</h4>
                 <div class="table-responsive">
                     <table class="table table-striped table-hover">
                         <thead>
                             <tr>
                                 <th>
-                                    { "Dateibeschreibung" }
+                                    { t("material-all-description") }
                                 </th>
                                 <th>
-                                    { "Link" }
+                                    { t("material-all-link") }
                                 </th>
                                 <th>
-                                    { "Kategorie" }
+                                    { t("material-all-category") }
                                 </th>
                                 <AdminOrOwner owner_id={ ctx.props().project_owner }>
                                     <th>
-                                        { "Ändern" }
+                                        { t("material-change") }
                                     </th>
                                     <th>
-                                        { "Löschen" }
+                                        { t("material-markers") }
+                                    </th>
+                                    <th>
+                                        { t("material-delete") }
                                     </th>
                                 </AdminOrOwner>
                             </tr>
@@ -39,7 +45,7 @@ pub fn other_and_all_list(ctx: &Context<Material>, elements: Vec<&MaterialTo>) -
                         {
                             if elements.is_empty() {
                                 html!{
-                                    <td colspan="3">{ "Noch keine Dateien gefunden!" }</td>
+                                    <td colspan="3">{ t("material-all-empty") }</td>
                                 }
                             } else {
                                 html! {
@@ -61,9 +67,14 @@ pub fn other_and_all_list(ctx: &Context<Material>, elements: Vec<&MaterialTo>) -
 
 fn other_element(ctx: &Context<Material>, other: MaterialTo) -> Html {
     let other_clone = other.clone();
+    let markers_clone = other.clone();
+    let has_markers = matches!(
+        other.category,
+        MaterialCategory::Audio | MaterialCategory::Video
+    );
 
     let category_color = match other.category {
-        crate::service::material::MaterialCategory::Other => "bg-info",
+        MaterialCategory::Other => "bg-info",
         _ => "",
     };
 
@@ -94,6 +105,16 @@ fn other_element(ctx: &Context<Material>, other: MaterialTo) -> Html {
                         owner_id={ ctx.props().project_owner }
                     />
                 </td>
+                <td>
+                    if has_markers {
+                        <MaterialMarkersButton
+                            onclick={ ctx.link().callback(
+                                move |_| Msg::Markers(MarkersMessage::ButtonClick(markers_clone.clone()))
+                            )}
+                            owner_id={ ctx.props().project_owner }
+                        />
+                    }
+                </td>
                 <td>
                     <MaterialDeleteButton
                         onclick={
@@ -0,0 +1,417 @@
+use gloo_console::error;
+use gloo_dialogs::{alert, prompt};
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use web_sys::{Element, HtmlInputElement, InputEvent, KeyboardEvent};
+use yew::{html, Component, Context, Html, Properties, TargetCast};
+
+use crate::{
+    components::{
+        material::{chapters::Chapters, member_storage_key},
+        waveform::Waveform,
+    },
+    i18n::{t, t_with},
+    service::material::{material_url, MaterialTo},
+    utilities::date::format_elapsed,
+};
+
+#[wasm_bindgen(module = "/js/custom/practice.js")]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn media_time(id: &str) -> Result<f64, JsValue>;
+    #[wasm_bindgen(catch)]
+    fn seek_media(id: &str, seconds: f64) -> Result<(), JsValue>;
+    #[wasm_bindgen(catch)]
+    fn toggle_media(id: &str) -> Result<(), JsValue>;
+    #[wasm_bindgen(catch)]
+    fn set_tempo(id: &str, rate: f64) -> Result<(), JsValue>;
+    #[wasm_bindgen(catch)]
+    fn set_loop(id: &str, start: f64, end: f64) -> Result<(), JsValue>;
+    #[wasm_bindgen(catch)]
+    fn set_transposition(id: &str, semitones: i32) -> Result<(), JsValue>;
+}
+
+const MIN_TEMPO: f64 = 0.5;
+const MAX_TEMPO: f64 = 1.5;
+const TEMPO_STEP: f64 = 0.05;
+
+/// Transposition up or down by at most an octave.
+const MAX_SEMITONES: i32 = 12;
+
+/// Shorter loops are most likely set by accident.
+const MIN_LOOP_SECONDS: f64 = 0.2;
+
+/// How far the arrow keys seek, in seconds.
+const SEEK_SECONDS: f64 = 5.0;
+
+/// A part of a material a member wants to practise again later.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedLoop {
+    pub name: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shortcut {
+    PlayPause,
+    SetStart,
+    SetEnd,
+    ToggleLoop,
+    Back,
+    Forward,
+    Slower,
+    Faster,
+    TransposeDown,
+    TransposeUp,
+}
+
+pub enum Msg {
+    ToggleTools,
+    Key(KeyboardEvent),
+    Shortcut(Shortcut),
+    TempoInput(InputEvent),
+    SaveLoop,
+    SelectLoop(usize),
+    DeleteLoop(usize),
+}
+
+#[derive(PartialEq, Properties)]
+pub struct PracticePlayerProperties {
+    pub project_id: i64,
+    pub material: MaterialTo,
+}
+
+/// Player of an audio material with tools to drill difficult bars: a loop between two points, slower or faster playback and transposition.
+/// Loops are kept per member and material in the browser.
+pub struct PracticePlayer {
+    show_tools: bool,
+    tempo: f64,
+    semitones: i32,
+    loop_start: Option<f64>,
+    loop_end: Option<f64>,
+    looping: bool,
+    saved_loops: Vec<SavedLoop>,
+}
+
+impl Component for PracticePlayer {
+    type Message = Msg;
+    type Properties = PracticePlayerProperties;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            show_tools: false,
+            tempo: 1.0,
+            semitones: 0,
+            loop_start: None,
+            loop_end: None,
+            looping: false,
+            saved_loops: LocalStorage::get(storage_key(ctx)).unwrap_or_default(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let id = element_id(&ctx.props().material);
+        match msg {
+            Msg::ToggleTools => {
+                self.show_tools = !self.show_tools;
+                true
+            }
+            Msg::Key(event) => {
+                // Keys typed into the inputs and focused buttons of the tools keep their meaning there.
+                if is_interactive(&event) || event.ctrl_key() || event.alt_key() || event.meta_key()
+                {
+                    return false;
+                }
+                let Some(shortcut) = shortcut(&event.key()) else {
+                    return false;
+                };
+                event.prevent_default();
+                ctx.link().send_message(Msg::Shortcut(shortcut));
+                false
+            }
+            Msg::Shortcut(shortcut) => {
+                match shortcut {
+                    Shortcut::PlayPause => log_error(toggle_media(&id)),
+                    Shortcut::SetStart => {
+                        self.loop_start = media_time(&id).ok();
+                        self.looping = loop_range(self.loop_start, self.loop_end).is_some();
+                        self.apply_loop(&id);
+                    }
+                    Shortcut::SetEnd => {
+                        self.loop_end = media_time(&id).ok();
+                        self.looping = loop_range(self.loop_start, self.loop_end).is_some();
+                        self.apply_loop(&id);
+                    }
+                    Shortcut::ToggleLoop => {
+                        self.looping =
+                            !self.looping && loop_range(self.loop_start, self.loop_end).is_some();
+                        self.apply_loop(&id);
+                    }
+                    Shortcut::Back | Shortcut::Forward => {
+                        let direction = if shortcut == Shortcut::Back {
+                            -1.0
+                        } else {
+                            1.0
+                        };
+                        if let Ok(time) = media_time(&id) {
+                            log_error(seek_media(&id, time + direction * SEEK_SECONDS));
+                        }
+                    }
+                    Shortcut::Slower | Shortcut::Faster => {
+                        let steps = if shortcut == Shortcut::Slower { -1 } else { 1 };
+                        self.tempo = step_tempo(self.tempo, steps);
+                        log_error(set_tempo(&id, self.tempo));
+                    }
+                    Shortcut::TransposeDown | Shortcut::TransposeUp => {
+                        let step = if shortcut == Shortcut::TransposeDown {
+                            -1
+                        } else {
+                            1
+                        };
+                        self.semitones =
+                            (self.semitones + step).clamp(-MAX_SEMITONES, MAX_SEMITONES);
+                        if let Err(js_error) = set_transposition(&id, self.semitones) {
+                            error!(js_error);
+                            alert(&t("practice-transpose-failed"));
+                            self.semitones = 0;
+                        }
+                    }
+                }
+                true
+            }
+            Msg::TempoInput(event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                self.tempo = step_tempo(input.value_as_number(), 0);
+                log_error(set_tempo(&id, self.tempo));
+                true
+            }
+            Msg::SaveLoop => {
+                let Some((start, end)) = loop_range(self.loop_start, self.loop_end) else {
+                    return false;
+                };
+                let default_name = format!(
+                    "{}–{}",
+                    format_elapsed(start as i64),
+                    format_elapsed(end as i64)
+                );
+                let Some(name) = prompt(&t("practice-loop-name"), Some(&default_name)) else {
+                    return false;
+                };
+                self.saved_loops.push(SavedLoop { name, start, end });
+                self.store_loops(ctx);
+                true
+            }
+            Msg::SelectLoop(index) => {
+                let Some(saved) = self.saved_loops.get(index) else {
+                    return false;
+                };
+                self.loop_start = Some(saved.start);
+                self.loop_end = Some(saved.end);
+                self.looping = true;
+                self.apply_loop(&id);
+                log_error(seek_media(&id, saved.start));
+                true
+            }
+            Msg::DeleteLoop(index) => {
+                if index < self.saved_loops.len() {
+                    self.saved_loops.remove(index);
+                    self.store_loops(ctx);
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let material = &ctx.props().material;
+        let range = loop_range(self.loop_start, self.loop_end);
+        let on_shortcut = |shortcut| ctx.link().callback(move |_| Msg::Shortcut(shortcut));
+
+        html! {
+            <div class="col">
+                <h5> { &material.title } </h5>
+                <div tabindex="0" onkeydown={ ctx.link().callback(Msg::Key) }>
+                    <audio
+                        controls=true
+                        id={ element_id(material) }
+                        src={ material_url(ctx.props().project_id, &material.file_technical_name) }
+                    ></audio>
+                    <div>
+                        <button type="button" class="btn btn-sm btn-link px-0" onclick={ ctx.link().callback(|_| Msg::ToggleTools) }>
+                            { if self.show_tools { t("practice-hide") } else { t("practice-show") } }
+                        </button>
+                    </div>
+                    if self.show_tools {
+                        <div class="border rounded p-2 mb-2">
+                            <Waveform
+                                url={ material_url(ctx.props().project_id, &material.file_technical_name) }
+                                element_id={ element_id(material) }
+                            />
+                            <div class="btn-group btn-group-sm mb-2" role="group">
+                                <button type="button" class="btn btn-outline-secondary" title={ t("practice-set-start") } onclick={ on_shortcut(Shortcut::SetStart) }>
+                                    { format!("A {}", self.loop_start.map(|start| format_elapsed(start as i64)).unwrap_or_default()) }
+                                </button>
+                                <button type="button" class="btn btn-outline-secondary" title={ t("practice-set-end") } onclick={ on_shortcut(Shortcut::SetEnd) }>
+                                    { format!("B {}", self.loop_end.map(|end| format_elapsed(end as i64)).unwrap_or_default()) }
+                                </button>
+                                <button
+                                    type="button"
+                                    class={ if self.looping { "btn btn-secondary" } else { "btn btn-outline-secondary" } }
+                                    disabled={ range.is_none() }
+                                    onclick={ on_shortcut(Shortcut::ToggleLoop) }
+                                >
+                                    { t("practice-loop") }
+                                </button>
+                                <button type="button" class="btn btn-outline-secondary" disabled={ range.is_none() } onclick={ ctx.link().callback(|_| Msg::SaveLoop) }>
+                                    { t("practice-save-loop") }
+                                </button>
+                            </div>
+                            if !self.saved_loops.is_empty() {
+                                <div class="mb-2">
+                                    { for self.saved_loops.iter().enumerate().map(|(index, saved)| html! {
+                                        <div class="btn-group btn-group-sm me-1 mb-1" role="group">
+                                            <button type="button" class="btn btn-outline-primary" onclick={ ctx.link().callback(move |_| Msg::SelectLoop(index)) }>
+                                                { &saved.name }
+                                            </button>
+                                            <button type="button" class="btn btn-outline-primary" title={ t("practice-delete-loop") } onclick={ ctx.link().callback(move |_| Msg::DeleteLoop(index)) }>
+                                                { "×" }
+                                            </button>
+                                        </div>
+                                    }) }
+                                </div>
+                            }
+                            <label class="form-label mb-0">
+                                { t_with("practice-tempo", &[("percent", &format!("{:.0}", self.tempo * 100.0))]) }
+                            </label>
+                            <input
+                                type="range"
+                                class="form-range"
+                                min={ MIN_TEMPO.to_string() }
+                                max={ MAX_TEMPO.to_string() }
+                                step={ TEMPO_STEP.to_string() }
+                                value={ self.tempo.to_string() }
+                                oninput={ ctx.link().callback(Msg::TempoInput) }
+                            />
+                            <div class="d-flex align-items-center">
+                                <button type="button" class="btn btn-sm btn-outline-secondary" disabled={ self.semitones <= -MAX_SEMITONES } onclick={ on_shortcut(Shortcut::TransposeDown) }>
+                                    { "−" }
+                                </button>
+                                <span class="mx-2">
+                                    { t_with("practice-transposition", &[("semitones", &format!("{:+}", self.semitones))]) }
+                                </span>
+                                <button type="button" class="btn btn-sm btn-outline-secondary" disabled={ self.semitones >= MAX_SEMITONES } onclick={ on_shortcut(Shortcut::TransposeUp) }>
+                                    { "+" }
+                                </button>
+                            </div>
+                            <p class="form-text mb-0">{ t("practice-shortcuts") }</p>
+                        </div>
+                    }
+                </div>
+                <Chapters element_id={ element_id(material) } markers={ material.markers.clone() } />
+                <h6> <i> { &material.file_name } </i> </h6>
+            </div>
+        }
+    }
+}
+
+impl PracticePlayer {
+    fn apply_loop(&self, id: &str) {
+        let (start, end) = match loop_range(self.loop_start, self.loop_end) {
+            Some(range) if self.looping => range,
+            _ => (f64::NAN, f64::NAN),
+        };
+        log_error(set_loop(id, start, end));
+    }
+
+    fn store_loops(&self, ctx: &Context<Self>) {
+        if let Err(error) = LocalStorage::set(storage_key(ctx), &self.saved_loops) {
+            error!(format!("Could not store loops: {error}"));
+        }
+    }
+}
+
+fn element_id(material: &MaterialTo) -> String {
+    format!("audio-{}", material.id)
+}
+
+fn storage_key(ctx: &Context<PracticePlayer>) -> String {
+    member_storage_key("loops", ctx.props().material.id)
+}
+
+fn log_error(result: Result<(), JsValue>) {
+    if let Err(js_error) = result {
+        error!(js_error);
+    }
+}
+
+fn shortcut(key: &str) -> Option<Shortcut> {
+    Some(match key {
+        " " | "k" => Shortcut::PlayPause,
+        "a" | "A" => Shortcut::SetStart,
+        "b" | "B" => Shortcut::SetEnd,
+        "l" | "L" => Shortcut::ToggleLoop,
+        "ArrowLeft" => Shortcut::Back,
+        "ArrowRight" => Shortcut::Forward,
+        "-" => Shortcut::Slower,
+        "+" | "=" => Shortcut::Faster,
+        "ArrowDown" => Shortcut::TransposeDown,
+        "ArrowUp" => Shortcut::TransposeUp,
+        _ => return None,
+    })
+}
+
+/// Changes the tempo by that many steps, staying on a step between the slowest and the fastest tempo.
+fn step_tempo(tempo: f64, steps: i32) -> f64 {
+    let step = (tempo / TEMPO_STEP).round() + steps as f64;
+    (step * TEMPO_STEP).clamp(MIN_TEMPO, MAX_TEMPO)
+}
+
+/// The loop between the two points, whichever was set first.
+fn loop_range(start: Option<f64>, end: Option<f64>) -> Option<(f64, f64)> {
+    let (start, end) = (start?, end?);
+    let (start, end) = (start.min(end), start.max(end));
+    (end - start >= MIN_LOOP_SECONDS).then_some((start, end))
+}
+
+/// Whether the key event comes from a control that handles keys itself, e.g. space pressing a focused button.
+fn is_interactive(event: &KeyboardEvent) -> bool {
+    event
+        .target_dyn_into::<Element>()
+        .and_then(|target| {
+            target
+                .closest("input, select, textarea, button, a, audio, video, [contenteditable]")
+                .ok()
+                .flatten()
+        })
+        .is_some()
+}
+
+#[cfg(test)]
+mod practice_test {
+    use super::{loop_range, shortcut, step_tempo, Shortcut};
+
+    #[test]
+    fn keys_are_mapped() {
+        assert_eq!(shortcut(" "), Some(Shortcut::PlayPause));
+        assert_eq!(shortcut("A"), Some(Shortcut::SetStart));
+        assert_eq!(shortcut("ArrowUp"), Some(Shortcut::TransposeUp));
+        assert_eq!(shortcut("x"), None);
+    }
+
+    #[test]
+    fn tempo_stays_in_range() {
+        assert!((step_tempo(1.0, -1) - 0.95).abs() < 1e-9);
+        assert!((step_tempo(0.93, 0) - 0.95).abs() < 1e-9);
+        assert_eq!(step_tempo(0.5, -1), 0.5);
+        assert_eq!(step_tempo(1.5, 3), 1.5);
+    }
+
+    #[test]
+    fn loop_needs_two_distinct_points() {
+        assert_eq!(loop_range(Some(12.0), Some(4.0)), Some((4.0, 12.0)));
+        assert_eq!(loop_range(Some(4.0), Some(4.1)), None);
+        assert_eq!(loop_range(Some(4.0), None), None);
+    }
+}
@@ -1,16 +1,19 @@
 use yew::{html, Html};
 
-use crate::service::material::{material_url, MaterialTo};
+use crate::{
+    i18n::t,
+    service::material::{material_url, MaterialTo},
+};
 
 pub fn sheet_list(id: i64, sheet_elements: Vec<&MaterialTo>) -> Html {
     html! {
         <div class="row mt-2">
             <div class="col">
-                <h4>{ "Noten" }</h4>
+                <h4>{ t("material-sheet-title") }</h4>
                 {
                     if sheet_elements.is_empty() {
                         html!{
-                            <p>{ "Noch keine Noten gefunden!" }</p>
+                            <p>{ t("material-sheet-empty") }</p>
                         }
                     } else {
                         html! {
@@ -1,6 +1,10 @@
 use yew::{html, Context, Html};
 
-use crate::service::material::{material_url, MaterialTo};
+use crate::{
+    components::material::chapters::Chapters,
+    i18n::t,
+    service::material::{material_url, MaterialTo},
+};
 
 use super::Material;
 
@@ -8,11 +12,11 @@ pub fn video_list(ctx: &Context<Material>, video_elements: Vec<&MaterialTo>) ->
     html! {
         <div class="row mt-2">
             <div class="col">
-                <h4>{ "Videos" }</h4>
+                <h4>{ t("material-video-title") }</h4>
                 {
                     if video_elements.is_empty() {
                         html!{
-                            <p>{ "Noch keine Videodateien gefunden!" }</p>
+                            <p>{ t("material-video-empty") }</p>
                         }
                     } else {
                         html! {
@@ -28,17 +32,23 @@ pub fn video_list(ctx: &Context<Material>, video_elements: Vec<&MaterialTo>) ->
 }
 
 fn video_element(ctx: &Context<Material>, video_element: MaterialTo) -> Html {
+    let element_id = format!("video-{}", video_element.id);
     html! {
         <div class="row">
             <div class="col">
             <h5> { &video_element.title } </h5>
             <div class="ratio ratio-16x9">
-                    <video id={video_element.title.clone()} controls=true>
+                    <video id={ element_id.clone() } controls=true>
                         <source src={ material_url(ctx.props().id, &video_element.file_technical_name) }/>
                     </video>
                 </div>
                 <h6> <i> { &video_element.file_name } </i> </h6>
             </div>
+            if !video_element.markers.is_empty() {
+                <div class="col-md-3">
+                    <Chapters { element_id } markers={ video_element.markers.clone() } />
+                </div>
+            }
         </div>
     }
 }
@@ -0,0 +1,252 @@
+use std::future::Future;
+
+use gloo_timers::callback::Interval;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use web_sys::{HtmlInputElement, InputEvent};
+use yew::{html, html::Scope, Component, Html, TargetCast};
+
+use crate::{i18n::t, utilities::date::format_elapsed};
+
+#[wasm_bindgen(module = "/js/custom/mixer.js")]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    async fn load_mixer(name: &str, urls: Vec<String>) -> Result<JsValue, JsValue>;
+    fn mixer_play(name: &str, from: f64);
+    fn mixer_pause(name: &str);
+    fn mixer_position(name: &str) -> f64;
+    fn mixer_set_gains(name: &str, gains: &[f64]);
+    fn mixer_set_pans(name: &str, pans: &[f64]);
+    fn mixer_set_delays(name: &str, delays: &[f64]) -> f64;
+    #[wasm_bindgen(catch)]
+    async fn mixer_render(name: &str, gains: &[f64], pans: &[f64]) -> Result<JsValue, JsValue>;
+    fn close_mixer(name: &str);
+}
+
+/// Time between two updates of the position in milliseconds.
+const TICK_MILLIS: u32 = 250;
+
+/// Messages of the transport, wrapped into the messages of the mixer it belongs to.
+pub enum TransportMsg {
+    Play,
+    Pause,
+    Stop,
+    Seek(InputEvent),
+    Tick,
+}
+
+enum TransportState {
+    /// Nothing is downloaded until the mixer is used for the first time.
+    Unloaded,
+    Loading,
+    Ready {
+        duration: f64,
+    },
+}
+
+/// Loading, play, pause, stop and seeking of a mixer in `mixer.js`, shared by the mixers of the material and of the
+/// submissions. The mixers only add the settings of their tracks.
+pub struct MixerTransport {
+    /// Name of the mixer in `mixer.js`, each mixer on a page needs its own.
+    name: &'static str,
+    state: TransportState,
+    playing: bool,
+    position: f64,
+    ticker: Option<Interval>,
+}
+
+impl MixerTransport {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            state: TransportState::Unloaded,
+            playing: false,
+            position: 0.0,
+            ticker: None,
+        }
+    }
+
+    pub fn is_unloaded(&self) -> bool {
+        matches!(self.state, TransportState::Unloaded)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        matches!(self.state, TransportState::Ready { .. })
+    }
+
+    /// Downloads and decodes the tracks. `loaded` turns the duration or the error into a message of the mixer, which
+    /// passes it on to [Self::loaded] or [Self::load_failed].
+    pub fn load<C: Component>(
+        &mut self,
+        link: &Scope<C>,
+        urls: Vec<String>,
+        loaded: fn(Result<f64, String>) -> C::Message,
+    ) {
+        self.state = TransportState::Loading;
+        let name = self.name;
+        link.send_future(async move {
+            loaded(match load_mixer(name, urls).await {
+                Ok(duration) => Ok(duration.as_f64().unwrap_or_default()),
+                Err(error) => Err(format!("{error:?}")),
+            })
+        });
+    }
+
+    pub fn loaded(&mut self, duration: f64) {
+        self.state = TransportState::Ready { duration };
+    }
+
+    pub fn load_failed(&mut self) {
+        self.state = TransportState::Unloaded;
+    }
+
+    /// Handles the buttons and the position bar. Playing an unloaded mixer does nothing, the mixer loads it first.
+    pub fn update<C: Component>(
+        &mut self,
+        link: &Scope<C>,
+        msg: TransportMsg,
+        wrap: fn(TransportMsg) -> C::Message,
+    ) -> bool {
+        match msg {
+            TransportMsg::Play => match self.state {
+                TransportState::Unloaded | TransportState::Loading => false,
+                TransportState::Ready { duration } => {
+                    if self.position >= duration {
+                        self.position = 0.0;
+                    }
+                    mixer_play(self.name, self.position);
+                    self.playing = true;
+                    let link = link.clone();
+                    self.ticker = Some(Interval::new(TICK_MILLIS, move || {
+                        link.send_message(wrap(TransportMsg::Tick))
+                    }));
+                    true
+                }
+            },
+            TransportMsg::Pause => {
+                self.pause();
+                true
+            }
+            TransportMsg::Stop => {
+                mixer_pause(self.name);
+                self.position = 0.0;
+                self.playing = false;
+                self.ticker = None;
+                true
+            }
+            TransportMsg::Seek(event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                self.position = input.value_as_number();
+                if self.playing {
+                    mixer_play(self.name, self.position);
+                }
+                true
+            }
+            TransportMsg::Tick => {
+                self.position = mixer_position(self.name);
+                if let TransportState::Ready { duration } = self.state {
+                    if self.position >= duration {
+                        self.pause();
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    /// Sets the volume of each track, in the order of the urls.
+    pub fn set_gains(&self, gains: &[f64]) {
+        if self.is_ready() {
+            mixer_set_gains(self.name, gains);
+        }
+    }
+
+    /// Sets the position of each track in the stereo field, in the order of the urls.
+    pub fn set_pans(&self, pans: &[f64]) {
+        if self.is_ready() {
+            mixer_set_pans(self.name, pans);
+        }
+    }
+
+    /// Sets the seconds each track starts after the start of the mix, which changes its duration.
+    pub fn set_delays(&mut self, delays: &[f64]) {
+        if self.is_ready() {
+            let duration = mixer_set_delays(self.name, delays);
+            self.state = TransportState::Ready { duration };
+            self.position = mixer_position(self.name);
+        }
+    }
+
+    /// Renders the loaded mix, see `mixer_render` in `mixer.js`.
+    pub fn render(
+        &self,
+        gains: Vec<f64>,
+        pans: Vec<f64>,
+    ) -> impl Future<Output = Result<JsValue, JsValue>> {
+        let name = self.name;
+        async move { mixer_render(name, &gains, &pans).await }
+    }
+
+    /// Stops the playback and frees the tracks, they are loaded again when played.
+    pub fn close(&mut self) {
+        self.ticker = None;
+        self.playing = false;
+        self.position = 0.0;
+        if !self.is_unloaded() {
+            close_mixer(self.name);
+        }
+        self.state = TransportState::Unloaded;
+    }
+
+    /// The buttons and the position bar. `actions` are shown after the time, e.g. an export button.
+    pub fn view<C: Component>(
+        &self,
+        link: &Scope<C>,
+        wrap: fn(TransportMsg) -> C::Message,
+        actions: Html,
+    ) -> Html {
+        let duration = match self.state {
+            TransportState::Ready { duration } => duration,
+            _ => 0.0,
+        };
+        let loading = matches!(self.state, TransportState::Loading);
+
+        html! {
+            <div class="d-flex align-items-center">
+                if self.playing {
+                    <button type="button" class="btn btn-outline-primary me-2" onclick={ link.callback(move |_| wrap(TransportMsg::Pause)) }>
+                        { t("mixer-pause") }
+                    </button>
+                } else {
+                    <button type="button" class="btn btn-primary me-2" disabled={ loading } onclick={ link.callback(move |_| wrap(TransportMsg::Play)) }>
+                        { if loading { t("mixer-loading") } else { t("mixer-play") } }
+                    </button>
+                }
+                <button type="button" class="btn btn-outline-secondary me-2" disabled={ duration == 0.0 } onclick={ link.callback(move |_| wrap(TransportMsg::Stop)) }>
+                    { t("mixer-stop") }
+                </button>
+                <input
+                    type="range"
+                    class="form-range flex-grow-1 me-2"
+                    min="0"
+                    max={ duration.to_string() }
+                    step="0.1"
+                    value={ self.position.to_string() }
+                    disabled={ duration == 0.0 }
+                    title={ t("mixer-position") }
+                    oninput={ link.callback(move |event| wrap(TransportMsg::Seek(event))) }
+                />
+                <span class="text-nowrap">
+                    { format!("{} / {}", format_elapsed(self.position as i64), format_elapsed(duration.ceil() as i64)) }
+                </span>
+                { actions }
+            </div>
+        }
+    }
+
+    fn pause(&mut self) {
+        mixer_pause(self.name);
+        self.position = mixer_position(self.name);
+        self.playing = false;
+        self.ticker = None;
+    }
+}
@@ -1,11 +1,20 @@
 pub(crate) mod admin_only;
+pub(crate) mod choir;
+pub(crate) mod countdown;
 pub(crate) mod delete_modal;
+pub(crate) mod enum_select;
 pub(crate) mod iframe;
 pub(crate) mod jwt_context;
 pub(crate) mod loading_spinner;
+pub(crate) mod locale_select;
 pub(crate) mod material;
+pub(crate) mod mixer_transport;
 pub(crate) mod modal;
 pub(crate) mod progress;
 pub(crate) mod project;
+pub(crate) mod recorder;
 pub(crate) mod submission;
+pub(crate) mod trash;
 pub(crate) mod upload;
+pub(crate) mod view_as;
+pub(crate) mod waveform;
@@ -4,6 +4,8 @@ use progress_component::Progress;
 
 use yew::{html, Component, Properties};
 
+use crate::i18n::t_with;
+
 pub enum ProgressMsg {}
 
 #[derive(Properties, PartialEq)]
@@ -32,7 +34,14 @@ impl Component for ProgressComponent {
             <div class="progress">
                 <div class="progress-bar" role="progressbar" id="fileUploadProgress" style={ format!("width: {}%", percent)} aria-valuenow={self.progress.loaded().to_string()} aria-valuemin="0" aria-valuemax={self.progress.total().to_string()}></div>
             </div>
-            <p>{ self.progress.loaded_string() } { self.progress.loaded_unit() } { " von " } { self.progress.total_string() } { self.progress.total_unit() } { " geladen" }</p>
+            <p>
+                {
+                    t_with("progress-loaded", &[
+                        ("loaded", &format!("{} {}", self.progress.loaded_string(), self.progress.loaded_unit())),
+                        ("total", &format!("{} {}", self.progress.total_string(), self.progress.total_unit())),
+                    ])
+                }
+            </p>
             </>
         }
     }
@@ -0,0 +1,47 @@
+use gloo_utils::window;
+use time::Duration;
+use yew_router::Routable;
+
+use crate::{
+    components::project::viewer_due,
+    i18n::{t, t_with},
+    service::project::ProjectTo,
+    utilities::{
+        date::now,
+        download_file,
+        ical::{Calendar, CalendarEvent},
+    },
+    Route,
+};
+
+/// Lets the viewer save the deadlines of the projects as an `.ics` file for their calendar app.
+pub fn download_calendar(file_name: &str, projects: &[ProjectTo]) {
+    let calendar = Calendar {
+        name: t("calendar-name"),
+        events: projects.iter().map(project_event).collect(),
+    };
+    download_file(file_name, "text/calendar", &calendar.to_ics(now()));
+}
+
+/// The deadline of the section of the viewer, reminded of a day and two hours before.
+fn project_event(project: &ProjectTo) -> CalendarEvent {
+    let location = window().location();
+    let host = location.host().unwrap_or_default();
+    let url = format!(
+        "{}{}",
+        location.origin().unwrap_or_default(),
+        Route::Event { id: project.id }.to_path()
+    );
+
+    CalendarEvent {
+        uid: format!("project-{}@{host}", project.id),
+        start: viewer_due(project),
+        summary: t_with("calendar-event-summary", &[("title", &project.title)]),
+        description: t_with(
+            "calendar-event-description",
+            &[("title", &project.title), ("url", &url)],
+        ),
+        url: Some(url),
+        alarms: vec![Duration::days(1), Duration::hours(2)],
+    }
+}
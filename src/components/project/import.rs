@@ -0,0 +1,291 @@
+use gloo_console::error;
+use time::macros::format_description;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Event, HtmlInputElement};
+use yew::{html, Callback, Component, Html, Properties, TargetCast};
+
+use crate::{
+    components::{jwt_context::get_token_data, modal::Modal, view_as::is_viewing_as},
+    i18n::{t, t_with},
+    service::project::{create_project, DeadlineRules, ProjectState, ProjectTo, SectionDeadline},
+    utilities::{
+        date::{format_datetime_human_readable, local_time_zone, time_zones},
+        project_import::{parse_projects, ImportDate, ImportError, ImportedProject},
+        requests::fetch::FetchError,
+    },
+};
+
+pub const MODAL_IMPORT_PROJECTS: &str = "modalImportProjects";
+
+pub enum Msg {
+    FileInput(Event),
+    FileRead(String),
+    FileReadFailed(String),
+    Start,
+    Created(ProjectTo),
+    Failed(FetchError),
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ProjectImportProperties {
+    pub on_created: Callback<ProjectTo>,
+}
+
+/// Creates all projects of a CSV or JSON file, after showing what is wrong with them.
+pub struct ProjectImportModal {
+    projects: Option<Result<Vec<ImportedProject>, ImportError>>,
+    /// Index of the project that is being created while the import runs.
+    current: Option<usize>,
+    created: usize,
+    /// Row, title and error of the projects the backend refused.
+    failures: Vec<(usize, String, String)>,
+    finished: bool,
+}
+
+impl Component for ProjectImportModal {
+    type Message = Msg;
+    type Properties = ProjectImportProperties;
+
+    fn create(_ctx: &yew::Context<Self>) -> Self {
+        Self {
+            projects: None,
+            current: None,
+            created: 0,
+            failures: Vec::new(),
+            finished: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::FileInput(event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                    return false;
+                };
+
+                ctx.link().send_future(async move {
+                    match JsFuture::from(file.text()).await {
+                        Ok(content) => Msg::FileRead(content.as_string().unwrap_or_default()),
+                        Err(error) => Msg::FileReadFailed(format!("{error:?}")),
+                    }
+                });
+                false
+            }
+            Msg::FileRead(content) => {
+                self.projects = Some(parse_projects(&content, &time_zones()));
+                self.current = None;
+                self.created = 0;
+                self.failures.clear();
+                self.finished = false;
+                true
+            }
+            Msg::FileReadFailed(message) => {
+                error!(format!("Could not read the file: {message}"));
+                self.projects = Some(Err(ImportError::Csv(message)));
+                true
+            }
+            Msg::Start => {
+                self.created = 0;
+                self.failures.clear();
+                self.finished = false;
+                self.create_from(ctx, 0);
+                true
+            }
+            Msg::Created(project) => {
+                self.created += 1;
+                ctx.props().on_created.emit(project);
+                self.create_next(ctx);
+                true
+            }
+            Msg::Failed(error) => {
+                error!(format!("Could not import project: {error}"));
+                if let Some(project) = self.current_project() {
+                    self.failures
+                        .push((project.row, project.title.clone(), error.to_string()));
+                }
+                self.create_next(ctx);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> Html {
+        let running = self.current.is_some();
+
+        html! {
+            <Modal id={ MODAL_IMPORT_PROJECTS } title={ t("project-import-title") }>
+                <p>{ t("project-import-format") }</p>
+                <input
+                    type="file"
+                    class="form-control"
+                    accept=".csv,.json,text/csv,application/json"
+                    disabled={ running }
+                    onchange={ ctx.link().callback(Msg::FileInput) }
+                />
+                {
+                    match &self.projects {
+                        Some(Ok(projects)) => self.preview(ctx, projects),
+                        Some(Err(error)) => html! {
+                            <div class="alert alert-danger mt-2">{ error.message() }</div>
+                        },
+                        None => html! {},
+                    }
+                }
+            </Modal>
+        }
+    }
+}
+
+impl ProjectImportModal {
+    fn preview(&self, ctx: &yew::Context<Self>, projects: &[ImportedProject]) -> Html {
+        let valid = projects.iter().filter(|project| project.is_valid()).count();
+        let done = self.created + self.failures.len();
+        let running = self.current.is_some();
+
+        html! {
+            <>
+            <div class="table-responsive mt-2">
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th>{ t("project-import-row") }</th>
+                            <th>{ t("project-list-piece") }</th>
+                            <th>{ t("project-list-due") }</th>
+                            <th>{ t("project-import-problems") }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        for projects.iter().map(|project| html! {
+                            <tr class={ if project.is_valid() { "" } else { "table-danger" } }>
+                                <td>{ project.row }</td>
+                                <td>{ &project.title }</td>
+                                <td>{ project.due.map(format_import_date).unwrap_or_default() }</td>
+                                <td>
+                                {
+                                    for project.problems.iter().map(|problem| html! {
+                                        <div>{ problem.message() }</div>
+                                    })
+                                }
+                                </td>
+                            </tr>
+                        })
+                    }
+                    </tbody>
+                </table>
+            </div>
+            <p>{ t_with("project-import-valid", &[("valid", &valid), ("total", &projects.len())]) }</p>
+            if running {
+                <div class="progress mb-2">
+                    <div
+                        class="progress-bar"
+                        role="progressbar"
+                        style={ format!("width: {}%", done * 100 / valid.max(1)) }
+                        aria-valuenow={ done.to_string() }
+                        aria-valuemin="0"
+                        aria-valuemax={ valid.to_string() }
+                    ></div>
+                </div>
+            }
+            if self.finished {
+                <div class={ if self.failures.is_empty() { "alert alert-success" } else { "alert alert-warning" } }>
+                    { t_with("project-import-summary", &[("created", &self.created), ("failed", &self.failures.len())]) }
+                    {
+                        for self.failures.iter().map(|(row, title, error)| html! {
+                            <div>{ t_with("project-import-failure", &[("row", row), ("title", title), ("error", error)]) }</div>
+                        })
+                    }
+                </div>
+            }
+            <button
+                type="button"
+                class="btn btn-danger"
+                disabled={ running || valid == 0 || is_viewing_as() }
+                onclick={ ctx.link().callback(|_| Msg::Start) }
+            >
+                { t_with("project-import-start", &[("count", &valid)]) }
+            </button>
+            </>
+        }
+    }
+
+    fn current_project(&self) -> Option<&ImportedProject> {
+        match (&self.projects, self.current) {
+            (Some(Ok(projects)), Some(index)) => projects.get(index),
+            _ => None,
+        }
+    }
+
+    fn create_next(&mut self, ctx: &yew::Context<Self>) {
+        let next = self.current.map(|index| index + 1).unwrap_or_default();
+        self.create_from(ctx, next);
+    }
+
+    /// Creates the first valid project from that index on, the import is finished if there is none.
+    /// Projects are created one after another, so the progress is shown and the backend is not flooded.
+    fn create_from(&mut self, ctx: &yew::Context<Self>, start: usize) {
+        let Some(Ok(projects)) = &self.projects else {
+            return;
+        };
+
+        let next = projects
+            .iter()
+            .enumerate()
+            .skip(start)
+            .find(|(_, project)| project.is_valid());
+
+        let Some((index, project)) = next else {
+            self.current = None;
+            self.finished = true;
+            return;
+        };
+
+        self.current = Some(index);
+        let project = project.clone();
+        let choir_id = get_token_data().ok().and_then(|user| user.choir_id);
+
+        ctx.link().send_future(async move {
+            let time_zone = project.time_zone.unwrap_or_else(local_time_zone);
+            let due = project
+                .due
+                .expect("Valid projects have a deadline")
+                .resolve(&time_zone);
+            let deadline_rules = DeadlineRules {
+                section_deadlines: project
+                    .section_deadlines
+                    .into_iter()
+                    .map(|(section, due)| SectionDeadline {
+                        section,
+                        due: due.resolve(&time_zone),
+                    })
+                    .collect(),
+                ..DeadlineRules::default()
+            };
+
+            match create_project(
+                project.title,
+                project.description,
+                due,
+                time_zone,
+                deadline_rules,
+                ProjectState::Open,
+                choir_id,
+            )
+            .await
+            {
+                Ok(project) => Msg::Created(project),
+                Err(error) => Msg::Failed(error),
+            }
+        });
+    }
+}
+
+fn format_import_date(date: ImportDate) -> String {
+    match date {
+        ImportDate::Instant(date) => format_datetime_human_readable(&date),
+        ImportDate::Wall(wall) => wall
+            .format(format_description!("[year]-[month]-[day] [hour]:[minute]"))
+            .unwrap(),
+    }
+}
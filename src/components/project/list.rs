@@ -1,5 +1,5 @@
 use gloo_console::{error, warn};
-use gloo_dialogs::alert;
+use gloo_dialogs::{alert, prompt};
 
 use web_sys::MouseEvent;
 use yew::{classes, function_component, html, Callback, Component, Html, Properties};
@@ -9,13 +9,29 @@ use crate::{
     components::{
         admin_only::AdminOnly,
         admin_only::AdminOrOwner,
+        countdown::Countdown,
         delete_modal::DeleteModal,
+        jwt_context::get_token_data,
         loading_spinner::LoadingSpinner,
         modal::Modal,
-        project::modals::{ProjectUpdateModal, MODAL_UPDATE_PROJECT},
+        project::{
+            modals::{ProjectUpdateModal, MODAL_NEW_PROJECT, MODAL_UPDATE_PROJECT},
+            viewer_due,
+        },
+        trash::{TrashItem, TrashList},
+        view_as::is_viewing_as,
+    },
+    i18n::{t, t_with},
+    service::{
+        project::{
+            change_project_state, delete_project, purge_project, restore_project, ProjectState,
+            ProjectTo,
+        },
+        template::{create_template, ProjectTemplate, TemplateContent},
+    },
+    utilities::{
+        date::format_datetime_human_readable, requests::fetch::FetchError, select_enum::SelectEnum,
     },
-    service::project::{delete_project, ProjectTo},
-    utilities::{date::format_datetime_human_readable, requests::fetch::FetchError},
     Route,
 };
 
@@ -37,9 +53,32 @@ pub enum UpdateMessage {
     Error(FetchError),
 }
 
+pub enum StateMessage {
+    Change(ProjectTo, ProjectState),
+    Success(ProjectTo),
+    Error(FetchError),
+}
+
+pub enum TrashMessage {
+    Restore(i64),
+    Restored(ProjectTo),
+    Purge(i64),
+    Purged(i64),
+    Error(FetchError),
+}
+
+pub enum TemplateMessage {
+    Save(ProjectTo),
+    Saved(ProjectTemplate),
+    Error(FetchError),
+}
+
 pub enum Msg {
     Delete(DeleteMessage),
     Update(UpdateMessage),
+    State(StateMessage),
+    Trash(TrashMessage),
+    Template(TemplateMessage),
 }
 
 #[derive(Properties, PartialEq, Clone)]
@@ -50,6 +89,11 @@ pub struct ProjectListsProperties {
     pub(crate) deleted_projects: Option<Vec<ProjectTo>>,
     pub(crate) project_delete: Callback<i64>,
     pub(crate) project_change: Callback<ProjectTo>,
+    pub(crate) project_restore: Callback<ProjectTo>,
+    pub(crate) project_purge: Callback<i64>,
+    /// Called before the modal for new projects opens, to fill it with the project.
+    pub(crate) project_duplicate: Callback<ProjectTo>,
+    pub(crate) template_saved: Callback<ProjectTemplate>,
 }
 
 pub struct ProjectLists {
@@ -85,7 +129,7 @@ impl Component for ProjectLists {
                     match error {
                         FetchError::StatusCode(code) => {
                             if code == 404 {
-                                alert("Project was already deleted!");
+                                alert(&t("project-list-already-deleted"));
                                 warn!("Could not delete project, got 404.");
                                 return false;
                             }
@@ -96,7 +140,7 @@ impl Component for ProjectLists {
                         }
                         _ => warn!("Could not delete project!"),
                     }
-                    alert("Das Projekt konnte nicht gelöscht werden! Details siehe Konsole.");
+                    alert(&t("project-list-delete-failed"));
                     warn!(format!("{:?}", error));
                     false
                 }
@@ -104,9 +148,7 @@ impl Component for ProjectLists {
                     let project_id = match self.selected_delete.take() {
                         Some(project) => project.id,
                         None => {
-                            alert(
-                            "Fehler: kein ausgewähltes Projekt gefunden. Bitte erneut versuchen.",
-                        );
+                            alert(&t("project-list-none-selected"));
                             return false;
                         }
                     };
@@ -135,9 +177,101 @@ impl Component for ProjectLists {
                 }
                 UpdateMessage::Error(error) => {
                     error!(error.to_string());
-                    alert("Das Projekt konnte nicht angepasst werden. \
-                    Überprüfe Deine Internetverbindung, versuche es erneut und wende dich sonst an den/die Administrator*in. \
-                    Details siehe Konsole.");
+                    alert(&t("project-list-update-failed"));
+                    false
+                }
+            },
+            Msg::State(message) => match message {
+                StateMessage::Change(project, state) => {
+                    let result_url = if state == ProjectState::Published {
+                        match prompt(&t("project-list-result-url"), project.result_url.as_deref()) {
+                            Some(url) if !url.trim().is_empty() => Some(url.trim().to_string()),
+                            Some(_) => None,
+                            None => return false,
+                        }
+                    } else {
+                        None
+                    };
+
+                    ctx.link().send_future(async move {
+                        match change_project_state(project.id, state, result_url).await {
+                            Ok(project) => Msg::State(StateMessage::Success(project)),
+                            Err(error) => Msg::State(StateMessage::Error(error)),
+                        }
+                    });
+                    false
+                }
+                StateMessage::Success(project) => {
+                    ctx.props().project_change.emit(project);
+                    false
+                }
+                StateMessage::Error(error) => {
+                    error!(error.to_string());
+                    alert(&t("project-list-state-failed"));
+                    false
+                }
+            },
+            Msg::Trash(message) => match message {
+                TrashMessage::Restore(project_id) => {
+                    ctx.link().send_future(async move {
+                        match restore_project(project_id).await {
+                            Ok(project) => Msg::Trash(TrashMessage::Restored(project)),
+                            Err(error) => Msg::Trash(TrashMessage::Error(error)),
+                        }
+                    });
+                    false
+                }
+                TrashMessage::Restored(project) => {
+                    ctx.props().project_restore.emit(project);
+                    false
+                }
+                TrashMessage::Purge(project_id) => {
+                    ctx.link().send_future(async move {
+                        match purge_project(project_id).await {
+                            Ok(()) => Msg::Trash(TrashMessage::Purged(project_id)),
+                            Err(error) => Msg::Trash(TrashMessage::Error(error)),
+                        }
+                    });
+                    false
+                }
+                TrashMessage::Purged(project_id) => {
+                    ctx.props().project_purge.emit(project_id);
+                    false
+                }
+                TrashMessage::Error(error) => {
+                    error!(error.to_string());
+                    alert(&t("trash-failed"));
+                    false
+                }
+            },
+            Msg::Template(message) => match message {
+                TemplateMessage::Save(project) => {
+                    let name = match prompt(&t("project-list-template-name"), Some(&project.title))
+                    {
+                        Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+                        _ => return false,
+                    };
+                    let content = TemplateContent::from_project(&project);
+
+                    ctx.link().send_future(async move {
+                        match create_template(name, content, project.choir_id).await {
+                            Ok(template) => Msg::Template(TemplateMessage::Saved(template)),
+                            Err(error) => Msg::Template(TemplateMessage::Error(error)),
+                        }
+                    });
+                    false
+                }
+                TemplateMessage::Saved(template) => {
+                    alert(&t_with(
+                        "project-list-template-saved",
+                        &[("name", &template.name)],
+                    ));
+                    ctx.props().template_saved.emit(template);
+                    false
+                }
+                TemplateMessage::Error(error) => {
+                    error!(error.to_string());
+                    alert(&t("project-list-template-failed"));
                     false
                 }
             },
@@ -149,16 +283,12 @@ impl Component for ProjectLists {
             <>
             <div class="row mt-2">
                 <div class="col">
-                    <h3> { "Ausstehende Projekte" } </h3>
+                    <h3> { t("project-list-pending") } </h3>
                 </div>
             </div>
 
             if let Some(projects) = &ctx.props().projects {
-            <ProjectList
-                projects={projects.clone()}
-                on_change={ ctx.link().callback(move |project| Msg::Update(UpdateMessage::Update(project)))}
-                on_delete={ ctx.link().callback(move |project| Msg::Delete(DeleteMessage::ListItemButtonClick(project)))}
-            />
+            { self.grouped_lists(ctx, projects) }
             } else {
                 <LoadingSpinner />
             }
@@ -166,30 +296,22 @@ impl Component for ProjectLists {
 
             <div class="row mt-2">
                 <div class="col">
-                    <h3> { "Alle Projekte" } </h3>
+                    <h3> { t("project-list-all") } </h3>
                 </div>
             </div>
             if let Some(projects) = &ctx.props().all_projects {
-            <ProjectList
-                projects={projects.clone()}
-                on_change={ ctx.link().callback(move |project| Msg::Update(UpdateMessage::Update(project)))}
-                on_delete={ ctx.link().callback(move |project| Msg::Delete(DeleteMessage::ListItemButtonClick(project)))}
-            />
+            { self.grouped_lists(ctx, projects) }
             } else {
                 <LoadingSpinner />
             }
 
             <div class="row mt-2">
                 <div class="col">
-                    <h3> { "Meine Projekte" } </h3>
+                    <h3> { t("project-list-mine") } </h3>
                 </div>
             </div>
             if let Some(projects) =  &ctx.props().my_projects {
-            <ProjectList
-                projects={projects.clone()}
-                on_change={ ctx.link().callback(move |project| Msg::Update(UpdateMessage::Update(project)))}
-                on_delete={ ctx.link().callback(move |project| Msg::Delete(DeleteMessage::ListItemButtonClick(project)))}
-            />
+            { self.grouped_lists(ctx, projects) }
             } else {
                 <LoadingSpinner />
             }
@@ -199,14 +321,11 @@ impl Component for ProjectLists {
                 if let Some(projects) = &ctx.props().deleted_projects {
                     <div class="row mt-2">
                         <div class="col-auto">
-                            <i> { "Auf Löschung warten: " }
-                                    {  projects.len() }
-                                { " Projekt(e)" }
-                            </i>
+                            <i>{ t_with("project-list-awaiting-deletion", &[("count", &projects.len())]) }</i>
                         </div>
                         if !projects.is_empty() {
                             <div class="col">
-                                <button class="btn btn-sm btn-outline-danger" data-bs-toggle="modal" data-bs-target={format!("#{MODAL_DELETED_PROJECTS}")}> { "Anzeigen" }</button>
+                                <button class="btn btn-sm btn-outline-danger" data-bs-toggle="modal" data-bs-target={format!("#{MODAL_DELETED_PROJECTS}")}> { t("project-list-show") }</button>
                             </div>
                         }
 
@@ -215,24 +334,20 @@ impl Component for ProjectLists {
 
                     <Modal
                         id={ MODAL_DELETED_PROJECTS }
-                        title="Zur Löschung vorgemerkte Projekte"
+                        title={ t("project-list-deleted-title") }
                     >   <i>
-                        <p> { "Diese Projekte wurden zur Löschung vorgemerkt, es laufen aber noch Downloads oder die Löschung wurde unterbrochen." } </p>
-                        <p> { "Sie werden spätestens beim nächsten Serverneustart gelöscht." } </p>
+                        <p> { t("project-list-deleted-explanation") } </p>
                         </i>
-                        <b>{ "Projekte:" }</b>
-                        <ul>
-                        {
-                            for projects.iter().map(|project| {
-                                html!{
-                                    <li>
-                                    { &project.title }
-                                    </li>
-                                }
-                            })
-                        }
-
-                        </ul>
+                        <b>{ t("project-list-deleted-projects") }</b>
+                        <TrashList
+                            items={ projects.iter().map(|project| TrashItem {
+                                id: project.id,
+                                name: project.title.clone(),
+                                purge_at: project.purge_at,
+                            }).collect::<Vec<_>>() }
+                            on_restore={ ctx.link().callback(|id| Msg::Trash(TrashMessage::Restore(id))) }
+                            on_purge={ ctx.link().callback(|id| Msg::Trash(TrashMessage::Purge(id))) }
+                        />
 
                     </Modal>
                 } else {
@@ -248,42 +363,28 @@ impl Component for ProjectLists {
             />
 
             <DeleteModal
-                title={"Projekt löschen".to_string() }
+                title={ t("project-list-delete-title") }
                 id={ "modalProjectDelete".to_string() }
                 on_cancel={ ctx.link().callback(|x| Msg::Delete(DeleteMessage::AbortClick(x))) }
                 on_confirm={ ctx.link().callback(|x| Msg::Delete(DeleteMessage::AcceptClick(x))) }
             >
             <>
                 <h4>
-                    { "Warnung!" }
+                    { t("project-list-delete-warning") }
                 </h4>
                 <p>
-                    { "Kann " }
-                    <b>
-                        { "nicht rückgängig " }
-                    </b>
-                    { "gemacht werden!" }
+                    <b>{ t("project-list-delete-irreversible") }</b>
                 </p>
                 <p>
-                    <b>
-                        { "Alle Abgaben " }
-                    </b>
-                    { "werden " }
-                    <b>
-                        { "unwiederruflich " }
-                    </b>
-                    { " gelöscht!" }
+                    <b>{ t("project-list-delete-submissions") }</b>
                 </p>
                 <p>
-                    { "Das Projekt " }
-                    <i>if let Some(project) = &self.selected_delete {
-                        { &project.title }
+                    if let Some(project) = &self.selected_delete {
+                        { t_with("project-list-delete-confirm", &[("title", &project.title)]) }
                     }
                     else {
-                        { "Fehler! kein Projekt ausgewählt" }
+                        { t("project-list-none-selected") }
                     }
-                    </i>
-                    { " wirklich löschen?" }
                 </p>
             </>
             </DeleteModal>
@@ -292,69 +393,176 @@ impl Component for ProjectLists {
     }
 }
 
+impl ProjectLists {
+    /// Shows the projects the viewer may see in one list per state, skipping empty states.
+    fn grouped_lists(&self, ctx: &yew::Context<Self>, projects: &[ProjectTo]) -> Html {
+        let visible = match get_token_data() {
+            Ok(user) => projects
+                .iter()
+                .filter(|project| project.is_visible_to(user.user_id, user.is_admin))
+                .cloned()
+                .collect::<Vec<_>>(),
+            Err(_) => Vec::new(),
+        };
+
+        if visible.is_empty() {
+            return html! {
+                <ProjectList
+                    projects={ Vec::new() }
+                    on_change={ ctx.link().callback(|project| Msg::Update(UpdateMessage::Update(project))) }
+                    on_delete={ ctx.link().callback(|project| Msg::Delete(DeleteMessage::ListItemButtonClick(project))) }
+                    on_state_change={ ctx.link().callback(|(project, state)| Msg::State(StateMessage::Change(project, state))) }
+                    on_duplicate={ ctx.props().project_duplicate.clone() }
+                    on_save_template={ ctx.link().callback(|project| Msg::Template(TemplateMessage::Save(project))) }
+                />
+            };
+        }
+
+        html! {
+            {
+                for ProjectState::options().into_iter().filter_map(|state| {
+                    let projects = visible
+                        .iter()
+                        .filter(|project| project.state == state)
+                        .cloned()
+                        .collect::<Vec<_>>();
+
+                    if projects.is_empty() {
+                        return None;
+                    }
+
+                    Some(html! {
+                        <>
+                        <h5 class="mt-2">{ state }</h5>
+                        <ProjectList
+                            projects={ projects }
+                            on_change={ ctx.link().callback(|project| Msg::Update(UpdateMessage::Update(project))) }
+                            on_delete={ ctx.link().callback(|project| Msg::Delete(DeleteMessage::ListItemButtonClick(project))) }
+                            on_state_change={ ctx.link().callback(|(project, state)| Msg::State(StateMessage::Change(project, state))) }
+                            on_duplicate={ ctx.props().project_duplicate.clone() }
+                            on_save_template={ ctx.link().callback(|project| Msg::Template(TemplateMessage::Save(project))) }
+                        />
+                        </>
+                    })
+                })
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Properties)]
 struct ProjectListProperties {
     pub(crate) projects: Vec<ProjectTo>,
     pub(crate) on_delete: Callback<ProjectTo>,
     pub(crate) on_change: Callback<ProjectTo>,
+    pub(crate) on_state_change: Callback<(ProjectTo, ProjectState)>,
+    pub(crate) on_duplicate: Callback<ProjectTo>,
+    pub(crate) on_save_template: Callback<ProjectTo>,
 }
 
 #[function_component(ProjectList)]
 fn project_list(props: &ProjectListProperties) -> Html {
     let props = props.clone();
+    let read_only = is_viewing_as();
     html! {
         <div class="table-responsive">
             <table class="table table-striped">
                 <thead>
                     <tr>
                         <th>
-                            { "Stück" }
+                            { t("project-list-piece") }
                         </th>
                         <th>
-                            { "Abgabe bis" }
+                            { t("project-list-due") }
                         </th>
-                        <th colspan="2">
-                            { "Bearbeiten" }
+                        <th colspan="4">
+                            { t("project-list-edit") }
                         </th>
                     </tr>
                     </thead>
                         <tbody>
                         if props.projects.is_empty() {
-                            <td>{ "Es gibt keine Projekte in dieser Kategorie." }</td>
+                            <td>{ t("project-list-empty") }</td>
                         }
                         { for props.projects.iter().map(|project| {
                             let project_change = props.on_change.clone();
                             let project_delete = props.on_delete.clone();
                             let project_clone = project.clone();
                             let project_clone_2 = project.clone();
+                            let on_duplicate = props.on_duplicate.clone();
+                            let project_clone_3 = project.clone();
+                            let on_save_template = props.on_save_template.clone();
+                            let project_clone_4 = project.clone();
                             html!{
                             <tr>
                                 <td>
                                     <Link<Route> classes={classes!("navbar-item")} to={Route::Event{id: project.id}}>
                                         { &project.title }
                                     </Link<Route>>
+                                    { " " }
+                                    <span class={ project.state.css_class() }>{ project.state }</span>
                                 </td>
                                 <td>
-                                    { format_datetime_human_readable(&project.due) }
+                                    { format_datetime_human_readable(&viewer_due(project)) }
+                                    <br/>
+                                    <Countdown due={ viewer_due(project) }/>
                                 </td>
                                 <AdminOrOwner owner_id={ project.creator }>
                                 <td>
                                     <button
                                         class="btn btn-sm btn-outline-danger"
+                                        disabled={ read_only }
                                         onclick={ move |_| project_change.emit(project_clone.clone()) }
                                         data-bs-toggle="modal"
                                         data-bs-target={format!("#{MODAL_UPDATE_PROJECT}")}
                                     >
-                                        { "Bearbeiten" }
+                                        { t("project-list-edit") }
                                     </button>
                                 </td>
                                 <td>
                                     <button
                                         class="btn btn-sm btn-danger"
+                                        disabled={ read_only }
                                         onclick={ move |_| project_delete.emit(project_clone_2.clone()) }
                                         data-bs-toggle="modal" data-bs-target="#modalProjectDelete"
                                     >
-                                        { "Löschen" }
+                                        { t("project-list-delete") }
+                                    </button>
+                                </td>
+                                <td>
+                                {
+                                    for project.state.transitions().iter().map(|state| {
+                                        let on_state_change = props.on_state_change.clone();
+                                        let project = project.clone();
+                                        let state = *state;
+                                        html! {
+                                            <button
+                                                class="btn btn-sm btn-outline-secondary me-1"
+                                                disabled={ read_only }
+                                                onclick={ move |_| on_state_change.emit((project.clone(), state)) }
+                                            >
+                                                { state.action_label() }
+                                            </button>
+                                        }
+                                    })
+                                }
+                                </td>
+                                <td>
+                                    <button
+                                        class="btn btn-sm btn-outline-secondary me-1"
+                                        disabled={ read_only }
+                                        onclick={ move |_| on_duplicate.emit(project_clone_3.clone()) }
+                                        data-bs-toggle="modal"
+                                        data-bs-target={format!("#{MODAL_NEW_PROJECT}")}
+                                    >
+                                        { t("project-list-duplicate") }
+                                    </button>
+                                    <button
+                                        class="btn btn-sm btn-outline-secondary"
+                                        disabled={ read_only }
+                                        onclick={ move |_| on_save_template.emit(project_clone_4.clone()) }
+                                    >
+                                        { t("project-list-save-template") }
                                     </button>
                                 </td>
                                 </AdminOrOwner>
@@ -1,2 +1,16 @@
+use time::OffsetDateTime;
+
+use crate::{components::jwt_context::get_token_data, service::project::ProjectTo};
+
+pub mod calendar;
+pub mod import;
 pub mod list;
 pub mod modals;
+
+/// Deadline of the project for the section of the viewer.
+pub fn viewer_due(project: &ProjectTo) -> OffsetDateTime {
+    match get_token_data() {
+        Ok(user) => project.due_for(&user.section),
+        Err(_) => project.due,
+    }
+}
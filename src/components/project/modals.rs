@@ -1,15 +1,29 @@
-use gloo_dialogs::alert;
-use time::{macros::format_description, PrimitiveDateTime, Time};
-use web_sys::{Event, InputEvent, MouseEvent};
-use yew::{html, Callback, Component, Properties};
+use gloo_console::error;
+use gloo_dialogs::{alert, confirm};
+use time::{macros::format_description, Date, OffsetDateTime, PrimitiveDateTime, Time};
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement, InputEvent, MouseEvent};
+use yew::{classes, html, Callback, Component, Properties, TargetCast};
 
 use wasm_bindgen::prelude::wasm_bindgen;
 
 use crate::{
-    components::modal::Modal,
+    components::{enum_select::EnumSelect, jwt_context::get_token_data, modal::Modal},
+    i18n::{t, t_with},
     pages::home::{get_value_from_event, get_value_from_input_event},
-    service::project::{create_project, update_project, ProjectTo},
-    utilities::{date::now, requests::fetch::FetchError},
+    service::{
+        material::copy_material,
+        project::{
+            create_project, update_project, DeadlinePolicy, DeadlineRules, ProjectState, ProjectTo,
+            SectionDeadline,
+        },
+        section::Section,
+        template::{delete_template, ProjectTemplate, TemplateContent},
+    },
+    utilities::{
+        date::{local_time_zone, now, time_zones, wall_time_in_time_zone, wall_time_to_instant},
+        requests::fetch::FetchError,
+        select_enum::SelectEnum,
+    },
 };
 
 pub const MODAL_NEW_PROJECT: &str = "modalNewProject";
@@ -58,7 +72,7 @@ impl Component for ProjectUpdateModal {
             }
             UpdateMessage::ButtonClick(result) => {
                 if result.title.is_empty() {
-                    alert("Titel fehlt!");
+                    alert(&t("project-modal-title-missing"));
                     return false;
                 }
 
@@ -68,6 +82,8 @@ impl Component for ProjectUpdateModal {
                         result.title,
                         result.description,
                         result.due,
+                        result.time_zone,
+                        result.deadline_rules,
                     )
                     .await
                     {
@@ -94,12 +110,24 @@ pub enum CreateMessage {
     Success(ProjectTo),
     Fail(FetchError),
     ButtonClick(ModalResult),
+    /// The project was created, but its material could not be copied.
+    MaterialCopyFailed(ProjectTo, FetchError),
+    DeleteTemplate(i64),
+    TemplateDeleted(i64),
+    TemplateDeleteFailed(FetchError),
 }
 
 #[derive(PartialEq, Properties)]
 pub struct ProjectCreateProperties {
     pub on_success: Callback<ProjectTo>,
     pub on_error: Callback<FetchError>,
+    #[prop_or_default]
+    pub templates: Vec<ProjectTemplate>,
+    /// Filled into the modal whenever it changes, e.g. when a project is duplicated.
+    #[prop_or_default]
+    pub prefill: Option<TemplateContent>,
+    #[prop_or_default]
+    pub on_template_deleted: Callback<i64>,
 }
 
 impl Component for ProjectCreateModal {
@@ -122,25 +150,76 @@ impl Component for ProjectCreateModal {
             }
             CreateMessage::ButtonClick(result) => {
                 if result.title.is_empty() {
-                    alert("Titel fehlt!");
+                    alert(&t("project-modal-title-missing"));
                     return false;
                 }
 
+                let choir_id = get_token_data().ok().and_then(|user| user.choir_id);
+                let material_source = result.material_source;
+
                 ctx.link().send_future(async move {
-                    match create_project(result.title, result.description, result.due).await {
-                        Ok(result) => CreateMessage::Success(result),
-                        Err(error) => CreateMessage::Fail(error),
+                    let project = match create_project(
+                        result.title,
+                        result.description,
+                        result.due,
+                        result.time_zone,
+                        result.deadline_rules,
+                        result.state,
+                        choir_id,
+                    )
+                    .await
+                    {
+                        Ok(project) => project,
+                        Err(error) => return CreateMessage::Fail(error),
+                    };
+
+                    match material_source {
+                        Some(source) => match copy_material(source, project.id).await {
+                            Ok(_) => CreateMessage::Success(project),
+                            Err(error) => CreateMessage::MaterialCopyFailed(project, error),
+                        },
+                        None => CreateMessage::Success(project),
                     }
                 });
 
                 false
             }
+            CreateMessage::MaterialCopyFailed(project, error) => {
+                error!(format!("Could not copy the material: {error}"));
+                alert(&t("project-modal-material-copy-failed"));
+                ctx.props().on_success.emit(project);
+                false
+            }
+            CreateMessage::DeleteTemplate(template_id) => {
+                ctx.link().send_future(async move {
+                    match delete_template(template_id).await {
+                        Ok(()) => CreateMessage::TemplateDeleted(template_id),
+                        Err(error) => CreateMessage::TemplateDeleteFailed(error),
+                    }
+                });
+                false
+            }
+            CreateMessage::TemplateDeleted(template_id) => {
+                ctx.props().on_template_deleted.emit(template_id);
+                false
+            }
+            CreateMessage::TemplateDeleteFailed(error) => {
+                error!(format!("Could not delete the template: {error}"));
+                alert(&t("project-modal-template-delete-failed"));
+                false
+            }
         }
     }
 
     fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
         html! (
-            <ProjectEditModal on_submit={ctx.link().callback(CreateMessage::ButtonClick)} id={ MODAL_NEW_PROJECT }/>
+            <ProjectEditModal
+                on_submit={ctx.link().callback(CreateMessage::ButtonClick)}
+                id={ MODAL_NEW_PROJECT }
+                templates={ ctx.props().templates.clone() }
+                prefill={ ctx.props().prefill.clone() }
+                on_template_delete={ ctx.link().callback(CreateMessage::DeleteTemplate) }
+            />
         )
     }
 }
@@ -149,7 +228,14 @@ pub struct ModalResult {
     id: Option<i64>,
     title: String,
     description: String,
-    due: PrimitiveDateTime,
+    due: OffsetDateTime,
+    /// Time zone the deadline was entered in.
+    time_zone: String,
+    deadline_rules: DeadlineRules,
+    /// State new projects start in. Ignored when updating.
+    state: ProjectState,
+    /// Project whose material is copied into the new one.
+    material_source: Option<i64>,
 }
 
 #[derive(Clone, PartialEq, Properties)]
@@ -158,12 +244,35 @@ struct ProjectEditModalProperties {
     pub project: Option<ProjectTo>,
     pub on_submit: Callback<ModalResult>,
     pub id: String,
+    /// Templates new projects can start from.
+    #[prop_or_default]
+    pub templates: Vec<ProjectTemplate>,
+    #[prop_or_default]
+    pub prefill: Option<TemplateContent>,
+    #[prop_or_default]
+    pub on_template_delete: Callback<i64>,
 }
 
 struct ProjectEditModal {
     id: Option<i64>,
     title: String,
+    /// Deadline as shown on clocks in `time_zone`.
     due: PrimitiveDateTime,
+    time_zone: String,
+    time_zones: Vec<String>,
+    /// Section deadlines are kept in `section_deadlines` while editing.
+    deadline_rules: DeadlineRules,
+    /// The grace period that was typed last is no number of hours. `deadline_rules` keeps the last valid one.
+    grace_period_invalid: bool,
+    /// Deadlines of sections as shown on clocks in `time_zone`.
+    section_deadlines: Vec<(Section, PrimitiveDateTime)>,
+    draft: bool,
+    /// Content the project was started from. Its deadlines follow `start`.
+    template: Option<TemplateContent>,
+    /// The saved template that is selected.
+    template_id: Option<i64>,
+    start: Date,
+    copy_material: bool,
 }
 
 enum Msg {
@@ -173,6 +282,18 @@ enum Msg {
     CreateClick(MouseEvent),
     NameInput(InputEvent),
     DateInput(Event),
+    TimeZoneInput(Event),
+    PolicyInput(Result<DeadlinePolicy, ()>),
+    GracePeriodInput(InputEvent),
+    AddSectionDeadline,
+    RemoveSectionDeadline(usize),
+    SectionDeadlineSection(usize, Result<Section, ()>),
+    SectionDeadlineDate(usize, Event),
+    DraftInput(Event),
+    TemplateInput(Event),
+    DeleteTemplate,
+    StartInput(Event),
+    CopyMaterialInput(Event),
 }
 
 impl Component for ProjectEditModal {
@@ -180,17 +301,46 @@ impl Component for ProjectEditModal {
     type Properties = ProjectEditModalProperties;
 
     fn create(ctx: &yew::Context<Self>) -> Self {
+        let time_zones = time_zones();
+
         match &ctx.props().project {
-            Some(project) => Self {
-                id: Some(project.id),
-                due: project.due,
-                title: project.title.clone(),
-            },
-            None => Self {
-                id: None,
-                due: now().replace_time(Time::from_hms(23, 59, 59).unwrap()),
-                title: "".to_string(),
-            },
+            Some(project) => {
+                let time_zone = project_time_zone(project);
+                Self {
+                    id: Some(project.id),
+                    due: wall_time_in_time_zone(project.due, &time_zone),
+                    title: project.title.clone(),
+                    deadline_rules: editable_rules(project),
+                    grace_period_invalid: false,
+                    section_deadlines: wall_section_deadlines(project, &time_zone),
+                    draft: false,
+                    template: None,
+                    template_id: None,
+                    start: today(),
+                    copy_material: false,
+                    time_zone,
+                    time_zones,
+                }
+            }
+            None => {
+                let time_zone = local_time_zone();
+                Self {
+                    id: None,
+                    due: wall_time_in_time_zone(now(), &time_zone)
+                        .replace_time(Time::from_hms(23, 59, 59).unwrap()),
+                    title: "".to_string(),
+                    time_zone,
+                    time_zones,
+                    deadline_rules: DeadlineRules::default(),
+                    grace_period_invalid: false,
+                    section_deadlines: Vec::new(),
+                    draft: false,
+                    template: None,
+                    template_id: None,
+                    start: today(),
+                    copy_material: false,
+                }
+            }
         }
     }
 
@@ -201,42 +351,167 @@ impl Component for ProjectEditModal {
                 false
             }
             Msg::DateInput(event) => {
-                let date_string = get_value_from_event(event);
-
-                if date_string.is_empty() {
-                    return false;
+                if let Some(due) = parse_date_input(get_value_from_event(event)) {
+                    self.due = due;
                 }
-
-                self.due = match PrimitiveDateTime::parse(
-                    &date_string,
-                    format_description!(
-                        "[year]-[month]-[day]T[hour]:[minute]:[second]:[subsecond]"
-                    ),
-                ) {
-                    Ok(date_time) => date_time,
-                    Err(_e) => PrimitiveDateTime::parse(
-                        &date_string,
-                        format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]"),
-                    )
-                    .unwrap_or_else(|_| {
-                        PrimitiveDateTime::parse(
-                            &date_string,
-                            format_description!("[year]-[month]-[day]T[hour]:[minute]"),
-                        )
-                        .unwrap_or_else(|error| {
-                            panic!("Could not parse date {date_string}, error: {error}")
-                        })
-                    }),
-                };
+                false
+            }
+            Msg::TimeZoneInput(event) => {
+                self.time_zone = selected_value(event);
+                false
+            }
+            Msg::PolicyInput(policy) => match policy {
+                Ok(policy) => {
+                    self.deadline_rules.deadline_policy = policy;
+                    true
+                }
+                Err(_) => {
+                    error!("Selected deadline policy could not be parsed!");
+                    false
+                }
+            },
+            Msg::GracePeriodInput(event) => {
+                match get_value_from_input_event(event).trim().parse() {
+                    Ok(hours) => {
+                        self.deadline_rules.grace_period_hours = hours;
+                        self.grace_period_invalid = false;
+                    }
+                    Err(_) => self.grace_period_invalid = true,
+                }
+                true
+            }
+            Msg::AddSectionDeadline => {
+                let section = Section::options()
+                    .into_iter()
+                    .find(|section| {
+                        !self
+                            .section_deadlines
+                            .iter()
+                            .any(|(used, _)| used == section)
+                    })
+                    .unwrap_or_else(Section::first);
+                self.section_deadlines.push((section, self.due));
+                true
+            }
+            Msg::RemoveSectionDeadline(index) => {
+                if index < self.section_deadlines.len() {
+                    self.section_deadlines.remove(index);
+                }
+                true
+            }
+            Msg::SectionDeadlineSection(index, section) => {
+                match (section, self.section_deadlines.get_mut(index)) {
+                    (Ok(section), Some(deadline)) => deadline.0 = section,
+                    _ => error!("Selected section could not be parsed!"),
+                }
+                false
+            }
+            Msg::SectionDeadlineDate(index, event) => {
+                let date = parse_date_input(get_value_from_event(event));
+                if let (Some(date), Some(deadline)) = (date, self.section_deadlines.get_mut(index))
+                {
+                    deadline.1 = date;
+                }
+                false
+            }
+            Msg::DraftInput(event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                self.draft = input.checked();
+                false
+            }
+            Msg::TemplateInput(event) => {
+                let value = selected_value(event);
+                let template = ctx
+                    .props()
+                    .templates
+                    .iter()
+                    .find(|template| template.id.to_string() == value)
+                    .cloned();
+
+                match template {
+                    Some(template) => {
+                        self.template_id = Some(template.id);
+                        self.apply_template(ctx, template.content);
+                    }
+                    None => {
+                        self.template_id = None;
+                        self.template = None;
+                        self.copy_material = false;
+                    }
+                }
+                true
+            }
+            Msg::DeleteTemplate => {
+                let template = ctx
+                    .props()
+                    .templates
+                    .iter()
+                    .find(|template| Some(template.id) == self.template_id);
+
+                if let Some(template) = template {
+                    if confirm(&t_with(
+                        "project-modal-template-delete-confirm",
+                        &[("name", &template.name)],
+                    )) {
+                        ctx.props().on_template_delete.emit(template.id);
+                        self.template_id = None;
+                        self.template = None;
+                        self.copy_material = false;
+                        return true;
+                    }
+                }
+                false
+            }
+            Msg::StartInput(event) => {
+                let value = get_value_from_event(event);
+                if let Ok(start) = Date::parse(&value, format_description!("[year]-[month]-[day]"))
+                {
+                    self.start = start;
+                    self.apply_start();
+                }
+                true
+            }
+            Msg::CopyMaterialInput(event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                self.copy_material = input.checked();
                 false
             }
             Msg::AbortClick(_) => false,
             Msg::CreateClick(_) => {
+                if self.grace_period_invalid
+                    && self.deadline_rules.deadline_policy == DeadlinePolicy::GracePeriod
+                {
+                    alert(&t("project-modal-grace-period-invalid"));
+                    return false;
+                }
+
                 ctx.props().on_submit.emit(ModalResult {
                     id: self.id,
                     title: self.title.clone(),
                     description: get_tinymce_content(self.text_area_name(ctx)),
-                    due: self.due,
+                    due: wall_time_to_instant(self.due, &self.time_zone),
+                    time_zone: self.time_zone.clone(),
+                    deadline_rules: DeadlineRules {
+                        section_deadlines: self
+                            .section_deadlines
+                            .iter()
+                            .map(|(section, due)| SectionDeadline {
+                                section: section.clone(),
+                                due: wall_time_to_instant(*due, &self.time_zone),
+                            })
+                            .collect(),
+                        ..self.deadline_rules.clone()
+                    },
+                    state: if self.draft {
+                        ProjectState::Draft
+                    } else {
+                        ProjectState::Open
+                    },
+                    material_source: self
+                        .template
+                        .as_ref()
+                        .and_then(|template| template.material_project_id)
+                        .filter(|_| self.copy_material),
                 });
 
                 false
@@ -244,11 +519,22 @@ impl Component for ProjectEditModal {
         }
     }
 
-    fn changed(&mut self, ctx: &yew::Context<Self>, _old_props: &Self::Properties) -> bool {
+    fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().prefill != old_props.prefill {
+            if let Some(content) = ctx.props().prefill.clone() {
+                self.template_id = None;
+                self.apply_template(ctx, content);
+            }
+        }
+
         if let Some(project) = &ctx.props().project {
             self.id = Some(project.id);
-            self.due = project.due;
+            self.time_zone = project_time_zone(project);
+            self.due = wall_time_in_time_zone(project.due, &self.time_zone);
             self.title = project.title.clone();
+            self.deadline_rules = editable_rules(project);
+            self.grace_period_invalid = false;
+            self.section_deadlines = wall_section_deadlines(project, &self.time_zone);
         }
         true
     }
@@ -262,26 +548,22 @@ impl Component for ProjectEditModal {
             }});"
         );
 
-        let (title, description, date) = match &ctx.props().project {
-            Some(project) => (
-                project.title.clone(),
-                project.description.clone(),
-                format_date_to_end_of_day(project.due),
-            ),
-            None => ("".to_string(), "".to_string(), end_of_today()),
-        };
-
-        if !description.is_empty() {
-            set_tinymce_content(text_area_name.clone(), description);
+        if let Some(project) = &ctx.props().project {
+            if !project.description.is_empty() {
+                set_tinymce_content(text_area_name.clone(), project.description.clone());
+            }
         }
 
+        let creating = ctx.props().project.is_none();
+        let templates = &ctx.props().templates;
+
         html! (
             <Modal
-                title={"Projekt erstellen".to_string() }
+                title={ t("project-modal-title") }
                 id={ ctx.props().id.clone() }
                 actions = { vec![
-                    ("Abbrechen".to_string(), "btn btn-secondary".to_string(), ctx.link().callback(Msg::AbortClick)),
-                    ("Erstellen".to_string(), "btn btn-danger".to_string(),  ctx.link().callback(Msg::CreateClick))
+                    (t("modal-cancel"), "btn btn-secondary".to_string(), ctx.link().callback(Msg::AbortClick)),
+                    (t("project-modal-create"), "btn btn-danger".to_string(),  ctx.link().callback(Msg::CreateClick))
                     ]
                 }
             >
@@ -291,19 +573,135 @@ impl Component for ProjectEditModal {
                 </script>
 
                 <form id="createProjectForm" class="">
+                    if creating && !templates.is_empty() {
+                        <div class="row mb-2">
+                            <div class="col">
+                                <label for="selectCreateProjectTemplate">{ t("project-modal-template") }</label>
+                                <select id="selectCreateProjectTemplate" class="form-control" onchange={ ctx.link().callback(Msg::TemplateInput) }>
+                                    <option value="" selected={ self.template_id.is_none() }>{ t("project-modal-template-none") }</option>
+                                    {
+                                        for templates.iter().map(|template| html! {
+                                            <option value={ template.id.to_string() } selected={ self.template_id == Some(template.id) }>{ &template.name }</option>
+                                        })
+                                    }
+                                </select>
+                            </div>
+                            if self.template_id.is_some() {
+                                <div class="col-auto d-flex align-items-end">
+                                    <button type="button" class="btn btn-outline-danger" onclick={ ctx.link().callback(|_| Msg::DeleteTemplate) }>
+                                        { t("project-modal-template-delete") }
+                                    </button>
+                                </div>
+                            }
+                        </div>
+                    }
+                    if let Some(template) = self.template.as_ref().filter(|_| creating) {
+                        <div class="row mb-2">
+                            <div class="col">
+                                <label for="inputCreateProjectStart">{ t("project-modal-start") }</label>
+                                <input id="inputCreateProjectStart" type="date" class="form-control" value={ format_start_input(self.start) } onchange={ ctx.link().callback(Msg::StartInput) }/>
+                            </div>
+                            if template.material_project_id.is_some() {
+                                <div class="col d-flex align-items-end">
+                                    <div class="form-check">
+                                        <input id="checkCreateProjectCopyMaterial" type="checkbox" class="form-check-input" checked={ self.copy_material } onchange={ ctx.link().callback(Msg::CopyMaterialInput) }/>
+                                        <label for="checkCreateProjectCopyMaterial" class="form-check-label">{ t("project-modal-copy-material") }</label>
+                                    </div>
+                                </div>
+                            }
+                        </div>
+                    }
                     <div class="row">
                         <div class="col">
-                            <label for="inputCreateProjectTitle">{ "Name des Projektes" }</label>
-                            <input id="inputCreateProjectTitle" type="text" class="form-control" value={title} placeholder="Name des Projektes" oninput={ ctx.link().callback(Msg::NameInput) }/>
+                            <label for="inputCreateProjectTitle">{ t("project-modal-name") }</label>
+                            <input id="inputCreateProjectTitle" type="text" class="form-control" value={ self.title.clone() } placeholder={ t("project-modal-name") } oninput={ ctx.link().callback(Msg::NameInput) }/>
+                        </div>
+                        <div class="col">
+                            <label for="inputCreateProjectDueDate">{ t("project-modal-due") }</label>
+                            <input id="inputCreateProjectDueDate" type="datetime-local" class="form-control" value={ format_date_input(self.due) } onchange={ ctx.link().callback(Msg::DateInput) }/>
+                        </div>
+                        <div class="col">
+                            <label for="selectCreateProjectTimeZone">{ t("project-modal-time-zone") }</label>
+                            <select id="selectCreateProjectTimeZone" class="form-control" onchange={ ctx.link().callback(Msg::TimeZoneInput) }>
+                            {
+                                for self.time_zones.iter().map(|zone| html! {
+                                    <option value={ zone.clone() } selected={ *zone == self.time_zone }>{ zone }</option>
+                                })
+                            }
+                            </select>
+                        </div>
+                    </div>
+                    <div class="row mt-2">
+                        <div class="col">
+                            <label for="selectCreateProjectDeadlinePolicy">{ t("project-modal-deadline-policy") }</label>
+                            <EnumSelect<DeadlinePolicy>
+                                id="selectCreateProjectDeadlinePolicy"
+                                name="deadline_policy"
+                                selected={ self.deadline_rules.deadline_policy }
+                                on_input={ ctx.link().callback(Msg::PolicyInput) }
+                            />
                         </div>
+                        if self.deadline_rules.deadline_policy == DeadlinePolicy::GracePeriod {
+                            <div class="col">
+                                <label for="inputCreateProjectGracePeriod">{ t("project-modal-grace-period") }</label>
+                                <input
+                                    id="inputCreateProjectGracePeriod"
+                                    type="number"
+                                    min="0"
+                                    class={ classes!("form-control", self.grace_period_invalid.then_some("is-invalid")) }
+                                    value={ self.deadline_rules.grace_period_hours.to_string() }
+                                    oninput={ ctx.link().callback(Msg::GracePeriodInput) }
+                                />
+                                <div class="invalid-feedback">{ t("project-modal-grace-period-invalid") }</div>
+                            </div>
+                        }
+                    </div>
+                    <div class="row mt-2">
                         <div class="col">
-                            <label for="inputCreateProjectDueDate">{ "Abgabedatum" }</label>
-                            <input id="inputCreateProjectDueDate" type="datetime-local" class="form-control" value={date} onchange={ ctx.link().callback(Msg::DateInput) }/>
+                            <label>{ t("project-modal-section-deadlines") }</label>
+                            {
+                                for self.section_deadlines.iter().enumerate().map(|(index, (section, due))| html! {
+                                    <div class="row mb-2">
+                                        <div class="col">
+                                            <EnumSelect<Section>
+                                                id={ format!("selectSectionDeadline{}{index}", ctx.props().id) }
+                                                name="section"
+                                                selected={ section.clone() }
+                                                on_input={ ctx.link().callback(move |section| Msg::SectionDeadlineSection(index, section)) }
+                                            />
+                                        </div>
+                                        <div class="col">
+                                            <input
+                                                type="datetime-local"
+                                                class="form-control"
+                                                value={ format_date_input(*due) }
+                                                onchange={ ctx.link().callback(move |event| Msg::SectionDeadlineDate(index, event)) }
+                                            />
+                                        </div>
+                                        <div class="col-auto">
+                                            <button type="button" class="btn btn-outline-danger" onclick={ ctx.link().callback(move |_| Msg::RemoveSectionDeadline(index)) }>
+                                                { t("project-modal-section-deadline-remove") }
+                                            </button>
+                                        </div>
+                                    </div>
+                                })
+                            }
+                            <div>
+                                <button type="button" class="btn btn-sm btn-outline-danger" onclick={ ctx.link().callback(|_| Msg::AddSectionDeadline) }>
+                                    { t("project-modal-section-deadline-add") }
+                                </button>
+                            </div>
                         </div>
                     </div>
+                    if creating {
+                        <div class="form-check mt-2">
+                            <input id="checkCreateProjectDraft" type="checkbox" class="form-check-input" checked={ self.draft } onchange={ ctx.link().callback(Msg::DraftInput) }/>
+                            <label for="checkCreateProjectDraft" class="form-check-label">{ t("project-modal-draft") }</label>
+                        </div>
+                    }
                     <div class="row mt-2">
                         <div class="col">
-                            <label for={ text_area_name.clone() }>{ "Beschreibung" }</label>
+                            <label for={ text_area_name.clone() }>{ t("project-modal-description") }</label>
                             <textarea id={ text_area_name }></textarea>
                         </div>
                     </div>
@@ -319,13 +717,109 @@ impl ProjectEditModal {
     fn text_area_name(&self, ctx: &yew::Context<Self>) -> String {
         format!("textarea{}", ctx.props().id)
     }
+
+    /// Fills in everything of the template, with the deadlines counted from the chosen start date.
+    fn apply_template(&mut self, ctx: &yew::Context<Self>, content: TemplateContent) {
+        self.title = content.title.clone();
+        set_tinymce_content(self.text_area_name(ctx), content.description.clone());
+        self.time_zone = content
+            .due_time_zone
+            .clone()
+            .unwrap_or_else(local_time_zone);
+        self.deadline_rules = DeadlineRules {
+            deadline_policy: content.deadline_policy,
+            grace_period_hours: content.grace_period_hours,
+            section_deadlines: Vec::new(),
+        };
+        self.grace_period_invalid = false;
+        self.copy_material = content.material_project_id.is_some();
+        self.template = Some(content);
+        self.apply_start();
+    }
+
+    fn apply_start(&mut self) {
+        if let Some(template) = &self.template {
+            self.due = template.due_from(self.start);
+            self.section_deadlines = template.section_deadlines_from(self.start);
+        }
+    }
 }
 
-fn end_of_today() -> String {
-    format_date_to_end_of_day(now())
+/// Projects created before the time zone was stored are edited in the one of the viewer.
+fn project_time_zone(project: &ProjectTo) -> String {
+    project
+        .due_time_zone
+        .clone()
+        .unwrap_or_else(local_time_zone)
 }
 
-fn format_date_to_end_of_day(date_time: PrimitiveDateTime) -> String {
-    let format = format_description!("[year]-[month]-[day]T23:59");
+/// The rules of the project without its section deadlines, which are edited separately.
+fn editable_rules(project: &ProjectTo) -> DeadlineRules {
+    DeadlineRules {
+        section_deadlines: Vec::new(),
+        ..project.deadline_rules.clone()
+    }
+}
+
+fn wall_section_deadlines(
+    project: &ProjectTo,
+    time_zone: &str,
+) -> Vec<(Section, PrimitiveDateTime)> {
+    project
+        .deadline_rules
+        .section_deadlines
+        .iter()
+        .map(|deadline| {
+            (
+                deadline.section.clone(),
+                wall_time_in_time_zone(deadline.due, time_zone),
+            )
+        })
+        .collect()
+}
+
+/// Parses the value of a `datetime-local` input, which browsers send with or without seconds. [None] if it is empty.
+fn parse_date_input(date_string: String) -> Option<PrimitiveDateTime> {
+    if date_string.is_empty() {
+        return None;
+    }
+
+    let date_time = match PrimitiveDateTime::parse(
+        &date_string,
+        format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]:[subsecond]"),
+    ) {
+        Ok(date_time) => date_time,
+        Err(_e) => PrimitiveDateTime::parse(
+            &date_string,
+            format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]"),
+        )
+        .unwrap_or_else(|_| {
+            PrimitiveDateTime::parse(
+                &date_string,
+                format_description!("[year]-[month]-[day]T[hour]:[minute]"),
+            )
+            .unwrap_or_else(|error| panic!("Could not parse date {date_string}, error: {error}"))
+        }),
+    };
+
+    Some(date_time)
+}
+
+fn selected_value(event: Event) -> String {
+    let select: HtmlSelectElement = event.target_unchecked_into();
+    select.value()
+}
+
+fn today() -> Date {
+    wall_time_in_time_zone(now(), &local_time_zone()).date()
+}
+
+fn format_date_input(date_time: PrimitiveDateTime) -> String {
+    let format = format_description!("[year]-[month]-[day]T[hour]:[minute]");
     date_time.format(&format).unwrap()
 }
+
+fn format_start_input(date: Date) -> String {
+    date.format(format_description!("[year]-[month]-[day]"))
+        .unwrap()
+}
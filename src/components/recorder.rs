@@ -0,0 +1,613 @@
+use gloo_console::error;
+use gloo_dialogs::alert;
+use gloo_storage::{LocalStorage, Storage};
+use gloo_timers::callback::Interval;
+use serde::Deserialize;
+use time::{macros::format_description, OffsetDateTime};
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+use web_sys::{Blob, Event, File, HtmlInputElement, HtmlSelectElement, Url};
+use yew::{html, Callback, Component, Context, Html, Properties, TargetCast};
+
+use crate::{
+    i18n::{t, t_with},
+    service::submission::SubmissionKind,
+    utilities::{
+        date::{format_elapsed, now, to_viewer_time},
+        latency::detect_latency,
+    },
+};
+
+#[wasm_bindgen(module = "/js/custom/recorder.js")]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    async fn start_recording(
+        with_video: bool,
+        preview_id: &str,
+        playback_url: &str,
+        count_in_beats: u32,
+    ) -> Result<JsValue, JsValue>;
+    fn recording_level() -> f64;
+    fn playback_offset_seconds() -> f64;
+    #[wasm_bindgen(catch)]
+    async fn stop_recording() -> Result<JsValue, JsValue>;
+    fn cancel_recording();
+    #[wasm_bindgen(catch)]
+    fn attach_to_file_input(
+        form_id: &str,
+        field_name: &str,
+        blob: &Blob,
+        file_name: &str,
+    ) -> Result<File, JsValue>;
+    #[wasm_bindgen(catch)]
+    async fn measure_loopback(clicks: u32) -> Result<JsValue, JsValue>;
+}
+
+/// Time between two updates of the level meter in milliseconds.
+const METER_MILLIS: u32 = 100;
+
+const PREVIEW_ID: &str = "recorderPreview";
+
+/// The latency is a property of the device, so it is measured once and kept in the browser.
+const LATENCY_STORAGE_KEY: &str = "recorderLatencyMs";
+
+const DEFAULT_COUNT_IN_BEATS: u32 = 4;
+const MAX_COUNT_IN_BEATS: u32 = 16;
+
+/// Clicks played during the latency test.
+const CALIBRATION_CLICKS: u32 = 6;
+
+pub enum Msg {
+    VideoInput(Event),
+    PlaybackInput(Event),
+    CountInInput(Event),
+    Calibrate,
+    Calibrated(Option<f64>),
+    CalibrationFailed(String),
+    Start,
+    Started(f64),
+    StartFailed(String),
+    Tick,
+    Stop,
+    Stopped(Blob, Option<PlaybackSync>),
+    StopFailed(String),
+    Retake,
+    Use,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct RecorderProperties {
+    /// Form with the file input the recording is put into.
+    pub form_id: String,
+    pub field_name: String,
+    /// Called with the kind of the recording and its file once it was put into the form.
+    pub on_recorded: Callback<(SubmissionKind, File)>,
+    #[prop_or_default]
+    pub disabled: bool,
+    /// Material members can sing along to while recording.
+    #[prop_or_default]
+    pub playbacks: Vec<Playback>,
+    /// Whether the recording is still the file in the form. The page clears it once the upload succeeded or another
+    /// file was chosen, so the sync of the recording is not sent with other files.
+    #[prop_or_default]
+    pub attached: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Playback {
+    pub material_id: i64,
+    pub title: String,
+    pub url: String,
+}
+
+/// How a sing-along recording lines up with its playback. Uploaded with the submission, so the conductor can align the tracks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlaybackSync {
+    material_id: i64,
+    /// Milliseconds into the recording at which the voice matches the start of the playback.
+    offset_ms: i64,
+}
+
+struct Recording {
+    blob: Blob,
+    /// Object url of the blob, so it can be played back.
+    url: String,
+    video: bool,
+    sync: Option<PlaybackSync>,
+}
+
+/// Result of `measure_loopback`.
+#[derive(Deserialize)]
+struct Loopback {
+    samples: Vec<f32>,
+    sample_rate: f32,
+    click_times: Vec<f64>,
+}
+
+enum RecorderState {
+    Idle,
+    Starting,
+    Recording {
+        started_at: OffsetDateTime,
+        sync: Option<PlaybackSync>,
+    },
+    Stopping,
+    Recorded {
+        recording: Recording,
+        used: bool,
+    },
+}
+
+/// Records the microphone (and optionally the camera) in the browser, so members can submit without transferring files.
+pub struct Recorder {
+    state: RecorderState,
+    with_video: bool,
+    /// Material id of the playback to sing along to, none to record without.
+    playback: Option<i64>,
+    count_in_beats: u32,
+    latency_ms: Option<f64>,
+    calibrating: bool,
+    level: f64,
+    elapsed_seconds: i64,
+    meter: Option<Interval>,
+}
+
+impl Component for Recorder {
+    type Message = Msg;
+    type Properties = RecorderProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            state: RecorderState::Idle,
+            with_video: false,
+            playback: None,
+            count_in_beats: DEFAULT_COUNT_IN_BEATS,
+            latency_ms: LocalStorage::get(LATENCY_STORAGE_KEY).ok(),
+            calibrating: false,
+            level: 0.0,
+            elapsed_seconds: 0,
+            meter: None,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if old_props.attached && !ctx.props().attached {
+            if let RecorderState::Recorded { used, .. } = &mut self.state {
+                *used = false;
+            }
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::VideoInput(event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                self.with_video = input.checked();
+                true
+            }
+            Msg::PlaybackInput(event) => {
+                let select: HtmlSelectElement = event.target_unchecked_into();
+                self.playback = select.value().parse().ok();
+                true
+            }
+            Msg::CountInInput(event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                self.count_in_beats = input
+                    .value()
+                    .parse::<u32>()
+                    .map_or(DEFAULT_COUNT_IN_BEATS, |beats| {
+                        beats.min(MAX_COUNT_IN_BEATS)
+                    });
+                true
+            }
+            Msg::Calibrate => {
+                self.calibrating = true;
+                ctx.link().send_future(async {
+                    let loopback = match measure_loopback(CALIBRATION_CLICKS).await {
+                        Ok(value) => value,
+                        Err(error) => return Msg::CalibrationFailed(format!("{error:?}")),
+                    };
+                    match serde_wasm_bindgen::from_value::<Loopback>(loopback) {
+                        Ok(loopback) => Msg::Calibrated(detect_latency(
+                            &loopback.samples,
+                            loopback.sample_rate,
+                            &loopback.click_times,
+                        )),
+                        Err(error) => Msg::CalibrationFailed(error.to_string()),
+                    }
+                });
+                true
+            }
+            Msg::Calibrated(latency_ms) => {
+                self.calibrating = false;
+                let Some(latency_ms) = latency_ms else {
+                    alert(&t("recorder-calibration-not-heard"));
+                    return true;
+                };
+                self.latency_ms = Some(latency_ms);
+                if let Err(error) = LocalStorage::set(LATENCY_STORAGE_KEY, latency_ms) {
+                    error!(format!("Could not store latency: {error}"));
+                }
+                true
+            }
+            Msg::CalibrationFailed(message) => {
+                error!(format!("Could not measure latency: {message}"));
+                alert(&t("recorder-calibration-failed"));
+                self.calibrating = false;
+                true
+            }
+            Msg::Start => {
+                self.discard_recording();
+                self.state = RecorderState::Starting;
+                let with_video = self.with_video;
+                let playback_url = self
+                    .selected_playback(ctx)
+                    .map(|playback| playback.url.clone())
+                    .unwrap_or_default();
+                let count_in_beats = self.count_in_beats;
+
+                ctx.link().send_future(async move {
+                    match start_recording(with_video, PREVIEW_ID, &playback_url, count_in_beats)
+                        .await
+                    {
+                        Ok(_) => Msg::Started(playback_offset_seconds()),
+                        Err(error) => Msg::StartFailed(format!("{error:?}")),
+                    }
+                });
+                true
+            }
+            Msg::Started(playback_offset) => {
+                let latency_ms = self.latency_ms;
+                let sync = self.selected_playback(ctx).and_then(|playback| {
+                    Some(PlaybackSync {
+                        material_id: playback.material_id,
+                        offset_ms: sync_offset_ms(playback_offset, latency_ms)?,
+                    })
+                });
+                self.state = RecorderState::Recording {
+                    started_at: now(),
+                    sync,
+                };
+                self.elapsed_seconds = 0;
+                let link = ctx.link().clone();
+                self.meter = Some(Interval::new(METER_MILLIS, move || {
+                    link.send_message(Msg::Tick)
+                }));
+                true
+            }
+            Msg::StartFailed(message) => {
+                error!(format!("Could not start recording: {message}"));
+                alert(&t("recorder-start-failed"));
+                self.state = RecorderState::Idle;
+                true
+            }
+            Msg::Tick => {
+                if let RecorderState::Recording { started_at, .. } = self.state {
+                    self.level = recording_level();
+                    self.elapsed_seconds = (now() - started_at).whole_seconds();
+                    return true;
+                }
+                false
+            }
+            Msg::Stop => {
+                let RecorderState::Recording { sync, .. } = self.state else {
+                    return false;
+                };
+                self.meter = None;
+                self.level = 0.0;
+                self.state = RecorderState::Stopping;
+
+                ctx.link().send_future(async move {
+                    match stop_recording().await.map(JsCast::dyn_into::<Blob>) {
+                        Ok(Ok(blob)) => Msg::Stopped(blob, sync),
+                        Ok(Err(value)) => Msg::StopFailed(format!("Not a blob: {value:?}")),
+                        Err(error) => Msg::StopFailed(format!("{error:?}")),
+                    }
+                });
+                true
+            }
+            Msg::Stopped(blob, sync) => {
+                let url = match Url::create_object_url_with_blob(&blob) {
+                    Ok(url) => url,
+                    Err(error) => {
+                        ctx.link()
+                            .send_message(Msg::StopFailed(format!("{error:?}")));
+                        return false;
+                    }
+                };
+                let video = self.with_video;
+                self.state = RecorderState::Recorded {
+                    recording: Recording {
+                        blob,
+                        url,
+                        video,
+                        sync,
+                    },
+                    used: false,
+                };
+                true
+            }
+            Msg::StopFailed(message) => {
+                error!(format!("Could not finish recording: {message}"));
+                alert(&t("recorder-stop-failed"));
+                self.state = RecorderState::Idle;
+                true
+            }
+            Msg::Retake => {
+                self.discard_recording();
+                self.state = RecorderState::Idle;
+                true
+            }
+            Msg::Use => {
+                let RecorderState::Recorded { recording, used } = &mut self.state else {
+                    return false;
+                };
+
+                let file_name =
+                    recording_file_name(&recording.blob.type_(), recording.video, now());
+                let props = ctx.props();
+                match attach_to_file_input(
+                    &props.form_id,
+                    &props.field_name,
+                    &recording.blob,
+                    &file_name,
+                ) {
+                    Ok(file) => {
+                        *used = true;
+                        props
+                            .on_recorded
+                            .emit((recording_kind(recording.video), file));
+                    }
+                    Err(error) => {
+                        error!(format!(
+                            "Could not add the recording to the form: {error:?}"
+                        ));
+                        alert(&t("recorder-use-failed"));
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let disabled = ctx.props().disabled;
+        let recording = matches!(self.state, RecorderState::Recording { .. });
+        let busy = matches!(
+            self.state,
+            RecorderState::Starting | RecorderState::Stopping
+        );
+
+        html! {
+            <div class="card">
+                <div class="card-body">
+                    <h5 class="card-title">{ t("recorder-title") }</h5>
+                    <div class="form-check">
+                        <input
+                            id="checkRecorderVideo"
+                            type="checkbox"
+                            class="form-check-input"
+                            checked={ self.with_video }
+                            disabled={ recording || busy }
+                            onchange={ ctx.link().callback(Msg::VideoInput) }
+                        />
+                        <label for="checkRecorderVideo" class="form-check-label">{ t("recorder-with-video") }</label>
+                    </div>
+                    if !ctx.props().playbacks.is_empty() {
+                        <div class="row mt-2 g-2 align-items-end">
+                            <div class="col">
+                                <label for="selectRecorderPlayback" class="form-label">{ t("recorder-playback") }</label>
+                                <select
+                                    id="selectRecorderPlayback"
+                                    class="form-select"
+                                    disabled={ recording || busy }
+                                    onchange={ ctx.link().callback(Msg::PlaybackInput) }
+                                >
+                                    <option value="" selected={ self.playback.is_none() }>{ t("recorder-no-playback") }</option>
+                                    { for ctx.props().playbacks.iter().map(|playback| html! {
+                                        <option
+                                            value={ playback.material_id.to_string() }
+                                            selected={ self.playback == Some(playback.material_id) }
+                                        >
+                                            { &playback.title }
+                                        </option>
+                                    }) }
+                                </select>
+                            </div>
+                            <div class="col-auto">
+                                <label for="inputRecorderCountIn" class="form-label">{ t("recorder-count-in") }</label>
+                                <input
+                                    id="inputRecorderCountIn"
+                                    type="number"
+                                    class="form-control"
+                                    min="0"
+                                    max={ MAX_COUNT_IN_BEATS.to_string() }
+                                    value={ self.count_in_beats.to_string() }
+                                    disabled={ recording || busy || self.playback.is_none() }
+                                    onchange={ ctx.link().callback(Msg::CountInInput) }
+                                />
+                            </div>
+                        </div>
+                        if self.playback.is_some() {
+                            <p class="form-text mb-0">{ t("recorder-headphones") }</p>
+                        }
+                        <div class="d-flex align-items-center mt-2">
+                            <span class="me-2">
+                                {
+                                    match self.latency_ms {
+                                        Some(latency) => t_with("recorder-latency", &[("latency", &format!("{latency:.0}"))]),
+                                        None => t("recorder-latency-unknown"),
+                                    }
+                                }
+                            </span>
+                            <button
+                                type="button"
+                                class="btn btn-sm btn-outline-secondary"
+                                disabled={ recording || busy || self.calibrating }
+                                onclick={ ctx.link().callback(|_| Msg::Calibrate) }
+                            >
+                                { if self.calibrating { t("recorder-calibrating") } else { t("recorder-calibrate") } }
+                            </button>
+                        </div>
+                    }
+                    <video
+                        id={ PREVIEW_ID }
+                        class={ if self.with_video && (recording || busy) { "w-100 mt-2" } else { "d-none" } }
+                        playsinline=true
+                    ></video>
+                    if recording {
+                        <div class="progress mt-2" title={ t("recorder-level") }>
+                            <div
+                                class={ if self.level > 0.95 { "progress-bar bg-danger" } else { "progress-bar bg-success" } }
+                                role="progressbar"
+                                style={ format!("width: {}%", (self.level * 100.0).round()) }
+                            ></div>
+                        </div>
+                        <p class="mt-1">{ t_with("recorder-elapsed", &[("time", &format_elapsed(self.elapsed_seconds))]) }</p>
+                    }
+                    if let RecorderState::Recorded { recording, used } = &self.state {
+                        <div class="mt-2">
+                            if recording.video {
+                                <video class="w-100" controls=true src={ recording.url.clone() }></video>
+                            } else {
+                                <audio class="w-100" controls=true src={ recording.url.clone() }></audio>
+                            }
+                        </div>
+                        if *used && ctx.props().attached {
+                            <div class="alert alert-success mt-2 mb-0">{ t("recorder-used") }</div>
+                            if let Some(sync) = recording.sync {
+                                <input type="hidden" name="playback_material_id" value={ sync.material_id.to_string() } />
+                                <input type="hidden" name="sync_offset_ms" value={ sync.offset_ms.to_string() } />
+                            }
+                        }
+                    }
+                    <div class="mt-2">
+                        {
+                            match &self.state {
+                                RecorderState::Idle | RecorderState::Starting => html! {
+                                    <button type="button" class="btn btn-danger" disabled={ disabled || busy || self.calibrating } onclick={ ctx.link().callback(|_| Msg::Start) }>
+                                        { t("recorder-start") }
+                                    </button>
+                                },
+                                RecorderState::Recording { .. } | RecorderState::Stopping => html! {
+                                    <button type="button" class="btn btn-danger" disabled={ busy } onclick={ ctx.link().callback(|_| Msg::Stop) }>
+                                        { t("recorder-stop") }
+                                    </button>
+                                },
+                                RecorderState::Recorded { used, .. } => html! {
+                                    <>
+                                    <button type="button" class="btn btn-outline-danger me-2" disabled={ disabled } onclick={ ctx.link().callback(|_| Msg::Retake) }>
+                                        { t("recorder-retake") }
+                                    </button>
+                                    <button type="button" class="btn btn-danger" disabled={ disabled || *used } onclick={ ctx.link().callback(|_| Msg::Use) }>
+                                        { t("recorder-use") }
+                                    </button>
+                                    </>
+                                },
+                            }
+                        }
+                    </div>
+                </div>
+            </div>
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.meter = None;
+        if matches!(
+            self.state,
+            RecorderState::Starting | RecorderState::Recording { .. }
+        ) {
+            cancel_recording();
+        }
+        self.discard_recording();
+    }
+}
+
+impl Recorder {
+    fn selected_playback<'a>(&self, ctx: &'a Context<Self>) -> Option<&'a Playback> {
+        let material_id = self.playback?;
+        ctx.props()
+            .playbacks
+            .iter()
+            .find(|playback| playback.material_id == material_id)
+    }
+
+    /// Frees the memory of the last recording.
+    fn discard_recording(&mut self) {
+        if let RecorderState::Recorded { recording, .. } = &self.state {
+            if let Err(error) = Url::revoke_object_url(&recording.url) {
+                error!(error);
+            }
+        }
+    }
+}
+
+fn recording_kind(video: bool) -> SubmissionKind {
+    if video {
+        SubmissionKind::Video
+    } else {
+        SubmissionKind::Audio
+    }
+}
+
+/// Name of the uploaded file, with an extension matching the format the browser recorded in.
+fn recording_file_name(mime_type: &str, video: bool, at: OffsetDateTime) -> String {
+    let container = mime_type.split(';').next().unwrap_or_default();
+    let extension = match container {
+        "audio/ogg" => "ogg",
+        "audio/mp4" => "m4a",
+        "video/mp4" => "mp4",
+        "audio/mpeg" => "mp3",
+        "audio/wav" | "audio/wave" => "wav",
+        _ => "webm",
+    };
+    let prefix = if video { "video" } else { "recording" };
+    let date = to_viewer_time(at)
+        .format(format_description!(
+            "[year]-[month]-[day]_[hour]-[minute]-[second]"
+        ))
+        .unwrap();
+    format!("{prefix}_{date}.{extension}")
+}
+
+/// Where the start of the playback is heard in the recording: when it was played plus the time until the singer's voice was recorded.
+/// [None] if nothing was played.
+fn sync_offset_ms(playback_offset_seconds: f64, latency_ms: Option<f64>) -> Option<i64> {
+    if !playback_offset_seconds.is_finite() {
+        return None;
+    }
+    Some((playback_offset_seconds * 1000.0 + latency_ms.unwrap_or_default()).round() as i64)
+}
+
+#[cfg(test)]
+mod recorder_test {
+    use time::macros::datetime;
+
+    use crate::service::submission::SubmissionKind;
+
+    use super::{recording_file_name, recording_kind, sync_offset_ms};
+
+    #[test]
+    fn file_name_matches_format() {
+        let at = datetime!(2026-10-18 20:15:30 UTC);
+        assert!(recording_file_name("audio/webm;codecs=opus", false, at).ends_with(".webm"));
+        assert!(recording_file_name("audio/ogg;codecs=opus", false, at).ends_with(".ogg"));
+        assert!(recording_file_name("audio/mp4", false, at).ends_with(".m4a"));
+        assert!(recording_file_name("video/mp4", true, at).starts_with("video_"));
+        assert!(recording_file_name("", false, at).ends_with(".webm"));
+    }
+
+    #[test]
+    fn kind_follows_camera() {
+        assert_eq!(recording_kind(false), SubmissionKind::Audio);
+        assert_eq!(recording_kind(true), SubmissionKind::Video);
+    }
+
+    #[test]
+    fn sync_offset_includes_latency() {
+        assert_eq!(sync_offset_ms(4.25, Some(120.4)), Some(4370));
+        assert_eq!(sync_offset_ms(4.25, None), Some(4250));
+        assert_eq!(sync_offset_ms(f64::NAN, Some(120.0)), None);
+    }
+}
@@ -0,0 +1,335 @@
+use std::{collections::HashMap, rc::Rc};
+
+use serde::Serialize;
+use web_sys::{Event, HtmlSelectElement};
+use yew::{function_component, html, Callback, Html, Properties, TargetCast};
+
+use crate::{
+    components::recorder::Playback,
+    i18n::{t, t_with},
+    service::{
+        section::Section,
+        submission::{Submission, SubmissionKind},
+    },
+    utilities::{
+        alignment::{align, onset_envelope, Alignment},
+        audio::decode_url,
+    },
+};
+
+/// Alignment of an uploaded audio submission to the reference playback, started from the list.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlignmentState {
+    Running,
+    /// [None] if the submission could not be aligned, e.g. because it is silent.
+    Done(Option<Alignment>),
+    Failed,
+}
+
+/// Downloads the reference and reduces it to what is compared with the submissions.
+pub async fn reference_envelope(url: &str) -> Result<Rc<Vec<f32>>, String> {
+    let audio = decode_url(url).await?;
+    Ok(Rc::new(onset_envelope(&audio.mono(), audio.sample_rate)))
+}
+
+/// Downloads a submission and aligns it to the envelope of the reference.
+pub async fn align_url(url: &str, reference: Rc<Vec<f32>>) -> Result<Option<Alignment>, String> {
+    let audio = decode_url(url).await?;
+    Ok(align(
+        &onset_envelope(&audio.mono(), audio.sample_rate),
+        &reference,
+    ))
+}
+
+/// The playback most submissions were recorded along to, else the first one.
+pub fn default_reference(submissions: &[Submission], references: &[Playback]) -> Option<i64> {
+    let mut counts = HashMap::new();
+    for id in submissions.iter().filter_map(|s| s.playback_material_id) {
+        *counts.entry(id).or_insert(0) += 1;
+    }
+    references
+        .iter()
+        .enumerate()
+        // Earlier references win ties.
+        .max_by_key(|(index, reference)| {
+            (
+                counts.get(&reference.material_id).copied().unwrap_or(0),
+                usize::MAX - index,
+            )
+        })
+        .map(|(_, reference)| reference.material_id)
+}
+
+#[derive(Serialize)]
+struct Manifest<'a> {
+    project_id: i64,
+    project_title: &'a str,
+    reference_material_id: i64,
+    reference_title: &'a str,
+    tracks: Vec<ManifestTrack<'a>>,
+}
+
+/// A track of the manifest. Offsets are milliseconds into the track at which the reference starts.
+#[derive(Serialize)]
+struct ManifestTrack<'a> {
+    submission_id: i64,
+    file_name: &'a str,
+    creator_name: &'a str,
+    section: &'a Section,
+    offset_ms: Option<i64>,
+    confidence: Option<f64>,
+    /// Offset measured while recording along to the playback, if the submission was recorded on the project page.
+    recorded_offset_ms: Option<i64>,
+}
+
+/// Lists the audio submissions with their offsets, so the tracks can be lined up in an editor or a script.
+pub fn manifest_json(
+    project_id: i64,
+    project_title: &str,
+    reference: &Playback,
+    submissions: &[Submission],
+    alignments: &HashMap<i64, AlignmentState>,
+) -> String {
+    let tracks = submissions
+        .iter()
+        .filter(|submission| submission.kind == SubmissionKind::Audio)
+        .map(|submission| {
+            let alignment = match alignments.get(&submission.id) {
+                Some(AlignmentState::Done(alignment)) => *alignment,
+                _ => None,
+            };
+            ManifestTrack {
+                submission_id: submission.id,
+                file_name: &submission.file_name,
+                creator_name: &submission.creator_name,
+                section: &submission.creator_section,
+                offset_ms: alignment.map(|alignment| alignment.offset_ms),
+                confidence: alignment.map(|alignment| alignment.confidence),
+                recorded_offset_ms: (submission.playback_material_id
+                    == Some(reference.material_id))
+                .then_some(submission.sync_offset_ms)
+                .flatten(),
+            }
+        })
+        .collect();
+
+    let manifest = Manifest {
+        project_id,
+        project_title,
+        reference_material_id: reference.material_id,
+        reference_title: &reference.title,
+        tracks,
+    };
+    serde_json::to_string_pretty(&manifest).expect("The manifest can always be serialised")
+}
+
+#[derive(PartialEq, Properties)]
+pub struct AlignmentToolbarProperties {
+    pub id: String,
+    pub references: Vec<Playback>,
+    pub selected: Option<i64>,
+    pub on_select: Callback<Option<i64>>,
+    pub on_align: Callback<()>,
+    pub on_export: Callback<()>,
+    /// Submissions aligned and to be aligned, while aligning.
+    pub progress: Option<(usize, usize)>,
+    /// Whether there are results to export.
+    pub aligned: bool,
+}
+
+/// Lets the conductor pick the reference, align all audio submissions to it and export the offsets.
+#[function_component(AlignmentToolbar)]
+pub fn alignment_toolbar(props: &AlignmentToolbarProperties) -> Html {
+    let select_id = format!("selectAlignmentReference{}", props.id);
+    let on_select = props.on_select.reform(|event: Event| {
+        let select: HtmlSelectElement = event.target_unchecked_into();
+        select.value().parse().ok()
+    });
+    let on_align = props.on_align.reform(|_| ());
+    let on_export = props.on_export.reform(|_| ());
+    let running = props.progress.is_some();
+
+    html! {
+        <div class="row g-2 mb-2 align-items-center">
+            <div class="col-auto">
+                <label for={ select_id.clone() } class="col-form-label col-form-label-sm">{ t("alignment-reference") }</label>
+            </div>
+            <div class="col-auto">
+                <select id={ select_id } class="form-select form-select-sm" disabled={ running } onchange={ on_select }>
+                    { for props.references.iter().map(|reference| html! {
+                        <option value={ reference.material_id.to_string() } selected={ props.selected == Some(reference.material_id) }>
+                            { &reference.title }
+                        </option>
+                    }) }
+                </select>
+            </div>
+            <div class="col-auto">
+                <button type="button" class="btn btn-sm btn-outline-primary" disabled={ running || props.selected.is_none() } onclick={ on_align }>
+                    {
+                        match props.progress {
+                            Some((done, total)) => t_with("alignment-running", &[("done", &done), ("total", &total)]),
+                            None => t("alignment-align"),
+                        }
+                    }
+                </button>
+            </div>
+            <div class="col-auto">
+                <button type="button" class="btn btn-sm btn-outline-secondary" disabled={ running || !props.aligned } onclick={ on_export }>
+                    { t("alignment-export") }
+                </button>
+            </div>
+        </div>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct AlignmentResultProperties {
+    pub state: AlignmentState,
+}
+
+/// The offset of a submission to the reference and how sure it is.
+#[function_component(AlignmentResult)]
+pub fn alignment_result(props: &AlignmentResultProperties) -> Html {
+    match &props.state {
+        AlignmentState::Running => {
+            html! { <span class="text-muted">{ t("alignment-aligning") }</span> }
+        }
+        AlignmentState::Failed => {
+            html! { <span class="text-danger">{ t("alignment-failed") }</span> }
+        }
+        AlignmentState::Done(None) => {
+            html! { <span class="text-warning">{ t("alignment-not-found") }</span> }
+        }
+        AlignmentState::Done(Some(alignment)) => html! {
+            <>
+            { t_with("alignment-offset-value", &[
+                ("offset", &alignment.offset_ms),
+                ("confidence", &format!("{:.0}", alignment.confidence * 100.0)),
+            ]) }
+            if !alignment.is_reliable() {
+                { " " }<span class="badge bg-warning text-dark" title={ t("alignment-uncertain-hint") }>{ t("alignment-uncertain") }</span>
+            }
+            </>
+        },
+    }
+}
+
+#[cfg(test)]
+mod alignment_test {
+    use std::collections::HashMap;
+
+    use serde_json::{json, Value};
+    use time::OffsetDateTime;
+
+    use crate::{
+        components::recorder::Playback,
+        service::submission::{Submission, SubmissionKind},
+        utilities::alignment::Alignment,
+    };
+
+    use super::{default_reference, manifest_json, AlignmentState};
+
+    fn playback(material_id: i64) -> Playback {
+        Playback {
+            material_id,
+            title: format!("Playback {material_id}"),
+            url: String::new(),
+        }
+    }
+
+    fn submission(id: i64, kind: SubmissionKind, playback_material_id: Option<i64>) -> Submission {
+        Submission {
+            id,
+            project_id: 1,
+            note: String::new(),
+            file_name: format!("take{id}.webm"),
+            file_technical_name: format!("take{id}"),
+            submitter: 1,
+            creator: 1,
+            creator_name: "Singer".to_string(),
+            creator_section: serde_json::from_str("\"Bass1\"").unwrap(),
+            upload_at: OffsetDateTime::UNIX_EPOCH,
+            kind,
+            purge_at: None,
+            playback_material_id,
+            sync_offset_ms: playback_material_id.map(|_| 120),
+        }
+    }
+
+    #[test]
+    fn most_used_playback_is_the_default() {
+        let references = [playback(1), playback(2), playback(3)];
+        let submissions = [
+            submission(1, SubmissionKind::Audio, Some(3)),
+            submission(2, SubmissionKind::Audio, None),
+            submission(3, SubmissionKind::Audio, Some(3)),
+        ];
+
+        assert_eq!(default_reference(&submissions, &references), Some(3));
+    }
+
+    #[test]
+    fn first_playback_is_the_default_without_recordings() {
+        let references = [playback(1), playback(2)];
+        let submissions = [submission(1, SubmissionKind::Audio, None)];
+
+        assert_eq!(default_reference(&submissions, &references), Some(1));
+        assert_eq!(default_reference(&submissions, &[]), None);
+    }
+
+    #[test]
+    fn manifest_contains_audio_tracks_with_offsets() {
+        let submissions = [
+            submission(1, SubmissionKind::Audio, Some(2)),
+            submission(2, SubmissionKind::Video, None),
+            submission(3, SubmissionKind::Audio, Some(5)),
+        ];
+        let alignments = HashMap::from([(
+            1,
+            AlignmentState::Done(Some(Alignment {
+                offset_ms: 150,
+                confidence: 0.5,
+            })),
+        )]);
+
+        let manifest: Value = serde_json::from_str(&manifest_json(
+            7,
+            "Messiah",
+            &playback(2),
+            &submissions,
+            &alignments,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            manifest,
+            json!({
+                "project_id": 7,
+                "project_title": "Messiah",
+                "reference_material_id": 2,
+                "reference_title": "Playback 2",
+                "tracks": [
+                    {
+                        "submission_id": 1,
+                        "file_name": "take1.webm",
+                        "creator_name": "Singer",
+                        "section": "Bass1",
+                        "offset_ms": 150,
+                        "confidence": 0.5,
+                        "recorded_offset_ms": 120,
+                    },
+                    {
+                        "submission_id": 3,
+                        "file_name": "take3.webm",
+                        "creator_name": "Singer",
+                        "section": "Bass1",
+                        "offset_ms": null,
+                        "confidence": null,
+                        // Recorded along to another playback.
+                        "recorded_offset_ms": null,
+                    },
+                ],
+            })
+        );
+    }
+}
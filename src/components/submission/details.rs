@@ -1,18 +1,34 @@
 use yew::{function_component, html, Html, Properties};
 
 use crate::{
+    components::{
+        submission::{
+            alignment::{AlignmentResult, AlignmentState},
+            pitch::PitchAnalysis,
+            quality::QualityReport,
+        },
+        waveform::Waveform,
+    },
+    i18n::{t, t_with},
     service::submission::{submission_stream_url, Submission},
-    utilities::date::format_datetime_human_readable_seconds,
+    utilities::{date::format_datetime_human_readable_seconds, quality::AudioQuality},
 };
 
-#[derive(Eq, PartialEq, Properties)]
+#[derive(PartialEq, Properties)]
 pub struct SubmissionProperties {
     pub submission: Submission,
+    /// Result of the quality check, if the submission was checked.
+    #[prop_or_default]
+    pub quality: Option<AudioQuality>,
+    /// Result of the alignment to the reference playback, if the submission was aligned.
+    #[prop_or_default]
+    pub alignment: Option<AlignmentState>,
 }
 
 #[function_component(SubmissionDetails)]
 pub fn submission_details(s: &SubmissionProperties) -> Html {
     let submission = &s.submission;
+    let audio_id = format!("submission-audio-{}", submission.id);
     html!(<>
 
         <table class="table">
@@ -22,19 +38,19 @@ pub fn submission_details(s: &SubmissionProperties) -> Html {
             </caption>
             <tr>
                 <td>
-                    <b>{ "Dateiname:" }</b>
+                    <b>{ t("submission-details-file-name") }</b>
                 </td>
                 <td>
                     <i>{ &submission.file_name } </i>
                 </td>
                 <td>
-                    <b>{ "Id: " }</b>
+                    <b>{ t("submission-details-id") }</b>
                 </td>
                 <td>
                     { submission.id }
                 </td>
                 <td>
-                    <b>{ "Hochgeladen: " }</b>
+                    <b>{ t("submission-details-uploaded") }</b>
                 </td>
                 <td>
                     { format_datetime_human_readable_seconds(&submission.upload_at) }
@@ -42,24 +58,50 @@ pub fn submission_details(s: &SubmissionProperties) -> Html {
             </tr>
             <tr>
                 <td>
-                    <b>{ "Autor (Id): " }</b>
+                    <b>{ t("submission-details-author-id") }</b>
                 </td>
                 <td>
                     { submission.creator }
                 </td>
                 <td>
-                    <b>{ "Autor (Name): " }</b>
+                    <b>{ t("submission-details-author-name") }</b>
                 </td>
                 <td>
                     { &submission.creator_name }
                 </td>
                 <td>
-                    <b>{ "Eingereicht von: " }</b>
+                    <b>{ t("submission-details-submitter") }</b>
                 </td>
                 <td>
                     { submission.submitter }
                 </td>
             </tr>
+            if let Some(offset_ms) = submission.sync_offset_ms {
+                <tr>
+                    <td>
+                        <b>{ t("submission-details-playback") }</b>
+                    </td>
+                    <td>
+                        { submission.playback_material_id.map(|id| id.to_string()).unwrap_or_default() }
+                    </td>
+                    <td>
+                        <b>{ t("submission-details-sync-offset") }</b>
+                    </td>
+                    <td colspan="3">
+                        { t_with("submission-details-sync-offset-value", &[("offset", &offset_ms.to_string())]) }
+                    </td>
+                </tr>
+            }
+            if let Some(alignment) = &s.alignment {
+                <tr>
+                    <td>
+                        <b>{ t("submission-details-alignment") }</b>
+                    </td>
+                    <td colspan="5">
+                        <AlignmentResult state={ alignment.clone() } />
+                    </td>
+                </tr>
+            }
         </tbody>
         </table>
         <div class="row mt-2">
@@ -68,13 +110,28 @@ pub fn submission_details(s: &SubmissionProperties) -> Html {
                     match submission.kind {
                         crate::service::submission::SubmissionKind::Audio => html!{
                             <>
-                            <h5> { "Vorschau: " }</h5>
-                            <audio controls=true src={ submission_stream_url(submission.project_id, &submission.file_technical_name) }></audio>
+                            <h5> { t("submission-details-preview") }</h5>
+                            <audio
+                                controls=true
+                                id={ audio_id.clone() }
+                                src={ submission_stream_url(submission.project_id, &submission.file_technical_name) }
+                            ></audio>
+                            // Keyed, so the row of another submission loads its own waveform and pitch.
+                            <Waveform
+                                key={ submission.id }
+                                url={ submission_stream_url(submission.project_id, &submission.file_technical_name) }
+                                element_id={ audio_id.clone() }
+                            />
+                            if let Some(quality) = &s.quality {
+                                <h5>{ t("submission-details-quality") }</h5>
+                                <QualityReport quality={ quality.clone() } />
+                            }
+                            <PitchAnalysis key={ submission.id } submission={ submission.clone() } />
                             </>
                         },
                         crate::service::submission::SubmissionKind::Video => html!{
                             <>
-                            <h5> { "Vorschau: " }</h5>
+                            <h5> { t("submission-details-preview") }</h5>
                             <div class="ratio ratio-16x9">
                                 <video controls=true>
                                     <source src={ submission_stream_url(submission.project_id, &submission.file_technical_name) }/>
@@ -84,7 +141,7 @@ pub fn submission_details(s: &SubmissionProperties) -> Html {
                         },
                         crate::service::submission::SubmissionKind::Document => html!{
                             <>
-                            <h5> { "Vorschau: " }</h5>
+                            <h5> { t("submission-details-preview") }</h5>
                             <div class="ratio ratio-16x9">
                                 <iframe src={ submission_stream_url(submission.project_id, &submission.file_technical_name) }>
                                 </iframe>
@@ -92,7 +149,7 @@ pub fn submission_details(s: &SubmissionProperties) -> Html {
                             </>
                         },
                         crate::service::submission::SubmissionKind::Other => html!{
-                            <p> { "Für \"Sonstiges\" kann keine Vorschau erstellt werden. Passe gegebenfalls die Art der Abgabe über die Schaltfläche \"Ändern\" an!" } </p>
+                            <p> { t("submission-details-no-preview") } </p>
                         },
                     }
                 }
@@ -1,3 +1,8 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+};
+
 use gloo_dialogs::alert;
 use web_sys::MouseEvent;
 use yew::{html, Callback, Component, Properties};
@@ -7,21 +12,78 @@ use gloo_console::error;
 use crate::{
     components::{
         delete_modal::DeleteModal,
+        enum_select::EnumSelect,
+        recorder::Playback,
         submission::{
+            alignment::{
+                align_url, default_reference, manifest_json, reference_envelope, AlignmentState,
+                AlignmentToolbar,
+            },
             details::SubmissionDetails,
+            mix::PreviewMix,
+            quality::{check_url, QualityBadge},
             update::{SubmissionUpdate, SubmissionUpdateData},
         },
+        view_as::is_viewing_as,
     },
-    service::submission::{
-        delete_submission, submission_download_url, update_submission, Submission, UpdateSubmission,
+    i18n::{t, t_with},
+    service::{
+        project::ProjectTo,
+        submission::{
+            delete_submission, submission_download_url, submission_stream_url, update_submission,
+            Submission, SubmissionKind, UpdateSubmission,
+        },
+    },
+    utilities::{
+        alignment::Alignment, download_file, quality::AudioQuality, requests::fetch::FetchError,
+        select_enum::SelectEnum,
     },
-    utilities::requests::fetch::FetchError,
 };
 
+/// Which submissions are listed, by whether they were uploaded after the deadline.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, SelectEnum)]
+pub enum LateFilter {
+    #[select(value = "all", label = "submission-list-filter-all")]
+    All,
+    #[select(value = "on_time", label = "submission-list-filter-on-time")]
+    OnTime,
+    #[select(value = "late", label = "submission-list-filter-late")]
+    Late,
+}
+
+impl LateFilter {
+    fn matches(self, late: bool) -> bool {
+        match self {
+            LateFilter::All => true,
+            LateFilter::OnTime => !late,
+            LateFilter::Late => late,
+        }
+    }
+}
+
+/// Quality check of an uploaded audio submission, started from the list.
+enum QualityState {
+    Running,
+    Done(AudioQuality),
+}
+
 pub struct SubmissionList {
     selected_submission: Option<i64>,
     selected_delete: Option<Submission>,
     selected_update: Option<Submission>,
+    late_filter: LateFilter,
+    /// By submission id.
+    quality: HashMap<i64, QualityState>,
+    /// Playback the submissions are aligned to, [None] until chosen or preselected.
+    alignment_reference: Option<i64>,
+    /// Envelope of the reference by its material id, loaded once for all submissions.
+    reference_envelope: Option<(i64, Rc<Vec<f32>>)>,
+    /// By submission id. Cleared when another reference is chosen.
+    alignment: HashMap<i64, AlignmentState>,
+    /// Submissions still to be aligned. They are aligned one after another, as every one is decoded completely.
+    alignment_queue: VecDeque<i64>,
+    /// Results of a previous reference are dropped.
+    alignment_generation: u32,
 }
 
 #[derive(PartialEq, Properties)]
@@ -30,19 +92,28 @@ pub struct SubmissionListProperties {
     pub submission_delete: Callback<i64>,
     pub submission_update: Callback<Submission>,
     pub id: String,
+    /// Project of the submissions. If set, late submissions are marked and can be filtered.
+    #[prop_or_default]
+    pub project: Option<ProjectTo>,
+    /// Playbacks the audio submissions can be aligned to. Aligning is only offered if there are any.
+    #[prop_or_default]
+    pub references: Vec<Playback>,
+    /// Whether the audio submissions can be heard together, for the conductor.
+    #[prop_or_default]
+    pub preview_mix: bool,
 }
 
 pub enum UpdateMessage {
-    Init(Submission),
+    Init(Box<Submission>),
     #[allow(dead_code)] // Field is required to be a valid event target
     Abort(MouseEvent),
     Submit(SubmissionUpdateData),
-    Success(Submission),
+    Success(Box<Submission>),
     Error(FetchError),
 }
 
 pub enum DeleteMessage {
-    ListItemButtonClick(Submission),
+    ListItemButtonClick(Box<Submission>),
     #[allow(dead_code)] // Field is required to be a valid event target
     AcceptClick(MouseEvent),
     #[allow(dead_code)] // Field is required to be a valid event target
@@ -55,6 +126,14 @@ pub enum Msg {
     SelectOrUnselect(i64),
     Delete(DeleteMessage),
     Update(UpdateMessage),
+    Filter(Result<LateFilter, ()>),
+    CheckQuality(i64),
+    QualityChecked(i64, Result<AudioQuality, String>),
+    SelectReference(Option<i64>),
+    Align,
+    ReferenceLoaded(u32, Result<Rc<Vec<f32>>, String>),
+    Aligned(u32, i64, Result<Option<Alignment>, String>),
+    ExportManifest,
 }
 
 impl Component for SubmissionList {
@@ -66,63 +145,123 @@ impl Component for SubmissionList {
             selected_submission: None,
             selected_delete: None,
             selected_update: None,
+            late_filter: LateFilter::All,
+            quality: HashMap::new(),
+            alignment_reference: None,
+            reference_envelope: None,
+            alignment: HashMap::new(),
+            alignment_queue: VecDeque::new(),
+            alignment_generation: 0,
         }
     }
 
     fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let read_only = is_viewing_as();
+        let project = ctx.props().project.as_ref();
+        let is_late = |submission: &Submission| project.is_some_and(|p| p.is_late(submission));
+
+        let submissions = ctx
+            .props()
+            .submissions
+            .iter()
+            .filter(|submission| self.late_filter.matches(is_late(submission)))
+            .collect::<Vec<_>>();
+
         html! {
             <>
+            if project.is_some() {
+                <div class="row mb-2 justify-content-end">
+                    <div class="col-auto">
+                        <label for={ format!("selectLateFilter{}", ctx.props().id) } class="visually-hidden">{ t("submission-list-filter") }</label>
+                        <EnumSelect<LateFilter>
+                            id={ format!("selectLateFilter{}", ctx.props().id) }
+                            name="late_filter"
+                            selected={ self.late_filter }
+                            on_input={ ctx.link().callback(Msg::Filter) }
+                        />
+                    </div>
+                </div>
+            }
+            if !ctx.props().references.is_empty() {
+                <AlignmentToolbar
+                    id={ ctx.props().id.clone() }
+                    references={ ctx.props().references.clone() }
+                    selected={ self.reference(ctx) }
+                    on_select={ ctx.link().callback(Msg::SelectReference) }
+                    on_align={ ctx.link().callback(|_| Msg::Align) }
+                    on_export={ ctx.link().callback(|_| Msg::ExportManifest) }
+                    progress={ self.alignment_progress() }
+                    aligned={ self.alignment.values().any(|state| matches!(state, AlignmentState::Done(_))) }
+                />
+            }
+            if ctx.props().preview_mix && ctx.props().submissions.iter().any(|submission| submission.kind == SubmissionKind::Audio) {
+                if let Some(project) = project {
+                    <PreviewMix
+                        project_id={ project.id }
+                        submissions={ ctx.props().submissions.clone() }
+                        alignment={ self.alignment.clone() }
+                        reference={ self.reference(ctx) }
+                    />
+                }
+            }
             <div class="table-responsive">
                 <table class="table table-striped">
                     <thead>
                         <tr>
                             <th>
-                                { "Name" }
+                                { t("submission-list-name") }
+                            </th>
+                            <th>
+                                { t("submission-kind") }
                             </th>
                             <th>
-                                { "Art" }
+                                { t("submission-list-comment") }
                             </th>
                             <th>
-                                { "Kommentar" }
+                                { t("submission-section") }
                             </th>
                             <th>
-                                { "Stimme" }
+                                { t("submission-list-author") }
                             </th>
                             <th>
-                                { "Autor*in" }
+                                { t("submission-list-quality") }
                             </th>
                             <th>
-                                { "Details" }
+                                { t("submission-list-details") }
                             </th>
                             <th>
-                                { "Herunterladen" }
+                                { t("submission-list-download") }
                             </th>
                             <th>
-                                { "Ändern" }
+                                { t("submission-list-change") }
                             </th>
                             <th>
-                                { "Löschen" }
+                                { t("submission-list-delete") }
                             </th>
                         </tr>
                     </thead>
                     <tbody>
                     {
-                        if ctx.props().submissions.is_empty() {
+                        if submissions.is_empty() {
                             html!{
-                                <td>{ "Keine Abgaben gefunden" }</td>
+                                <td>{ t("submission-list-empty") }</td>
                             }
                         } else {
                             html! {
                                 <>
                                 {
-                                    for ctx.props().submissions.iter().enumerate().map(|(index, submission)| {
-                                        let submission_clone = submission.clone();
-                                        let submission_clone_2 = submission.clone();
+                                    for submissions.iter().enumerate().map(|(index, submission)| {
+                                        let submission_clone = (*submission).clone();
+                                        let submission_clone_2 = (*submission).clone();
                                         html! {
                                             <>
                                             <tr>
                                                 <td>
                                                     { &submission.file_name }
+                                                    if is_late(submission) {
+                                                        { " " }
+                                                        <span class="badge bg-warning text-dark">{ t("submission-list-late") }</span>
+                                                    }
                                                 </td>
                                                 <td>
                                                     { &submission.kind }
@@ -131,37 +270,61 @@ impl Component for SubmissionList {
                                                     { &submission.note }
                                                 </td>
                                                 <td>
-                                                    { submission.creator_section }
+                                                    { &submission.creator_section }
                                                 </td>
                                                 <td>
                                                     { &submission.creator_name }
                                                 </td>
                                                 <td>
-                                                    <button class="btn btn-sm btn-outline-danger" onclick={ ctx.link().callback(move |_| Msg::SelectOrUnselect(index as i64)) }>{"Details"}</button>
+                                                    if submission.kind == SubmissionKind::Audio {
+                                                        {
+                                                            match self.quality.get(&submission.id) {
+                                                                Some(QualityState::Done(quality)) => html! {
+                                                                    <QualityBadge quality={ quality.clone() } />
+                                                                },
+                                                                Some(QualityState::Running) => html! {
+                                                                    <span class="text-muted small">{ t("quality-running") }</span>
+                                                                },
+                                                                None => {
+                                                                    let id = submission.id;
+                                                                    html! {
+                                                                        <button class="btn btn-sm btn-outline-secondary" onclick={ ctx.link().callback(move |_| Msg::CheckQuality(id)) }>
+                                                                            { t("quality-check") }
+                                                                        </button>
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                </td>
+                                                <td>
+                                                    <button class="btn btn-sm btn-outline-danger" onclick={ ctx.link().callback(move |_| Msg::SelectOrUnselect(index as i64)) }>{ t("submission-list-details") }</button>
                                                 </td>
                                                 <td>
                                                     <a href={ submission_download_url(submission.id) } download="true" target="_blank">
                                                         <button class="btn btn-sm btn-outline-danger">
-                                                            {"Herunterladen"}
+                                                            { t("submission-list-download") }
                                                         </button>
                                                     </a>
                                                 </td>
                                                 <td>
                                                     <button
                                                         class="btn btn-sm btn-outline-danger"
-                                                        onclick={ ctx.link().callback(move |_| Msg::Update(UpdateMessage::Init(submission_clone.clone()))) }
+                                                        disabled={ read_only }
+                                                        onclick={ ctx.link().callback(move |_| Msg::Update(UpdateMessage::Init(Box::new(submission_clone.clone())))) }
                                                         data-bs-toggle="modal"
                                                         data-bs-target={ format!("#{}", update_modal_id(&ctx.props().id)) }>
-                                                            { "Ändern" }
+                                                            { t("submission-list-change") }
                                                     </button>
                                                 </td>
                                                 <td>
                                                     <button
                                                         class="btn btn-sm btn-danger"
-                                                        onclick={ ctx.link().callback(move |_| Msg::Delete(DeleteMessage::ListItemButtonClick(submission_clone_2.clone()))) }
+                                                        disabled={ read_only }
+                                                        onclick={ ctx.link().callback(move |_| Msg::Delete(DeleteMessage::ListItemButtonClick(Box::new(submission_clone_2.clone())))) }
                                                         data-bs-toggle="modal"
                                                         data-bs-target={ format!("#{}", delete_modal_id(&ctx.props().id)) }>
-                                                            { "Löschen" }
+                                                            { t("submission-list-delete") }
                                                     </button>
                                                 </td>
                                             </tr>
@@ -170,7 +333,16 @@ impl Component for SubmissionList {
                                                 if index as i64 == selected_index {
                                                     <tr>
                                                         <td colspan="10">
-                                                            <SubmissionDetails submission={ submission.clone() } />
+                                                            <SubmissionDetails
+                                                                submission={ (*submission).clone() }
+                                                                quality={
+                                                                    match self.quality.get(&submission.id) {
+                                                                        Some(QualityState::Done(quality)) => Some(quality.clone()),
+                                                                        _ => None,
+                                                                    }
+                                                                }
+                                                                alignment={ self.alignment.get(&submission.id).cloned() }
+                                                            />
                                                         </td>
                                                     </tr>
                                                 }
@@ -194,28 +366,22 @@ impl Component for SubmissionList {
             />
 
             <DeleteModal
-                    title={"Projekt löschen".to_string() }
+                    title={ t("submission-list-delete-title") }
                     id={ delete_modal_id(&ctx.props().id) }
                     on_cancel={ ctx.link().callback(|x| Msg::Delete(DeleteMessage::AbortClick(x))) }
                     on_confirm={ ctx.link().callback(|x| Msg::Delete(DeleteMessage::AcceptClick(x))) }
             >
                 <>
                     <p>
-                        { "Warnung! Kann " }
-                            <b>
-                            { "nicht rückgängig " }
-                            </b>
-                        { "gemacht werden!" }
+                        <b>{ t("submission-list-delete-irreversible") }</b>
                     </p>
                     <p>
-                        { "Die Abgabe " }
                         if let Some(submission) = &self.selected_delete {
-                            <i> { &submission.file_name } </i>
+                            { t_with("submission-list-delete-confirm", &[("file", &submission.file_name)]) }
                         }
                         else {
-                            <h2> { "Fehler! Keine Abgabe ausgewählt!" } </h2>
+                            <h2> { t("submission-list-none-selected") } </h2>
                         }
-                        { " wirklich löschen?" }
                     </p>
                 </>
             </DeleteModal>
@@ -238,7 +404,7 @@ impl Component for SubmissionList {
             }
             Msg::Delete(delete_message) => match delete_message {
                 DeleteMessage::ListItemButtonClick(submission) => {
-                    self.selected_delete = Some(submission);
+                    self.selected_delete = Some(*submission);
                     true
                 }
                 DeleteMessage::AcceptClick(_) => {
@@ -273,14 +439,14 @@ impl Component for SubmissionList {
                         "Fetch error while deleting submission: {:?}",
                         error
                     ));
-                    alert("Fehler beim Löschen der Abgabe.");
+                    alert(&t("submission-list-delete-failed"));
                     self.selected_submission = None;
                     true
                 }
             },
             Msg::Update(message) => match message {
                 UpdateMessage::Init(submission) => {
-                    self.selected_update = Some(submission);
+                    self.selected_update = Some(*submission);
                     true
                 }
                 UpdateMessage::Abort(_) => {
@@ -300,7 +466,9 @@ impl Component for SubmissionList {
                         )
                         .await
                         {
-                            Ok(submission) => Msg::Update(UpdateMessage::Success(submission)),
+                            Ok(submission) => {
+                                Msg::Update(UpdateMessage::Success(Box::new(submission)))
+                            }
                             Err(error) => Msg::Update(UpdateMessage::Error(error)),
                         }
                     });
@@ -310,15 +478,195 @@ impl Component for SubmissionList {
                     true
                 }
                 UpdateMessage::Success(submission) => {
-                    ctx.props().submission_update.emit(submission);
+                    ctx.props().submission_update.emit(*submission);
                     false
                 }
                 UpdateMessage::Error(error) => {
-                    alert("Daten der Abgabe konnten nicht geändert werden. Details siehe Konsole.");
+                    alert(&t("submission-list-update-failed"));
                     error!(format!("{}", error));
                     false
                 }
             },
+            Msg::Filter(filter) => match filter {
+                Ok(filter) => {
+                    self.late_filter = filter;
+                    self.selected_submission = None;
+                    true
+                }
+                Err(_) => {
+                    error!("Selected filter could not be parsed!");
+                    false
+                }
+            },
+            Msg::CheckQuality(id) => {
+                let Some(submission) = ctx.props().submissions.iter().find(|s| s.id == id) else {
+                    return false;
+                };
+                let url =
+                    submission_stream_url(submission.project_id, &submission.file_technical_name);
+                ctx.link()
+                    .send_future(async move { Msg::QualityChecked(id, check_url(&url).await) });
+                self.quality.insert(id, QualityState::Running);
+                true
+            }
+            Msg::QualityChecked(id, result) => {
+                match result {
+                    Ok(quality) => {
+                        self.quality.insert(id, QualityState::Done(quality));
+                    }
+                    Err(message) => {
+                        error!(format!("Could not check submission {id}: {message}"));
+                        alert(&t("quality-check-failed"));
+                        self.quality.remove(&id);
+                    }
+                }
+                true
+            }
+            Msg::SelectReference(reference) => {
+                self.alignment_reference = reference;
+                self.alignment.clear();
+                self.alignment_queue.clear();
+                self.alignment_generation += 1;
+                true
+            }
+            Msg::Align => {
+                let Some(reference) = self.reference(ctx) else {
+                    return false;
+                };
+                self.alignment_generation += 1;
+                self.alignment_queue = ctx
+                    .props()
+                    .submissions
+                    .iter()
+                    .filter(|submission| submission.kind == SubmissionKind::Audio)
+                    .map(|submission| submission.id)
+                    .collect();
+                self.alignment = self
+                    .alignment_queue
+                    .iter()
+                    .map(|&id| (id, AlignmentState::Running))
+                    .collect();
+
+                if self.alignment_queue.is_empty() {
+                    return true;
+                }
+                if self
+                    .reference_envelope
+                    .as_ref()
+                    .is_some_and(|(id, _)| *id == reference)
+                {
+                    self.align_next(ctx);
+                } else if let Some(playback) = ctx
+                    .props()
+                    .references
+                    .iter()
+                    .find(|playback| playback.material_id == reference)
+                {
+                    let generation = self.alignment_generation;
+                    let url = playback.url.clone();
+                    ctx.link().send_future(async move {
+                        Msg::ReferenceLoaded(generation, reference_envelope(&url).await)
+                    });
+                }
+                true
+            }
+            Msg::ReferenceLoaded(generation, result) => {
+                if generation != self.alignment_generation {
+                    return false;
+                }
+                match (result, self.reference(ctx)) {
+                    (Ok(envelope), Some(reference)) => {
+                        self.reference_envelope = Some((reference, envelope));
+                        self.align_next(ctx);
+                    }
+                    (Ok(_), None) => {}
+                    (Err(message), _) => {
+                        error!(format!("Could not load the reference: {message}"));
+                        alert(&t("alignment-reference-failed"));
+                        self.alignment.clear();
+                        self.alignment_queue.clear();
+                    }
+                }
+                true
+            }
+            Msg::Aligned(generation, id, result) => {
+                if generation != self.alignment_generation {
+                    return false;
+                }
+                let state = match result {
+                    Ok(alignment) => AlignmentState::Done(alignment),
+                    Err(message) => {
+                        error!(format!("Could not align submission {id}: {message}"));
+                        AlignmentState::Failed
+                    }
+                };
+                self.alignment.insert(id, state);
+                self.align_next(ctx);
+                true
+            }
+            Msg::ExportManifest => {
+                let (Some(project), Some(reference)) = (
+                    ctx.props().project.as_ref(),
+                    self.reference(ctx).and_then(|id| {
+                        ctx.props()
+                            .references
+                            .iter()
+                            .find(|playback| playback.material_id == id)
+                    }),
+                ) else {
+                    return false;
+                };
+                download_file(
+                    &format!("project-{}-alignment.json", project.id),
+                    "application/json",
+                    &manifest_json(
+                        project.id,
+                        &project.title,
+                        reference,
+                        &ctx.props().submissions,
+                        &self.alignment,
+                    ),
+                );
+                false
+            }
+        }
+    }
+}
+
+impl SubmissionList {
+    /// The chosen reference, or the preselected one if none was chosen yet.
+    fn reference(&self, ctx: &yew::Context<Self>) -> Option<i64> {
+        self.alignment_reference
+            .or_else(|| default_reference(&ctx.props().submissions, &ctx.props().references))
+    }
+
+    /// Aligned and all submissions while aligning.
+    fn alignment_progress(&self) -> Option<(usize, usize)> {
+        let running = self
+            .alignment
+            .values()
+            .filter(|state| **state == AlignmentState::Running)
+            .count();
+        (running > 0).then(|| (self.alignment.len() - running, self.alignment.len()))
+    }
+
+    fn align_next(&mut self, ctx: &yew::Context<Self>) {
+        let Some((_, envelope)) = &self.reference_envelope else {
+            return;
+        };
+        while let Some(id) = self.alignment_queue.pop_front() {
+            // Submissions deleted in the meantime are skipped.
+            let Some(submission) = ctx.props().submissions.iter().find(|s| s.id == id) else {
+                self.alignment.remove(&id);
+                continue;
+            };
+            let url = submission_stream_url(submission.project_id, &submission.file_technical_name);
+            let envelope = envelope.clone();
+            let generation = self.alignment_generation;
+            ctx.link().send_future(async move {
+                Msg::Aligned(generation, id, align_url(&url, envelope).await)
+            });
+            return;
         }
     }
 }
@@ -0,0 +1,495 @@
+use std::collections::HashMap;
+
+use gloo_console::error;
+use gloo_dialogs::alert;
+use gloo_storage::{LocalStorage, Storage};
+use js_sys::{Array, Float32Array, Reflect};
+use wasm_bindgen::JsValue;
+use web_sys::{HtmlInputElement, InputEvent};
+use yew::{html, Component, Context, Html, Properties, TargetCast};
+
+use crate::{
+    components::{
+        material::member_storage_key,
+        mixer_transport::{MixerTransport, TransportMsg},
+        submission::alignment::AlignmentState,
+    },
+    i18n::{t, t_with},
+    service::{
+        section::Section,
+        submission::{submission_stream_url, Submission, SubmissionKind},
+    },
+    utilities::{
+        download_bytes,
+        mix::{encode_wav, sections_of, track_delays, ChannelSetting, MixSettings},
+    },
+};
+
+/// Name of the mixer in `mixer.js`, the material is mixed separately.
+const MIXER_NAME: &str = "submissions";
+
+/// A change to the setting of a section or a track.
+pub enum Change {
+    Gain(f64),
+    Pan(f64),
+    Mute,
+    Solo,
+}
+
+pub enum Msg {
+    Transport(TransportMsg),
+    Loaded(Result<f64, String>),
+    Section(Section, Change),
+    Track(i64, Change),
+    Reset,
+    Export,
+    Exported(Result<(), String>),
+}
+
+#[derive(PartialEq, Properties)]
+pub struct PreviewMixProperties {
+    pub project_id: i64,
+    /// Only the audio submissions are mixed.
+    pub submissions: Vec<Submission>,
+    /// Alignments to the reference by submission id, they line up the tracks.
+    #[prop_or_default]
+    pub alignment: HashMap<i64, AlignmentState>,
+    /// Playback the submissions are aligned to. Offsets measured while recording along to another one are not used.
+    #[prop_or_default]
+    pub reference: Option<i64>,
+}
+
+/// Plays all audio submissions of a project together, grouped by section, so the conductor hears the whole ensemble
+/// before sending the files to an editor. The mix is remembered per member and project in the browser.
+pub struct PreviewMix {
+    /// Nothing is downloaded until the mix is played or exported for the first time.
+    transport: MixerTransport,
+    settings: MixSettings,
+    /// Whether the mix is exported as soon as it is loaded, or is being exported.
+    exporting: bool,
+}
+
+impl Component for PreviewMix {
+    type Message = Msg;
+    type Properties = PreviewMixProperties;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            transport: MixerTransport::new(MIXER_NAME),
+            settings: LocalStorage::get(member_storage_key("preview-mix", ctx.props().project_id))
+                .unwrap_or_default(),
+            exporting: false,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if tracks(&ctx.props().submissions) != tracks(&old_props.submissions) {
+            // The tracks have to be loaded again with the new submissions.
+            self.close();
+        } else if ctx.props().alignment != old_props.alignment
+            || ctx.props().reference != old_props.reference
+        {
+            self.apply_delays(ctx);
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Transport(TransportMsg::Play) if self.transport.is_unloaded() => {
+                self.load(ctx);
+                true
+            }
+            Msg::Transport(msg) => self.transport.update(ctx.link(), msg, Msg::Transport),
+            Msg::Loaded(Ok(duration)) => {
+                self.transport.loaded(duration);
+                self.apply_delays(ctx);
+                self.apply_mix(ctx);
+                if self.exporting {
+                    self.render(ctx);
+                } else {
+                    ctx.link().send_message(Msg::Transport(TransportMsg::Play));
+                }
+                true
+            }
+            Msg::Loaded(Err(message)) => {
+                error!(format!(
+                    "Could not load the submissions of the mix: {message}"
+                ));
+                alert(&t("preview-mix-load-failed"));
+                self.transport.load_failed();
+                self.exporting = false;
+                true
+            }
+            Msg::Section(section, change) => {
+                let tracks = tracks(&ctx.props().submissions);
+                let sections = sections_of(&tracks);
+                let index = sections.iter().position(|s| **s == section).unwrap_or(0);
+                let current = self.settings.section(&section, index, sections.len());
+                self.settings
+                    .sections
+                    .insert(section, changed_setting(current, change));
+                self.mix_changed(ctx);
+                true
+            }
+            Msg::Track(id, change) => {
+                let current = self.settings.track(id);
+                self.settings
+                    .tracks
+                    .insert(id, changed_setting(current, change));
+                self.mix_changed(ctx);
+                true
+            }
+            Msg::Reset => {
+                self.settings = MixSettings::default();
+                self.mix_changed(ctx);
+                true
+            }
+            Msg::Export => {
+                self.exporting = true;
+                if self.transport.is_unloaded() {
+                    self.load(ctx);
+                } else if self.transport.is_ready() {
+                    self.render(ctx);
+                }
+                true
+            }
+            Msg::Exported(result) => {
+                self.exporting = false;
+                if let Err(message) = result {
+                    error!(format!("Could not export the mix: {message}"));
+                    alert(&t("preview-mix-export-failed"));
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let tracks = tracks(&ctx.props().submissions);
+        let sections = sections_of(&tracks);
+        let submissions = audio_submissions(&ctx.props().submissions);
+        let offsets = offsets(&submissions, &ctx.props().alignment, ctx.props().reference);
+        let export = html! {
+            <button type="button" class="btn btn-outline-secondary text-nowrap ms-2" disabled={ self.exporting } onclick={ ctx.link().callback(|_| Msg::Export) }>
+                { if self.exporting { t("preview-mix-exporting") } else { t("preview-mix-export") } }
+            </button>
+        };
+
+        html! {
+            <div class="card mb-2">
+                <div class="card-body">
+                    <h5 class="card-title">{ t("preview-mix-title") }</h5>
+                    <p class="text-muted small">{ t("preview-mix-hint") }</p>
+                    { self.transport.view(ctx.link(), Msg::Transport, export) }
+                    <table class="table table-sm align-middle mt-2 mb-0">
+                        <thead>
+                            <tr>
+                                <th></th>
+                                <th>{ t("preview-mix-gain") }</th>
+                                <th>{ t("preview-mix-pan") }</th>
+                                <th></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                        { for sections.iter().enumerate().map(|(index, section)| {
+                            let setting = self.settings.section(section, index, sections.len());
+                            html! {
+                                <>
+                                { self.section_row(ctx, section, setting) }
+                                { for submissions.iter().zip(&offsets).filter(|(submission, _)| submission.creator_section == **section).map(|(submission, offset)| {
+                                    self.track_row(ctx, submission, *offset)
+                                }) }
+                                </>
+                            }
+                        }) }
+                        </tbody>
+                    </table>
+                    <button type="button" class="btn btn-sm btn-link px-0" onclick={ ctx.link().callback(|_| Msg::Reset) }>
+                        { t("mixer-reset") }
+                    </button>
+                </div>
+            </div>
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.close();
+    }
+}
+
+impl PreviewMix {
+    fn section_row(&self, ctx: &Context<Self>, section: &Section, setting: ChannelSetting) -> Html {
+        let label = section.label();
+        let (gain, pan, mute, solo) = (
+            section.clone(),
+            section.clone(),
+            section.clone(),
+            section.clone(),
+        );
+
+        html! {
+            <tr class="table-light">
+                <th>{ &label }</th>
+                <td>{ gain_input(&label, setting.gain, ctx.link().callback(move |event| Msg::Section(gain.clone(), Change::Gain(input_value(event))))) }</td>
+                <td>{ pan_input(&label, setting.pan, ctx.link().callback(move |event| Msg::Section(pan.clone(), Change::Pan(input_value(event))))) }</td>
+                <td class="text-nowrap">
+                    <button
+                        type="button"
+                        class={ if setting.muted { "btn btn-sm btn-secondary me-1" } else { "btn btn-sm btn-outline-secondary me-1" } }
+                        onclick={ ctx.link().callback(move |_| Msg::Section(mute.clone(), Change::Mute)) }
+                    >
+                        { t("mixer-mute") }
+                    </button>
+                    <button
+                        type="button"
+                        class={ if setting.solo { "btn btn-sm btn-warning" } else { "btn btn-sm btn-outline-warning" } }
+                        onclick={ ctx.link().callback(move |_| Msg::Section(solo.clone(), Change::Solo)) }
+                    >
+                        { t("mixer-solo") }
+                    </button>
+                </td>
+            </tr>
+        }
+    }
+
+    fn track_row(&self, ctx: &Context<Self>, submission: &Submission, offset_ms: i64) -> Html {
+        let id = submission.id;
+        let setting = self.settings.track(id);
+        let label = &submission.creator_name;
+
+        html! {
+            <tr>
+                <td class="ps-3">
+                    { label }
+                    <span class="text-muted small" title={ submission.file_name.clone() }>
+                        { " " }{ t_with("preview-mix-offset", &[("offset", &offset_ms)]) }
+                    </span>
+                </td>
+                <td>{ gain_input(label, setting.gain, ctx.link().callback(move |event| Msg::Track(id, Change::Gain(input_value(event))))) }</td>
+                <td>{ pan_input(label, setting.pan, ctx.link().callback(move |event| Msg::Track(id, Change::Pan(input_value(event))))) }</td>
+                <td>
+                    <button
+                        type="button"
+                        class={ if setting.muted { "btn btn-sm btn-secondary" } else { "btn btn-sm btn-outline-secondary" } }
+                        onclick={ ctx.link().callback(move |_| Msg::Track(id, Change::Mute)) }
+                    >
+                        { t("mixer-mute") }
+                    </button>
+                </td>
+            </tr>
+        }
+    }
+
+    fn load(&mut self, ctx: &Context<Self>) {
+        let urls = audio_submissions(&ctx.props().submissions)
+            .iter()
+            .map(|submission| {
+                submission_stream_url(submission.project_id, &submission.file_technical_name)
+            })
+            .collect();
+        self.transport.load(ctx.link(), urls, Msg::Loaded);
+    }
+
+    fn render(&self, ctx: &Context<Self>) {
+        let (gains, pans): (Vec<_>, Vec<_>) = self
+            .settings
+            .effective(&tracks(&ctx.props().submissions))
+            .into_iter()
+            .unzip();
+        let file_name = format!("project-{}-preview-mix.wav", ctx.props().project_id);
+        let rendering = self.transport.render(gains, pans);
+        ctx.link().send_future(async move {
+            let result = match rendering.await {
+                Ok(rendered) => rendered_to_wav(&rendered).and_then(|wav| {
+                    download_bytes(&file_name, "audio/wav", &wav)
+                        .map_err(|error| format!("{error:?}"))
+                }),
+                Err(error) => Err(format!("{error:?}")),
+            };
+            Msg::Exported(result)
+        });
+    }
+
+    fn mix_changed(&mut self, ctx: &Context<Self>) {
+        self.apply_mix(ctx);
+        if let Err(error) = LocalStorage::set(
+            member_storage_key("preview-mix", ctx.props().project_id),
+            &self.settings,
+        ) {
+            error!(format!("Could not store the mix: {error}"));
+        }
+    }
+
+    fn apply_mix(&self, ctx: &Context<Self>) {
+        let (gains, pans): (Vec<_>, Vec<_>) = self
+            .settings
+            .effective(&tracks(&ctx.props().submissions))
+            .into_iter()
+            .unzip();
+        self.transport.set_gains(&gains);
+        self.transport.set_pans(&pans);
+    }
+
+    fn apply_delays(&mut self, ctx: &Context<Self>) {
+        let submissions = audio_submissions(&ctx.props().submissions);
+        self.transport.set_delays(&track_delays(&offsets(
+            &submissions,
+            &ctx.props().alignment,
+            ctx.props().reference,
+        )));
+    }
+
+    fn close(&mut self) {
+        self.exporting = false;
+        self.transport.close();
+    }
+}
+
+fn audio_submissions(submissions: &[Submission]) -> Vec<&Submission> {
+    submissions
+        .iter()
+        .filter(|submission| submission.kind == SubmissionKind::Audio)
+        .collect()
+}
+
+/// The tracks of the mix as submission id and section, in the order they are loaded.
+fn tracks(submissions: &[Submission]) -> Vec<(i64, Section)> {
+    audio_submissions(submissions)
+        .into_iter()
+        .map(|submission| (submission.id, submission.creator_section.clone()))
+        .collect()
+}
+
+/// Where the reference starts in each track: as aligned, else as measured while recording along to the reference itself.
+fn offsets(
+    submissions: &[&Submission],
+    alignment: &HashMap<i64, AlignmentState>,
+    reference: Option<i64>,
+) -> Vec<i64> {
+    submissions
+        .iter()
+        .map(|submission| match alignment.get(&submission.id) {
+            Some(AlignmentState::Done(Some(alignment))) => alignment.offset_ms,
+            _ if reference.is_some() && submission.playback_material_id == reference => {
+                submission.sync_offset_ms.unwrap_or_default()
+            }
+            _ => 0,
+        })
+        .collect()
+}
+
+fn changed_setting(mut setting: ChannelSetting, change: Change) -> ChannelSetting {
+    match change {
+        Change::Gain(gain) => setting.gain = gain.clamp(0.0, 2.0),
+        Change::Pan(pan) => setting.pan = pan.clamp(-1.0, 1.0),
+        Change::Mute => setting.muted = !setting.muted,
+        Change::Solo => setting.solo = !setting.solo,
+    }
+    setting
+}
+
+fn input_value(event: InputEvent) -> f64 {
+    let input: HtmlInputElement = event.target_unchecked_into();
+    input.value_as_number()
+}
+
+fn gain_input(label: &str, gain: f64, on_input: yew::Callback<InputEvent>) -> Html {
+    html! {
+        <input
+            type="range"
+            class="form-range"
+            min="0"
+            max="2"
+            step="0.05"
+            value={ gain.to_string() }
+            title={ t_with("preview-mix-gain-of", &[("name", &label)]) }
+            oninput={ on_input }
+        />
+    }
+}
+
+fn pan_input(label: &str, pan: f64, on_input: yew::Callback<InputEvent>) -> Html {
+    html! {
+        <input
+            type="range"
+            class="form-range"
+            min="-1"
+            max="1"
+            step="0.1"
+            value={ pan.to_string() }
+            title={ t_with("preview-mix-pan-of", &[("name", &label)]) }
+            oninput={ on_input }
+        />
+    }
+}
+
+/// Encodes the samples returned by `mixer_render`.
+fn rendered_to_wav(rendered: &JsValue) -> Result<Vec<u8>, String> {
+    let field = |name: &str| {
+        Reflect::get(rendered, &JsValue::from_str(name)).map_err(|error| format!("{error:?}"))
+    };
+    let sample_rate = field("sample_rate")?
+        .as_f64()
+        .ok_or("The sample rate is missing")? as u32;
+    let channels = Array::from(&field("channels")?)
+        .iter()
+        .map(|channel| Float32Array::from(channel).to_vec())
+        .collect::<Vec<_>>();
+    Ok(encode_wav(&channels, sample_rate))
+}
+
+#[cfg(test)]
+mod mix_test {
+    use std::collections::HashMap;
+
+    use time::OffsetDateTime;
+
+    use crate::{
+        components::submission::alignment::AlignmentState,
+        service::submission::{Submission, SubmissionKind},
+        utilities::alignment::Alignment,
+    };
+
+    use super::offsets;
+
+    fn submission(id: i64, playback_material_id: Option<i64>) -> Submission {
+        Submission {
+            id,
+            project_id: 1,
+            note: String::new(),
+            file_name: format!("take{id}.webm"),
+            file_technical_name: format!("take{id}"),
+            submitter: 1,
+            creator: 1,
+            creator_name: "Singer".to_string(),
+            creator_section: serde_json::from_str("\"Bass1\"").unwrap(),
+            upload_at: OffsetDateTime::UNIX_EPOCH,
+            kind: SubmissionKind::Audio,
+            purge_at: None,
+            playback_material_id,
+            sync_offset_ms: playback_material_id.map(|_| 120),
+        }
+    }
+
+    #[test]
+    fn recorded_offsets_are_only_used_for_the_reference() {
+        let submissions = [
+            submission(1, Some(2)),
+            submission(2, Some(5)),
+            submission(3, Some(5)),
+            submission(4, None),
+        ];
+        let alignment = HashMap::from([(
+            3,
+            AlignmentState::Done(Some(Alignment {
+                offset_ms: 300,
+                confidence: 0.9,
+            })),
+        )]);
+
+        let offsets = offsets(&submissions.iter().collect::<Vec<_>>(), &alignment, Some(2));
+
+        assert_eq!(offsets, vec![120, 0, 300, 0]);
+    }
+}
@@ -1,5 +1,9 @@
+pub mod alignment;
 pub mod details;
 pub mod list;
+pub mod mix;
+pub mod pitch;
+pub mod quality;
 pub mod update;
 
 use gloo_utils::document;
@@ -8,11 +12,10 @@ use web_sys::HtmlInputElement;
 use yew::{function_component, html, Callback, Html, Properties};
 
 use crate::{
-    service::submission::{Section, SubmissionKind},
-    utilities::callback::{
-        convert_string_callback, select_to_enum_callback_fallible,
-        select_to_enum_callback_infallible,
-    },
+    components::enum_select::EnumSelect,
+    i18n::t,
+    service::{section::Section, submission::SubmissionKind},
+    utilities::callback::convert_string_callback,
 };
 
 #[derive(Clone, Debug, PartialEq, Properties)]
@@ -28,14 +31,14 @@ pub struct InputSubmissionNoteProperties {
 pub fn input_submission_note(props: &InputSubmissionNoteProperties) -> Html {
     html! {
         <>
-        <label for={ props.id.clone() }> { "Anmerkungen" } </label>
+        <label for={ props.id.clone() }> { t("submission-note") } </label>
         <input id={ props.id.clone() }
             type="text"
             class="form-control"
             name="note"
             maxlength="100"
             value={ props.value.clone() }
-            placeholder="z.B. Takt 15 bitte rausschneiden..."
+            placeholder={ t("submission-note-placeholder") }
             oninput={convert_string_callback(props.on_input.clone())}/>
         </>
     }
@@ -51,22 +54,10 @@ pub struct InputSubmissionSectionProperties {
 
 #[function_component(InputSubmissionSection)]
 pub fn input_submission_section(props: &InputSubmissionSectionProperties) -> Html {
-    let section = props.selected;
     html! {
         <>
-        <label for={ props.id.clone() }> { "Stimme" } </label>
-        <select id={ props.id.clone() } name="section" class="form-control" required=true oninput={select_to_enum_callback_fallible(props.on_input.clone())}>
-            <option value="Soprano1" selected={ section == Section::Soprano1 }> { "Sopran 1" }</option>
-            <option value="Soprano2" selected={ section == Section::Soprano2 }> { "Sopran 2" }</option>
-            <option value="Alto1" selected={ section == Section::Alto1 }> { "Alt 1" }</option>
-            <option value="Alto2" selected={ section == Section::Alto2 }> { "Alt 2" }</option>
-            <option value="Tenor1" selected={ section == Section::Tenor1 }> { "Tenor 1" }</option>
-            <option value="Tenor2" selected={ section == Section::Tenor2 }> { "Tenor 2" }</option>
-            <option value="Bass1" selected={ section == Section::Bass1 }> { "Bass 1" }</option>
-            <option value="Bass2" selected={ section == Section::Bass2 }> { "Bass 2" }</option>
-            <option value="Conductor" selected={ section == Section::Conductor }> { "Dirigent" }</option>
-            <option value="Instrument" selected={ section == Section::Instrument }> { "Instrument" }</option>
-        </select>
+        <label for={ props.id.clone() }> { t("submission-section") } </label>
+        <EnumSelect<Section> id={ props.id.clone() } name="section" selected={ props.selected.clone() } on_input={ props.on_input.clone() }/>
         </>
     }
 }
@@ -74,23 +65,17 @@ pub fn input_submission_section(props: &InputSubmissionSectionProperties) -> Htm
 #[derive(Clone, Debug, PartialEq, Properties)]
 pub struct InputSubmissionKindProperties {
     #[prop_or_default]
-    pub on_input: Callback<SubmissionKind>,
+    pub on_input: Callback<Result<SubmissionKind, ()>>,
     pub selected: SubmissionKind,
     pub id: String,
 }
 
 #[function_component(InputSubmissionKind)]
 pub fn input_submission_kind(props: &InputSubmissionKindProperties) -> Html {
-    let selection = props.selected;
     html! {
         <>
-        <label for={ props.id.clone() }> { "Art" } </label>
-        <select id={ props.id.clone() } name="kind" class="form-control" required=true oninput={ select_to_enum_callback_infallible(props.on_input.clone()) }>
-            <option value="video" selected={ selection == SubmissionKind::Video} >{ "Video" }</option>
-            <option value="audio" selected={ selection == SubmissionKind::Audio}>{ "Audio" }</option>
-            <option value="document" selected={ selection == SubmissionKind::Document}>{ "Dokument" }</option>
-            <option value="other" selected={ selection == SubmissionKind::Other}>{ "Sonstiges" }</option>
-        </select>
+        <label for={ props.id.clone() }> { t("submission-kind") } </label>
+        <EnumSelect<SubmissionKind> id={ props.id.clone() } name="kind" selected={ props.selected } on_input={ props.on_input.clone() }/>
         </>
     }
 }
@@ -126,14 +111,14 @@ pub fn input_submission_creator_name(props: &InputSubmissionCreatorNamePropertie
 
     html! {
         <>
-        <label for={ props.id.clone() }> { "Ersteller*in" } </label>
+        <label for={ props.id.clone() }> { t("submission-creator") } </label>
         <input id={ props.id.clone() }
             type="text"
             class="form-control"
             name="creatorname"
             maxlength="100"
             value={ value.clone() }
-            placeholder={ "Name des/der Ersteller*in der Abgabe" }
+            placeholder={ t("submission-creator-placeholder") }
             oninput={convert_string_callback(props.on_input.clone())}/>
         </>
     }
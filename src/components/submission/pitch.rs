@@ -0,0 +1,555 @@
+use std::rc::Rc;
+
+use gloo_console::error;
+use gloo_dialogs::alert;
+use js_sys::Uint8Array;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    CanvasRenderingContext2d, Event, File, HtmlCanvasElement, HtmlInputElement, HtmlSelectElement,
+    InputEvent,
+};
+use yew::{html, Component, Context, Html, NodeRef, Properties, TargetCast};
+
+use crate::{
+    i18n::{t, t_with},
+    service::{
+        material::{material_by_project, material_url, MaterialCategory, MaterialTo},
+        submission::{submission_stream_url, Submission},
+    },
+    utilities::{
+        audio::{decode_file, decode_url, DecodedAudio},
+        pitch::{deviation_cents, track_pitch, Intonation, PitchTrack, IN_TUNE_CENTS},
+        requests::fetch::{get_request_bytes, FetchError},
+        score::{is_score_file, parse_score, Part},
+        select_enum::SelectEnum,
+    },
+};
+
+const HEIGHT: u32 = 160;
+
+/// Deviations up to this many cents are drawn as slightly off, larger ones as out of tune.
+const SLIGHTLY_OFF_CENTS: f64 = 50.0;
+
+const COLOR_REFERENCE: &str = "#ced4da";
+const COLOR_IN_TUNE: &str = "#198754";
+const COLOR_SLIGHTLY_OFF: &str = "#fd7e14";
+const COLOR_OFF: &str = "#dc3545";
+const COLOR_UNCOMPARED: &str = "#6c757d";
+const COLOR_GRID: &str = "#f1f3f5";
+
+/// What the submission is compared to.
+#[derive(Clone, PartialEq)]
+pub enum Reference {
+    Material(i64),
+    File(File),
+}
+
+/// The loaded reference: the pitch of a recording or the parts of a score.
+pub enum ReferenceData {
+    Track(PitchTrack),
+    Parts(Vec<Part>),
+}
+
+pub enum Msg {
+    Open,
+    MaterialLoaded(Vec<MaterialTo>),
+    MaterialLoadFailed(FetchError),
+    SelectReference(Event),
+    SelectFile(Event),
+    SelectPart(Event),
+    OffsetInput(InputEvent),
+    Analyse,
+    /// The error is shown to the user.
+    Analysed(Result<(Rc<PitchTrack>, ReferenceData), String>),
+}
+
+#[derive(PartialEq, Properties)]
+pub struct PitchAnalysisProperties {
+    pub submission: Submission,
+}
+
+/// Compares the pitch of an audio submission to a recording or score of its part, so singers see where they were out of tune.
+/// Opt-in, as it downloads and analyses the files in the browser.
+pub struct PitchAnalysis {
+    /// Material that can serve as reference. [None] until the analysis is opened.
+    references: Option<Vec<MaterialTo>>,
+    selected: Option<Reference>,
+    /// Milliseconds into the submission at which the reference starts.
+    offset_ms: i64,
+    sung: Option<Rc<PitchTrack>>,
+    reference: Option<PitchTrack>,
+    /// Parts of the score chosen as reference.
+    parts: Vec<Part>,
+    selected_part: usize,
+    running: bool,
+    canvas: NodeRef,
+}
+
+impl Component for PitchAnalysis {
+    type Message = Msg;
+    type Properties = PitchAnalysisProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            references: None,
+            selected: None,
+            offset_ms: 0,
+            sung: None,
+            reference: None,
+            parts: Vec::new(),
+            selected_part: 0,
+            running: false,
+            canvas: NodeRef::default(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Open => {
+                let project_id = ctx.props().submission.project_id;
+                ctx.link().send_future(async move {
+                    match material_by_project(project_id).await {
+                        Ok(material) => Msg::MaterialLoaded(material),
+                        Err(error) => Msg::MaterialLoadFailed(error),
+                    }
+                });
+                self.references = Some(Vec::new());
+                true
+            }
+            Msg::MaterialLoaded(material) => {
+                let references = material
+                    .into_iter()
+                    .filter(|material| {
+                        material.category == MaterialCategory::Audio
+                            || is_score_file(&material.file_name)
+                    })
+                    .collect::<Vec<_>>();
+                let submission = &ctx.props().submission;
+                let section = submission.creator_section.value();
+                let preselected = references
+                    .iter()
+                    .find(|material| Some(material.id) == submission.playback_material_id)
+                    .or_else(|| {
+                        references
+                            .iter()
+                            .find(|material| title_names_section(&material.title, &section))
+                    })
+                    .or(references.first());
+                if let Some(material) = preselected {
+                    self.select(ctx, Reference::Material(material.id));
+                }
+                self.references = Some(references);
+                true
+            }
+            Msg::MaterialLoadFailed(error) => {
+                error!(format!("Could not load the references: {error}"));
+                alert(&t("pitch-reference-failed"));
+                self.references = Some(Vec::new());
+                true
+            }
+            Msg::SelectReference(event) => {
+                let select: HtmlSelectElement = event.target_unchecked_into();
+                match select.value().parse() {
+                    Ok(id) => self.select(ctx, Reference::Material(id)),
+                    Err(_) => self.selected = None,
+                }
+                true
+            }
+            Msg::SelectFile(event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                    self.select(ctx, Reference::File(file));
+                }
+                true
+            }
+            Msg::SelectPart(event) => {
+                let select: HtmlSelectElement = event.target_unchecked_into();
+                self.selected_part = select.value().parse().unwrap_or_default();
+                self.reference = self
+                    .parts
+                    .get(self.selected_part)
+                    .map(|part| PitchTrack::from_notes(&part.notes));
+                true
+            }
+            Msg::OffsetInput(event) => {
+                let input: HtmlInputElement = event.target_unchecked_into();
+                match input.value().parse() {
+                    Ok(offset) => {
+                        self.offset_ms = offset;
+                        true
+                    }
+                    Err(_) => false,
+                }
+            }
+            Msg::Analyse => {
+                let Some(reference) = self.selected.clone() else {
+                    return false;
+                };
+                let submission = &ctx.props().submission;
+                let submission_url =
+                    submission_stream_url(submission.project_id, &submission.file_technical_name);
+                let material = match &reference {
+                    Reference::Material(id) => self
+                        .references
+                        .iter()
+                        .flatten()
+                        .find(|material| material.id == *id)
+                        .cloned(),
+                    Reference::File(_) => None,
+                };
+                let sung = self.sung.clone();
+
+                ctx.link().send_future(async move {
+                    let sung = match sung {
+                        Some(sung) => sung,
+                        None => match decode_url(&submission_url).await {
+                            Ok(audio) => Rc::new(track(&audio)),
+                            Err(message) => {
+                                error!(format!("Could not decode the submission: {message}"));
+                                return Msg::Analysed(Err(t("pitch-failed")));
+                            }
+                        },
+                    };
+                    Msg::Analysed(
+                        load_reference(reference, material)
+                            .await
+                            .map(|reference| (sung, reference)),
+                    )
+                });
+                self.running = true;
+                true
+            }
+            Msg::Analysed(result) => {
+                self.running = false;
+                match result {
+                    Ok((sung, reference)) => {
+                        self.sung = Some(sung);
+                        match reference {
+                            ReferenceData::Track(track) => {
+                                self.parts = Vec::new();
+                                self.reference = Some(track);
+                            }
+                            ReferenceData::Parts(parts) => {
+                                self.selected_part = preselected_part(
+                                    &parts,
+                                    &ctx.props().submission.creator_section.value(),
+                                );
+                                self.reference =
+                                    Some(PitchTrack::from_notes(&parts[self.selected_part].notes));
+                                self.parts = parts;
+                            }
+                        }
+                    }
+                    Err(message) => {
+                        alert(&message);
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Some(references) = &self.references else {
+            return html! {
+                <button type="button" class="btn btn-sm btn-outline-secondary mb-2" onclick={ ctx.link().callback(|_| Msg::Open) }>
+                    { t("pitch-open") }
+                </button>
+            };
+        };
+        let selected_material = match &self.selected {
+            Some(Reference::Material(id)) => Some(*id),
+            _ => None,
+        };
+        let intonation = self.intonation();
+
+        html! {
+            <div class="mb-3">
+                <h5>{ t("pitch-title") }</h5>
+                <p class="text-muted small mb-2">{ t("pitch-local") }</p>
+                <div class="row g-2 align-items-end mb-2">
+                    <div class="col-md-4">
+                        <label for="selectPitchReference" class="form-label">{ t("pitch-reference") }</label>
+                        <select id="selectPitchReference" class="form-select form-select-sm" onchange={ ctx.link().callback(Msg::SelectReference) }>
+                            <option value="" selected={ selected_material.is_none() }>{ t("pitch-reference-file") }</option>
+                            { for references.iter().map(|material| html! {
+                                <option value={ material.id.to_string() } selected={ selected_material == Some(material.id) }>
+                                    { &material.title }
+                                </option>
+                            }) }
+                        </select>
+                    </div>
+                    if selected_material.is_none() {
+                        <div class="col-md-4">
+                            <input
+                                type="file"
+                                class="form-control form-control-sm"
+                                accept=".mid,.midi,.musicxml,.xml,audio/*"
+                                onchange={ ctx.link().callback(Msg::SelectFile) }
+                            />
+                        </div>
+                    }
+                    if self.parts.len() > 1 {
+                        <div class="col-md-2">
+                            <label for="selectPitchPart" class="form-label">{ t("pitch-part") }</label>
+                            <select id="selectPitchPart" class="form-select form-select-sm" onchange={ ctx.link().callback(Msg::SelectPart) }>
+                                { for self.parts.iter().enumerate().map(|(index, part)| html! {
+                                    <option value={ index.to_string() } selected={ index == self.selected_part }>{ &part.name }</option>
+                                }) }
+                            </select>
+                        </div>
+                    }
+                    <div class="col-md-2">
+                        <label for="inputPitchOffset" class="form-label">{ t("pitch-offset") }</label>
+                        <input
+                            id="inputPitchOffset"
+                            type="number"
+                            step="10"
+                            class="form-control form-control-sm"
+                            value={ self.offset_ms.to_string() }
+                            oninput={ ctx.link().callback(Msg::OffsetInput) }
+                        />
+                    </div>
+                    <div class="col-auto">
+                        <button
+                            type="button"
+                            class="btn btn-sm btn-outline-primary"
+                            disabled={ self.running || self.selected.is_none() }
+                            onclick={ ctx.link().callback(|_| Msg::Analyse) }
+                        >
+                            { if self.running { t("pitch-running") } else { t("pitch-analyse") } }
+                        </button>
+                    </div>
+                </div>
+                if let Some(intonation) = intonation {
+                    <canvas ref={ self.canvas.clone() } class="w-100 border rounded" height={ HEIGHT.to_string() }></canvas>
+                    <p class="small mb-0">
+                        {
+                            match (intonation.in_tune_share(), intonation.median_deviation()) {
+                                (Some(share), Some(median)) => t_with("pitch-summary", &[
+                                    ("share", &format!("{:.0}", share * 100.0)),
+                                    ("cents", &IN_TUNE_CENTS),
+                                    ("median", &format!("{median:+.0}")),
+                                ]),
+                                _ => t("pitch-nothing-compared"),
+                            }
+                        }
+                    </p>
+                    <p class="small text-muted">{ t("pitch-legend") }</p>
+                }
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        self.draw();
+    }
+}
+
+impl PitchAnalysis {
+    fn select(&mut self, ctx: &Context<Self>, reference: Reference) {
+        let submission = &ctx.props().submission;
+        // Recordings made along the playback know where it starts.
+        self.offset_ms = match reference {
+            Reference::Material(id) if Some(id) == submission.playback_material_id => {
+                submission.sync_offset_ms.unwrap_or_default()
+            }
+            _ => 0,
+        };
+        self.selected = Some(reference);
+        self.reference = None;
+        self.parts = Vec::new();
+    }
+
+    fn intonation(&self) -> Option<Intonation> {
+        Some(Intonation::compare(
+            self.sung.as_ref()?,
+            self.reference.as_ref()?,
+            self.offset_ms as f64 / 1000.0,
+        ))
+    }
+
+    fn draw(&self) {
+        let (Some(canvas), Some(intonation)) =
+            (self.canvas.cast::<HtmlCanvasElement>(), self.intonation())
+        else {
+            return;
+        };
+        let width = canvas.client_width().max(1) as u32;
+        if canvas.width() != width {
+            canvas.set_width(width);
+        }
+        let Some(context) = canvas
+            .get_context("2d")
+            .ok()
+            .flatten()
+            .and_then(|context| context.dyn_into::<CanvasRenderingContext2d>().ok())
+        else {
+            return;
+        };
+
+        // Sung pitches are moved into the octave of the reference, as octaves are not counted as deviation.
+        let points = intonation
+            .frames
+            .iter()
+            .map(|&(sung, reference)| match (sung, reference) {
+                (Some(sung), Some(reference)) => {
+                    let octaves = ((sung - reference) / 12.0).round();
+                    (Some(sung - 12.0 * octaves), Some(reference))
+                }
+                frame => frame,
+            })
+            .collect::<Vec<_>>();
+        let pitches = points
+            .iter()
+            .flat_map(|&(sung, reference)| [sung, reference])
+            .flatten();
+        let (low, high) = pitches.fold((f32::INFINITY, f32::NEG_INFINITY), |(low, high), p| {
+            (low.min(p), high.max(p))
+        });
+        if !low.is_finite() {
+            return;
+        }
+        let (low, high) = ((low - 1.0).floor() as f64, (high + 1.0).ceil() as f64);
+
+        let height = HEIGHT as f64;
+        let x_of = |index: usize| index as f64 / points.len().max(1) as f64 * width as f64;
+        let y_of = |pitch: f32| height - (pitch as f64 - low) / (high - low) * height;
+        context.clear_rect(0.0, 0.0, width as f64, height);
+
+        context.set_fill_style_str(COLOR_GRID);
+        for pitch in (low as i32..=high as i32).filter(|pitch| pitch % 12 == 0) {
+            context.fill_rect(0.0, y_of(pitch as f32), width as f64, 1.0);
+            context.set_fill_style_str(COLOR_UNCOMPARED);
+            let _ = context.fill_text(
+                &format!("C{}", pitch / 12 - 1),
+                2.0,
+                y_of(pitch as f32) - 2.0,
+            );
+            context.set_fill_style_str(COLOR_GRID);
+        }
+
+        context.set_fill_style_str(COLOR_REFERENCE);
+        for (index, (_, reference)) in points.iter().enumerate() {
+            if let Some(reference) = reference {
+                context.fill_rect(x_of(index), y_of(*reference) - 2.0, 1.5, 4.0);
+            }
+        }
+
+        for (index, ((sung, _), &(original, reference))) in
+            points.iter().zip(&intonation.frames).enumerate()
+        {
+            let Some(sung) = sung else {
+                continue;
+            };
+            let deviation = original
+                .zip(reference)
+                .map(|(original, reference)| deviation_cents(original, reference));
+            context.set_fill_style_str(match deviation.map(f64::abs) {
+                None => COLOR_UNCOMPARED,
+                Some(cents) if cents <= IN_TUNE_CENTS => COLOR_IN_TUNE,
+                Some(cents) if cents <= SLIGHTLY_OFF_CENTS => COLOR_SLIGHTLY_OFF,
+                Some(_) => COLOR_OFF,
+            });
+            context.fill_rect(x_of(index), y_of(*sung) - 1.0, 1.5, 2.0);
+        }
+    }
+}
+
+fn track(audio: &DecodedAudio) -> PitchTrack {
+    track_pitch(&audio.mono(), audio.sample_rate)
+}
+
+async fn load_reference(
+    reference: Reference,
+    material: Option<MaterialTo>,
+) -> Result<ReferenceData, String> {
+    let failed = |message: String| {
+        error!(format!("Could not load the reference: {message}"));
+        t("pitch-reference-failed")
+    };
+
+    match (reference, material) {
+        (Reference::Material(_), Some(material)) => {
+            let url = material_url(material.project_id, &material.file_technical_name);
+            if is_score_file(&material.file_name) {
+                let content = get_request_bytes(&url)
+                    .await
+                    .map_err(|error| failed(error.to_string()))?;
+                parse_score(&content)
+                    .map(ReferenceData::Parts)
+                    .map_err(|error| error.message())
+            } else {
+                let audio = decode_url(&url).await.map_err(failed)?;
+                Ok(ReferenceData::Track(track(&audio)))
+            }
+        }
+        (Reference::File(file), _) => {
+            if is_score_file(&file.name()) {
+                let buffer = JsFuture::from(file.array_buffer())
+                    .await
+                    .map_err(|error| failed(format!("{error:?}")))?;
+                parse_score(&Uint8Array::new(&buffer).to_vec())
+                    .map(ReferenceData::Parts)
+                    .map_err(|error| error.message())
+            } else {
+                let audio = decode_file(&file).await.map_err(failed)?;
+                Ok(ReferenceData::Track(track(&audio)))
+            }
+        }
+        (Reference::Material(id), None) => Err(failed(format!("No material {id}"))),
+    }
+}
+
+/// Whether the title names the section by its configured value, e.g. "Soprano 1 playback" for `Soprano1`.
+/// Case, spaces and punctuation are ignored. Labels are not compared, as they depend on the language of the user.
+fn title_names_section(title: &str, section_value: &str) -> bool {
+    let compact = |text: &str| {
+        text.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    let section = compact(section_value);
+    !section.is_empty() && compact(title).contains(&section)
+}
+
+/// The part of the score named after the section, else the first one.
+fn preselected_part(parts: &[Part], section_value: &str) -> usize {
+    parts
+        .iter()
+        .position(|part| title_names_section(&part.name, section_value))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod pitch_test {
+    use crate::utilities::score::Part;
+
+    use super::{preselected_part, title_names_section};
+
+    fn part(name: &str) -> Part {
+        Part {
+            name: name.to_string(),
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn titles_are_matched_by_section_value() {
+        assert!(title_names_section("Soprano 1 - Playback", "Soprano1"));
+        assert!(title_names_section("bass1.mp3", "Bass1"));
+        assert!(!title_names_section("Sopran 1", "Soprano1"));
+        assert!(!title_names_section("Tutti", ""));
+    }
+
+    #[test]
+    fn parts_are_matched_by_section_value() {
+        let parts = [part("Piano"), part("Alto 1"), part("Soprano 1")];
+
+        assert_eq!(preselected_part(&parts, "Soprano1"), 2);
+        assert_eq!(preselected_part(&parts, "Alto1"), 1);
+        assert_eq!(preselected_part(&parts, "Bass1"), 0);
+    }
+}
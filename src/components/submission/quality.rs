@@ -0,0 +1,233 @@
+use gloo_console::error;
+use web_sys::File;
+use yew::{function_component, html, Component, Context, Html, Properties};
+
+use crate::{
+    i18n::{t, t_with},
+    utilities::{
+        audio::{decode_file, decode_url},
+        date::format_elapsed,
+        quality::{format_db, AudioQuality, QualityRating},
+    },
+};
+
+/// Downloads and analyses an uploaded submission.
+pub async fn check_url(url: &str) -> Result<AudioQuality, String> {
+    decode_url(url)
+        .await
+        .map(|audio| AudioQuality::analyse(&audio))
+}
+
+#[derive(PartialEq, Properties)]
+pub struct QualityProperties {
+    pub quality: AudioQuality,
+}
+
+/// The rating of a recording, its warnings are shown on hover.
+#[function_component(QualityBadge)]
+pub fn quality_badge(props: &QualityProperties) -> Html {
+    let rating = props.quality.rating();
+    let warnings = props
+        .quality
+        .warnings()
+        .iter()
+        .map(|warning| warning.message())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    html! {
+        <span class={ rating.badge_class() } title={ warnings }>{ rating.label() }</span>
+    }
+}
+
+/// The warnings and measurements of a recording.
+#[function_component(QualityReport)]
+pub fn quality_report(props: &QualityProperties) -> Html {
+    let quality = &props.quality;
+    let warnings = quality.warnings();
+    let seconds = |seconds: f64| format_elapsed(seconds.round() as i64);
+
+    html! {
+        <>
+        if !warnings.is_empty() {
+            <ul class="mb-1">
+                { for warnings.iter().map(|warning| html! {
+                    <li class={ if warning.is_severe() { "text-danger" } else { "" } }>{ warning.message() }</li>
+                }) }
+            </ul>
+        }
+        <details class="small">
+            <summary>{ t("quality-measurements") }</summary>
+            <table class="table table-sm mb-0">
+                <tr>
+                    <td>{ t("quality-duration") }</td>
+                    <td>{ seconds(quality.duration_seconds) }</td>
+                </tr>
+                <tr>
+                    <td>{ t("quality-sample-rate") }</td>
+                    <td>
+                        { quality.sample_rate.map_or_else(
+                            || t("quality-sample-rate-unknown"),
+                            |rate| t_with("quality-sample-rate-value", &[("rate", &format!("{:.1}", rate as f64 / 1000.0))]),
+                        ) }
+                    </td>
+                </tr>
+                <tr>
+                    <td>{ t("quality-channels") }</td>
+                    <td>{ quality.channels }</td>
+                </tr>
+                <tr>
+                    <td>{ t("quality-peak") }</td>
+                    <td>{ t_with("quality-peak-value", &[("peak", &format_db(quality.peak_dbfs)), ("true_peak", &format_db(quality.true_peak_dbtp))]) }</td>
+                </tr>
+                <tr>
+                    <td>{ t("quality-clipping") }</td>
+                    <td>{ quality.clipping_count }</td>
+                </tr>
+                <tr>
+                    <td>{ t("quality-loudness") }</td>
+                    <td>{ t_with("quality-loudness-value", &[("loudness", &quality.loudness_lufs.map_or_else(|| format_db(f64::NEG_INFINITY), format_db))]) }</td>
+                </tr>
+                <tr>
+                    <td>{ t("quality-silence") }</td>
+                    <td>{ format!("{} / {}", seconds(quality.leading_silence_seconds), seconds(quality.trailing_silence_seconds)) }</td>
+                </tr>
+            </table>
+        </details>
+        </>
+    }
+}
+
+enum FileCheck {
+    Running,
+    Done(AudioQuality),
+    Failed,
+}
+
+pub enum Msg {
+    Checked {
+        /// Results for files that are no longer selected are dropped.
+        generation: u32,
+        index: usize,
+        result: Result<AudioQuality, String>,
+    },
+}
+
+#[derive(PartialEq, Properties)]
+pub struct QualityCheckProperties {
+    /// Files about to be uploaded. Only audio files are checked.
+    pub files: Vec<File>,
+}
+
+/// Checks the audio files before they are uploaded, so singers can record again instead of submitting a broken recording.
+pub struct QualityCheck {
+    checks: Vec<(String, FileCheck)>,
+    generation: u32,
+}
+
+impl Component for QualityCheck {
+    type Message = Msg;
+    type Properties = QualityCheckProperties;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut check = Self {
+            checks: Vec::new(),
+            generation: 0,
+        };
+        check.start(ctx);
+        check
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.generation += 1;
+        self.start(ctx);
+        true
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Checked {
+                generation,
+                index,
+                result,
+            } => {
+                if generation != self.generation {
+                    return false;
+                }
+                let Some((name, check)) = self.checks.get_mut(index) else {
+                    return false;
+                };
+                *check = match result {
+                    Ok(quality) => FileCheck::Done(quality),
+                    Err(message) => {
+                        error!(format!("Could not check the quality of {name}: {message}"));
+                        FileCheck::Failed
+                    }
+                };
+                true
+            }
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html! {
+            { for self.checks.iter().map(|(name, check)| match check {
+                FileCheck::Running => html! {
+                    <div class="alert alert-secondary mt-2 mb-0">{ t_with("quality-checking", &[("file", name)]) }</div>
+                },
+                FileCheck::Failed => html! {
+                    <div class="alert alert-secondary mt-2 mb-0">{ t_with("quality-failed", &[("file", name)]) }</div>
+                },
+                FileCheck::Done(quality) => {
+                    let class = match quality.rating() {
+                        QualityRating::Good => "alert alert-success mt-2 mb-0",
+                        QualityRating::Warnings => "alert alert-warning mt-2 mb-0",
+                        QualityRating::Problems => "alert alert-danger mt-2 mb-0",
+                    };
+                    html! {
+                        <div { class }>
+                            <p class="mb-1">
+                                <b>{ name }</b>{ " " }<QualityBadge quality={ quality.clone() } />
+                                if !quality.warnings().is_empty() {
+                                    { " " }{ t("quality-upload-anyway") }
+                                }
+                            </p>
+                            <QualityReport quality={ quality.clone() } />
+                        </div>
+                    }
+                }
+            }) }
+        }
+    }
+}
+
+impl QualityCheck {
+    fn start(&mut self, ctx: &Context<Self>) {
+        let files = ctx
+            .props()
+            .files
+            .iter()
+            // By type, as recordings of the browser are `.webm` files with or without video.
+            .filter(|file| file.type_().starts_with("audio/"))
+            .cloned()
+            .collect::<Vec<_>>();
+        self.checks = files
+            .iter()
+            .map(|file| (file.name(), FileCheck::Running))
+            .collect();
+
+        let generation = self.generation;
+        for (index, file) in files.into_iter().enumerate() {
+            ctx.link().send_future(async move {
+                let result = decode_file(&file)
+                    .await
+                    .map(|audio| AudioQuality::analyse(&audio));
+                Msg::Checked {
+                    generation,
+                    index,
+                    result,
+                }
+            });
+        }
+    }
+}
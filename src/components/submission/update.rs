@@ -12,7 +12,11 @@ use crate::{
             InputSubmissionSection,
         },
     },
-    service::submission::{Section, Submission, SubmissionKind},
+    i18n::t,
+    service::{
+        section::Section,
+        submission::{Submission, SubmissionKind},
+    },
 };
 
 #[derive(Clone, Debug, PartialEq, Properties)]
@@ -39,7 +43,7 @@ pub struct SubmissionUpdateData {
 pub enum UpdateMsg {
     Note(String),
     Section(Result<Section, ()>),
-    Kind(SubmissionKind),
+    Kind(Result<SubmissionKind, ()>),
     CreatorName(String),
     #[allow(dead_code)] // Field is required to be a valid event target
     Submit(MouseEvent),
@@ -83,6 +87,14 @@ impl Component for SubmissionUpdate {
                 false
             }
             UpdateMsg::Kind(kind) => {
+                let kind = match kind {
+                    Ok(k) => k,
+                    Err(_) => {
+                        enum_implement_warning("kind");
+                        return false;
+                    }
+                };
+
                 data.kind = kind;
                 false
             }
@@ -108,11 +120,11 @@ impl Component for SubmissionUpdate {
 
         html! {
             <Modal
-                title={ "Abgabe anpassen".to_string() }
+                title={ t("submission-update-title") }
                 id= { ctx.props().id.clone() }
                 actions = { vec![
-                    ("Abbrechen".to_string(), "btn btn-secondary".to_string(), ctx.props().on_abort.clone()),
-                    ("Erstellen".to_string(), "btn btn-danger".to_string(), ctx.link().callback(UpdateMsg::Submit))
+                    (t("modal-cancel"), "btn btn-secondary".to_string(), ctx.props().on_abort.clone()),
+                    (t("submission-update-save"), "btn btn-danger".to_string(), ctx.link().callback(UpdateMsg::Submit))
                     ]
                 }
             >
@@ -131,7 +143,7 @@ impl Component for SubmissionUpdate {
                                     </div>
 
                                     <div class="col-auto">
-                                        <InputSubmissionSection id={ "selectUpdatedSection".to_string() } selected={ submission.creator_section } on_input={ctx.link().callback(UpdateMsg::Section)}/>
+                                        <InputSubmissionSection id={ "selectUpdatedSection".to_string() } selected={ submission.creator_section.clone() } on_input={ctx.link().callback(UpdateMsg::Section)}/>
                                     </div>
                                     <div class="col-auto">
                                         <InputSubmissionKind id={ "selectUpdatedSubmissionKind".to_string() } selected={ submission.kind } on_input={ctx.link().callback(UpdateMsg::Kind)}/>
@@ -142,7 +154,7 @@ impl Component for SubmissionUpdate {
                         }
                     }
 
-                    None => html!{{ "Fehler! Keine Abgabe ausgewählt!" }}
+                    None => html!{{ t("submission-list-none-selected") }}
                 }
 
             }
@@ -152,11 +164,11 @@ impl Component for SubmissionUpdate {
 }
 
 fn data_not_initialized_warning() {
-    alert("Keine Abgabe zur Änderung ausgewählt. Bitte das Fenster schließen und es erneut versuchen!");
+    alert(&t("submission-update-none-selected"));
 }
 
 fn enum_implement_warning(field: &str) {
-    alert("Bitte merke Dir die Schritte, die du ausgeführt hast. Ein Fehler ist aufgetreten, wende dich an den/die Administrator*in");
+    alert(&t("submission-update-select-failed"));
 
     error!("Select option could not be parsed: {}", field);
 }
@@ -169,7 +181,7 @@ impl SubmissionUpdate {
             .map(|submission| SubmissionUpdateData {
                 id: submission.id,
                 note: submission.note.clone(),
-                section: submission.creator_section,
+                section: submission.creator_section.clone(),
                 kind: submission.kind,
                 creator_name: submission.creator_name.clone(),
             })
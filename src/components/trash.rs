@@ -0,0 +1,92 @@
+use gloo_dialogs::confirm;
+use time::OffsetDateTime;
+use yew::{function_component, html, Callback, Html, Properties};
+
+use crate::{
+    components::{countdown::Countdown, view_as::is_viewing_as},
+    i18n::{t, t_with},
+};
+
+/// Something deleted that can be restored until it is purged.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrashItem {
+    pub id: i64,
+    pub name: String,
+    /// When the backend removes it for good. Unknown for items deleted before the trash existed.
+    pub purge_at: Option<OffsetDateTime>,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct TrashListProperties {
+    pub items: Vec<TrashItem>,
+    pub on_restore: Callback<i64>,
+    /// Only called after the user confirmed the purge.
+    pub on_purge: Callback<i64>,
+}
+
+/// Lists deleted items with buttons to restore them or to purge them immediately.
+#[function_component(TrashList)]
+pub fn trash_list(props: &TrashListProperties) -> Html {
+    let read_only = is_viewing_as();
+
+    if props.items.is_empty() {
+        return html! {
+            <p><i>{ t("trash-empty") }</i></p>
+        };
+    }
+
+    html! {
+        <div class="table-responsive">
+            <table class="table table-sm">
+                <thead>
+                    <tr>
+                        <th>{ t("trash-name") }</th>
+                        <th>{ t("trash-purge-at") }</th>
+                        <th colspan="2"></th>
+                    </tr>
+                </thead>
+                <tbody>
+                {
+                    for props.items.iter().map(|item| {
+                        let id = item.id;
+                        let name = item.name.clone();
+                        let on_restore = props.on_restore.clone();
+                        let on_purge = props.on_purge.clone();
+
+                        html! {
+                            <tr>
+                                <td>{ &item.name }</td>
+                                <td>
+                                    if let Some(purge_at) = item.purge_at {
+                                        <Countdown due={ purge_at }/>
+                                    } else {
+                                        { t("trash-purge-unknown") }
+                                    }
+                                </td>
+                                <td>
+                                    <button class="btn btn-sm btn-outline-primary" disabled={ read_only } onclick={ move |_| on_restore.emit(id) }>
+                                        { t("trash-restore") }
+                                    </button>
+                                </td>
+                                <td>
+                                    <button
+                                        class="btn btn-sm btn-danger"
+                                        disabled={ read_only }
+                                        onclick={ move |_| {
+                                            if confirm(&t_with("trash-purge-confirm", &[("name", &name)])) {
+                                                on_purge.emit(id);
+                                            }
+                                        } }
+                                    >
+                                        { t("trash-purge") }
+                                    </button>
+                                </td>
+                            </tr>
+                        }
+                    })
+                }
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -1,9 +1,13 @@
 use gloo_dialogs::alert;
 use wasm_bindgen::UnwrapThrowExt;
-use web_sys::{ErrorEvent, Event, InputEvent, ProgressEvent};
-use yew::{html, Callback, Component, Context, Html, Properties};
+use web_sys::{ErrorEvent, Event, HtmlInputElement, InputEvent, ProgressEvent};
+use yew::{html, Callback, Component, Context, Html, NodeRef, Properties};
 
-use crate::components::progress::ProgressComponent;
+use crate::{
+    components::{progress::ProgressComponent, view_as::is_viewing_as},
+    i18n::{t, t_with},
+    service::CONFIG,
+};
 
 use xmlhttp::xmlhttp_post_request::{PostRequest, SentRequest};
 
@@ -25,6 +29,7 @@ pub struct Upload {
     progress: Option<Progress>,
     upload_successfully_finished: bool,
     current_request: Option<SentRequest>,
+    file_input: NodeRef,
 }
 
 #[derive(PartialEq, Properties)]
@@ -39,6 +44,9 @@ pub struct UploadProperties {
     pub change_callback: Callback<Event>,
     #[prop_or_default]
     pub input_callback: Callback<InputEvent>,
+    /// If set, uploading is disabled and the reason is shown instead.
+    #[prop_or_default]
+    pub disabled_reason: Option<String>,
 }
 
 impl Component for Upload {
@@ -50,6 +58,7 @@ impl Component for Upload {
             progress: None,
             upload_successfully_finished: false,
             current_request: None,
+            file_input: NodeRef::default(),
         }
     }
 
@@ -60,6 +69,16 @@ impl Component for Upload {
                 false
             }
             Msg::UploadFile => {
+                if let Some(reason) = disabled_reason(ctx) {
+                    alert(&reason);
+                    return false;
+                }
+
+                if let Some(message) = self.oversized_files_message() {
+                    alert(&message);
+                    return false;
+                }
+
                 self.upload_successfully_finished = false;
                 let mut request = PostRequest::new_from_form(&ctx.props().form_id);
 
@@ -77,7 +96,7 @@ impl Component for Upload {
                 let error_callback = move |_error: ErrorEvent| {
                     link.send_message(Msg::UploadOnerror(
                         /*error.message() -> is undefined, creates a type error!*/
-                        "Noch nicht verfügbar, siehe Konsole (F11)".to_string(),
+                        t("upload-error-details-unavailable"),
                     ));
                 };
 
@@ -99,7 +118,7 @@ impl Component for Upload {
                 true
             }
             Msg::UploadOnerror(message) => {
-                alert(&format!("Beim Upload ist ein Fehler aufgetreten! Bitte versuche es erneut und wende dich dann an den/die Administrator*in. Fehlermeldung: {}", message));
+                alert(&t_with("upload-failed", &[("message", &message)]));
                 self.upload_successfully_finished = false;
                 self.progress = None;
                 self.current_request = None;
@@ -151,22 +170,31 @@ impl Component for Upload {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let disabled_reason = disabled_reason(ctx);
+        let disabled = disabled_reason.is_some();
+
         html! {
             <div>
                 <div>
 
                     <input
                         type="file"
+                        ref={ self.file_input.clone() }
                         class="form-control"
                         name={ctx.props().field_name.clone()}
                         multiple={ ctx.props().multiple }
                         onchange={ ctx.props().change_callback.clone() }
                         oninput={ ctx.props().input_callback.clone() }
+                        { disabled }
                     />
 
+                    if let Some(reason) = disabled_reason {
+                        <div class="alert alert-secondary mt-2 mb-0">{ reason }</div>
+                    }
+
                     if let Some(progress) = &self.progress {
                         <div class="mt-2">
-                            <h4>{ "Upload läuft" }</h4>
+                            <h4>{ t("upload-running") }</h4>
                             <ProgressComponent loaded={ progress.loaded } total={ progress.total }/>
                         </div>
                     }
@@ -174,15 +202,15 @@ impl Component for Upload {
                         <div class="col">
                             if self.upload_successfully_finished {
                                 <div class="mt-2">
-                                <h4> { "Upload erfolgreich!" } </h4>
+                                <h4> { t("upload-successful") } </h4>
                                 </div>
                             }
                             </div>
                         <div class="col text-end">
                             if self.current_request.is_some() {
-                                <button type="button" class="btn btn-danger" onclick={ctx.link().callback(move|_| { Msg::Abort })}> { "Abbrechen" } </button>
+                                <button type="button" class="btn btn-danger" onclick={ctx.link().callback(move|_| { Msg::Abort })}> { t("modal-cancel") } </button>
                             } else {
-                                <button type="button" class="btn btn-danger" onclick={ctx.link().callback(move |_| { Msg::Files })}> { "Upload starten" }</button>
+                                <button type="button" class="btn btn-danger" { disabled } onclick={ctx.link().callback(move |_| { Msg::Files })}> { t("upload-start") }</button>
                             }
                         </div>
                     </div>
@@ -191,3 +219,38 @@ impl Component for Upload {
         }
     }
 }
+
+impl Upload {
+    /// Names the selected files that are larger than the upload limit of the config, if there are any.
+    fn oversized_files_message(&self) -> Option<String> {
+        let config = CONFIG.get().expect("Config unset");
+        let max_size = config.max_upload_size_bytes()?;
+        let files = self.file_input.cast::<HtmlInputElement>()?.files()?;
+
+        let oversized = (0..files.length())
+            .filter_map(|index| files.get(index))
+            .filter(|file| file.size() > max_size)
+            .map(|file| file.name())
+            .collect::<Vec<_>>();
+
+        if oversized.is_empty() {
+            return None;
+        }
+
+        Some(t_with(
+            "upload-files-too-large",
+            &[
+                ("max", &config.max_upload_size_mb.unwrap_or_default()),
+                ("files", &oversized.join(", ")),
+            ],
+        ))
+    }
+}
+
+fn disabled_reason(ctx: &Context<Upload>) -> Option<String> {
+    if is_viewing_as() {
+        return Some(t("upload-blocked-view-as"));
+    }
+
+    ctx.props().disabled_reason.clone()
+}
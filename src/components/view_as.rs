@@ -0,0 +1,233 @@
+use gloo_console::error;
+use gloo_dialogs::alert;
+use gloo_storage::{SessionStorage, Storage};
+use gloo_utils::window;
+use serde::{Deserialize, Serialize};
+use yew::{function_component, html, Component, Html};
+
+use crate::{
+    components::{
+        jwt_context::{get_authenticated_token_data, PerformerData},
+        modal::Modal,
+        submission::InputSubmissionSection,
+    },
+    i18n::{t, t_with},
+    service::{section::Section, CONFIG},
+    utilities::callback::convert_string_callback,
+};
+
+pub const MODAL_VIEW_AS: &str = "modalViewAs";
+const VIEW_AS_STORAGE_KEY: &str = "viewAs";
+
+/// Identity of the member an admin views the portal as.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewAsIdentity {
+    pub user_id: i64,
+    pub name: String,
+    pub section: Section,
+}
+
+impl ViewAsIdentity {
+    /// Replaces the identity of the authenticated user with this one. Admin rights are removed, the choir stays the same.
+    pub fn apply_to(self, user: PerformerData) -> PerformerData {
+        PerformerData {
+            section: self.section,
+            user_id: self.user_id,
+            name: self.name,
+            is_admin: false,
+            exp: user.exp,
+            choir_id: user.choir_id,
+            choirs: Vec::new(),
+        }
+    }
+}
+
+/// Reads the identity the portal is currently viewed as, if any.
+/// The identity is kept in the session storage, so it survives reloads of the tab.
+/// Always [None] if the feature is switched off in the config.
+pub fn get_view_as() -> Option<ViewAsIdentity> {
+    let enabled = CONFIG
+        .get()
+        .map(|config| config.features.view_as)
+        .unwrap_or(false);
+
+    if !enabled {
+        return None;
+    }
+
+    SessionStorage::get(VIEW_AS_STORAGE_KEY).ok()
+}
+
+/// Whether an admin currently views the portal as another member.
+/// All changing requests are blocked while this is the case.
+pub fn is_viewing_as() -> bool {
+    get_view_as().is_some()
+        && get_authenticated_token_data()
+            .map(|user| user.is_admin)
+            .unwrap_or(false)
+}
+
+/// Stores the identity to view the portal as and reloads the page, so every component renders with it.
+pub fn start_view_as(identity: ViewAsIdentity) {
+    if let Err(error) = SessionStorage::set(VIEW_AS_STORAGE_KEY, identity) {
+        error!(format!("Could not store view as identity: {error}"));
+        alert(&t("view-as-failed"));
+        return;
+    }
+
+    reload();
+}
+
+/// Leaves the view as mode and reloads the page with the identity of the authenticated user.
+pub fn stop_view_as() {
+    SessionStorage::delete(VIEW_AS_STORAGE_KEY);
+    reload();
+}
+
+fn reload() {
+    if let Err(error) = window().location().reload() {
+        error!("Could not reload the page!");
+        error!(error);
+    }
+}
+
+#[function_component(ViewAsBanner)]
+pub fn view_as_banner() -> Html {
+    if !is_viewing_as() {
+        return html! {};
+    }
+
+    let identity = match get_view_as() {
+        Some(identity) => identity,
+        None => return html! {},
+    };
+
+    html! {
+        <div class="alert alert-warning sticky-top rounded-0 mb-0">
+            <div class="container">
+                <div class="row align-items-center">
+                    <div class="col">
+                        {
+                            t_with("view-as-banner", &[
+                                ("name", &identity.name),
+                                ("id", &identity.user_id),
+                                ("section", &identity.section),
+                            ])
+                        }
+                    </div>
+                    <div class="col-auto">
+                        <button type="button" class="btn btn-sm btn-warning" onclick={ |_| stop_view_as() }>
+                            { t("view-as-stop") }
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+pub struct ViewAsModal {
+    user_id: String,
+    name: String,
+    section: Section,
+}
+
+pub enum Msg {
+    UserId(String),
+    Name(String),
+    Section(Result<Section, ()>),
+    Confirm,
+}
+
+impl Component for ViewAsModal {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_ctx: &yew::Context<Self>) -> Self {
+        Self {
+            user_id: String::new(),
+            name: String::new(),
+            section: Section::first(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::UserId(user_id) => {
+                self.user_id = user_id;
+                false
+            }
+            Msg::Name(name) => {
+                self.name = name;
+                false
+            }
+            Msg::Section(section) => {
+                match section {
+                    Ok(section) => self.section = section,
+                    Err(_) => error!("Selected section could not be parsed!"),
+                }
+                false
+            }
+            Msg::Confirm => {
+                let user_id = match self.user_id.trim().parse::<i64>() {
+                    Ok(id) => id,
+                    Err(_) => {
+                        alert(&t("view-as-id-not-a-number"));
+                        return false;
+                    }
+                };
+
+                let name = match self.name.trim() {
+                    "" => t_with("view-as-default-name", &[("id", &user_id)]),
+                    name => name.to_string(),
+                };
+
+                start_view_as(ViewAsIdentity {
+                    user_id,
+                    name,
+                    section: self.section.clone(),
+                });
+
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> Html {
+        let actions = vec![
+            (
+                t("modal-cancel"),
+                "btn btn-secondary".to_string(),
+                Default::default(),
+            ),
+            (
+                t("view-as-confirm"),
+                "btn btn-danger".to_string(),
+                ctx.link().callback(|_| Msg::Confirm),
+            ),
+        ];
+
+        html! {
+            <Modal id={ MODAL_VIEW_AS } title={ t("view-as-open") } actions={ actions }>
+                <p>
+                    <i>{ t("view-as-explanation") }</i>
+                </p>
+                <form>
+                    <div class="row">
+                        <div class="col">
+                            <label for="inputViewAsUserId">{ t("view-as-id") }</label>
+                            <input id="inputViewAsUserId" type="number" class="form-control" oninput={ convert_string_callback(ctx.link().callback(Msg::UserId)) }/>
+                        </div>
+                        <div class="col">
+                            <label for="inputViewAsName">{ t("view-as-name") }</label>
+                            <input id="inputViewAsName" type="text" class="form-control" oninput={ convert_string_callback(ctx.link().callback(Msg::Name)) }/>
+                        </div>
+                        <div class="col-auto">
+                            <InputSubmissionSection id={ "selectViewAsSection".to_string() } selected={ self.section.clone() } on_input={ ctx.link().callback(Msg::Section) }/>
+                        </div>
+                    </div>
+                </form>
+            </Modal>
+        }
+    }
+}
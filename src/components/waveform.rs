@@ -0,0 +1,250 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use gloo_console::error;
+use gloo_timers::callback::Interval;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, MouseEvent};
+use yew::{html, Component, Context, Html, NodeRef, Properties};
+
+use crate::{
+    i18n::t,
+    utilities::{audio::decode_url, waveform::Waveform as Peaks},
+};
+
+#[wasm_bindgen(module = "/js/custom/practice.js")]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn media_time(id: &str) -> Result<f64, JsValue>;
+    #[wasm_bindgen(catch)]
+    fn seek_media(id: &str, seconds: f64) -> Result<(), JsValue>;
+}
+
+thread_local! {
+    /// Decoding is slow, so the peaks of each url are only computed once per page load.
+    static CACHE: RefCell<HashMap<String, Rc<Peaks>>> = RefCell::new(HashMap::new());
+}
+
+/// Time between two updates of the playhead in milliseconds.
+const PLAYHEAD_MILLIS: u32 = 100;
+
+const HEIGHT: u32 = 80;
+const MAX_ZOOM: u32 = 64;
+
+const COLOR_WAVE: &str = "#0d6efd";
+const COLOR_CLIPPED: &str = "#dc3545";
+const COLOR_SILENCE: &str = "#e9ecef";
+const COLOR_PLAYHEAD: &str = "#212529";
+
+pub enum Msg {
+    Loaded(Rc<Peaks>),
+    LoadFailed(String),
+    Tick,
+    Click(MouseEvent),
+    ZoomIn,
+    ZoomOut,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct WaveformProperties {
+    pub url: String,
+    /// Id of the audio element that is played, for the playhead and to seek.
+    pub element_id: String,
+}
+
+/// The waveform of an audio file with the position of its player, clipped parts red and silent parts grey.
+/// Clicking it seeks the player.
+pub struct Waveform {
+    peaks: Option<Rc<Peaks>>,
+    failed: bool,
+    zoom: u32,
+    playhead: f64,
+    canvas: NodeRef,
+    _ticker: Interval,
+}
+
+impl Component for Waveform {
+    type Message = Msg;
+    type Properties = WaveformProperties;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let url = ctx.props().url.clone();
+        let cached = CACHE.with(|cache| cache.borrow().get(&url).cloned());
+        if cached.is_none() {
+            ctx.link().send_future(async move {
+                match decode_url(&url).await {
+                    Ok(audio) => {
+                        let peaks = Rc::new(Peaks::new(&audio.mono(), audio.sample_rate));
+                        CACHE.with(|cache| cache.borrow_mut().insert(url, peaks.clone()));
+                        Msg::Loaded(peaks)
+                    }
+                    Err(message) => Msg::LoadFailed(message),
+                }
+            });
+        }
+
+        let link = ctx.link().clone();
+        Self {
+            peaks: cached,
+            failed: false,
+            zoom: 1,
+            playhead: 0.0,
+            canvas: NodeRef::default(),
+            _ticker: Interval::new(PLAYHEAD_MILLIS, move || link.send_message(Msg::Tick)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Loaded(peaks) => {
+                self.peaks = Some(peaks);
+                true
+            }
+            Msg::LoadFailed(message) => {
+                error!(format!("Could not load waveform: {message}"));
+                self.failed = true;
+                true
+            }
+            Msg::Tick => {
+                let playhead = media_time(&ctx.props().element_id).unwrap_or_default();
+                if playhead == self.playhead {
+                    return false;
+                }
+                self.playhead = playhead;
+                self.draw();
+                false
+            }
+            Msg::Click(event) => {
+                let (Some(canvas), Some(peaks)) = (self.canvas_element(), &self.peaks) else {
+                    return false;
+                };
+                let (start, end) = visible_range(peaks.duration(), self.zoom, self.playhead);
+                let fraction = event.offset_x() as f64 / canvas.client_width().max(1) as f64;
+                let time = start + fraction * (end - start);
+                if let Err(js_error) = seek_media(&ctx.props().element_id, time) {
+                    error!(js_error);
+                }
+                false
+            }
+            Msg::ZoomIn => {
+                self.zoom = (self.zoom * 2).min(MAX_ZOOM);
+                true
+            }
+            Msg::ZoomOut => {
+                self.zoom = (self.zoom / 2).max(1);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.failed {
+            return html! { <p class="text-muted small">{ t("waveform-failed") }</p> };
+        }
+        if self.peaks.is_none() {
+            return html! { <p class="text-muted small">{ t("waveform-loading") }</p> };
+        }
+
+        html! {
+            <div class="mb-2">
+                <canvas
+                    ref={ self.canvas.clone() }
+                    class="w-100 border rounded"
+                    height={ HEIGHT.to_string() }
+                    style="cursor: pointer;"
+                    title={ t("waveform-seek") }
+                    onclick={ ctx.link().callback(Msg::Click) }
+                ></canvas>
+                <div class="d-flex align-items-center small">
+                    <button type="button" class="btn btn-sm btn-link px-1" disabled={ self.zoom == 1 } onclick={ ctx.link().callback(|_| Msg::ZoomOut) }>
+                        { "−" }
+                    </button>
+                    <span>{ format!("{}×", self.zoom) }</span>
+                    <button type="button" class="btn btn-sm btn-link px-1" disabled={ self.zoom == MAX_ZOOM } onclick={ ctx.link().callback(|_| Msg::ZoomIn) }>
+                        { "+" }
+                    </button>
+                    <span class="ms-auto text-muted">{ t("waveform-legend") }</span>
+                </div>
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        self.draw();
+    }
+}
+
+impl Waveform {
+    fn canvas_element(&self) -> Option<HtmlCanvasElement> {
+        self.canvas.cast::<HtmlCanvasElement>()
+    }
+
+    fn draw(&self) {
+        let (Some(canvas), Some(peaks)) = (self.canvas_element(), &self.peaks) else {
+            return;
+        };
+        // Drawn with one column per pixel of the shown width, so the waveform stays sharp.
+        let width = canvas.client_width().max(1) as u32;
+        if canvas.width() != width {
+            canvas.set_width(width);
+        }
+        let Some(context) = canvas
+            .get_context("2d")
+            .ok()
+            .flatten()
+            .and_then(|context| context.dyn_into::<CanvasRenderingContext2d>().ok())
+        else {
+            return;
+        };
+
+        let height = HEIGHT as f64;
+        let middle = height / 2.0;
+        let (start, end) = visible_range(peaks.duration(), self.zoom, self.playhead);
+        context.clear_rect(0.0, 0.0, width as f64, height);
+
+        for (x, peak) in peaks.columns(start, end, width as usize).iter().enumerate() {
+            let x = x as f64;
+            if peak.is_silent() {
+                context.set_fill_style_str(COLOR_SILENCE);
+                context.fill_rect(x, 0.0, 1.0, height);
+            }
+            context.set_fill_style_str(if peak.is_clipped() {
+                COLOR_CLIPPED
+            } else {
+                COLOR_WAVE
+            });
+            let top = middle - peak.max.clamp(-1.0, 1.0) as f64 * middle;
+            let bottom = middle - peak.min.clamp(-1.0, 1.0) as f64 * middle;
+            context.fill_rect(x, top, 1.0, (bottom - top).max(1.0));
+        }
+
+        if end > start {
+            let x = (self.playhead - start) / (end - start) * width as f64;
+            context.set_fill_style_str(COLOR_PLAYHEAD);
+            context.fill_rect(x.floor(), 0.0, 2.0, height);
+        }
+    }
+}
+
+/// The part of the audio shown at the zoom, following the playhead when zoomed in.
+fn visible_range(duration: f64, zoom: u32, playhead: f64) -> (f64, f64) {
+    let length = duration / zoom.max(1) as f64;
+    let start = (playhead - length / 2.0).clamp(0.0, (duration - length).max(0.0));
+    (start, start + length)
+}
+
+#[cfg(test)]
+mod waveform_test {
+    use super::visible_range;
+
+    #[test]
+    fn whole_audio_is_shown_without_zoom() {
+        assert_eq!(visible_range(120.0, 1, 50.0), (0.0, 120.0));
+    }
+
+    #[test]
+    fn zoomed_range_follows_playhead() {
+        assert_eq!(visible_range(120.0, 4, 60.0), (45.0, 75.0));
+        assert_eq!(visible_range(120.0, 4, 5.0), (0.0, 30.0));
+        assert_eq!(visible_range(120.0, 4, 118.0), (90.0, 120.0));
+    }
+}
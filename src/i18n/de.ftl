@@ -0,0 +1,475 @@
+# Deutsche Texte des Abgabeportals
+
+# Sprachen
+locale-de = Deutsch
+locale-en = English
+locale-select = Sprache
+
+# Startseite
+home-authentication-failed = Fehler! Authentifizierung nicht erfolgreich. Versuche Weiterleitung ins Chorportal.
+home-logged-in-as = Angemeldet als:
+home-section = Stimme:
+home-id = Id:
+home-back-to-portal = Zurück zum Chorportal
+home-new-project = Neues Projekt
+home-import-projects = Projekte importieren
+home-export-calendar = Fristen in den Kalender
+home-projects-load-failed = Die Projekte konnten nicht geladen werden! Details siehe Konsole.
+home-project-create-failed = Konnte Projekt nicht erstellen! Details ggfs. siehe Konsole.
+
+# Projektseite
+project-load-failed = Das Projekt konnte nicht geladen werden! Details siehe Konsole.
+project-submissions-load-failed = Die Abgaben konnten nicht geladen werden! Details siehe Konsole.
+project-upload-failed = Beim Hochladen ist ein Fehler aufgetreten! Versuche es erneut und wende dich dann an den/die Administrator*in.
+project-redirecting = Leite um ins Chorportal zur Authorisierung... Bitte gehe ins Chorportal, falls du nicht automatisch weiter geleitet wirst.
+project-back = Zurück
+project-download-submissions = Abgaben downloaden
+project-other-choir = Dieses Projekt gehört zu einem anderen Chor.
+project-switch-choir = Zu { $choir } wechseln
+project-data = Projektdaten
+project-id = Id:
+project-owner-id = Besitzer-Id:
+project-due = Abgabe bis:
+project-add-to-calendar = Zum Kalender hinzufügen
+project-section-deadlines = Fristen der Stimmen:
+project-section-deadline-default = Alle anderen: { $date }
+project-section-deadline = { $section }: { $date }
+project-created = Erstellt:
+project-result = Ergebnis ansehen
+project-draft-hidden = Dieses Projekt wird noch vorbereitet und ist noch nicht sichtbar.
+project-upload-new-file = Neue Datei hochladen
+project-my-submissions = Meine Abgaben
+project-all-submissions = Alle Abgaben
+project-submission-trash = Gelöschte Abgaben
+
+# Fortschritt
+progress-loaded = { $loaded } von { $total } geladen
+
+# Restzeit
+relative-left = noch { $duration }
+relative-overdue = überfällig seit { $duration }
+relative-less-than-minute-left = noch weniger als eine Minute
+relative-just-overdue = gerade überfällig
+relative-day-one = { $count } Tag
+relative-day-other = { $count } Tage
+relative-hour-one = { $count } Stunde
+relative-hour-other = { $count } Stunden
+relative-minute-one = { $count } Minute
+relative-minute-other = { $count } Minuten
+
+# Fristen
+deadline-policy-open = Weiterhin annehmen, als verspätet markieren
+deadline-policy-until-due = Nicht mehr annehmen
+deadline-policy-grace-period = Während einer Nachfrist annehmen
+deadline-policy-locked = Abgaben gesperrt
+deadline-locked = Für dieses Projekt werden keine Abgaben angenommen.
+deadline-closed = Die Abgabefrist ist am { $date } abgelaufen, es werden keine Abgaben mehr angenommen.
+deadline-passed-late = Die Abgabefrist ist abgelaufen, neue Abgaben werden als verspätet markiert.
+deadline-closes = Abgaben sind noch bis { $date } möglich.
+
+# Projektstatus
+project-state-draft = Entwurf
+project-state-open = Offen
+project-state-closed = Geschlossen
+project-state-published = Veröffentlicht
+project-state-archived = Archiviert
+project-state-action-draft = Zum Entwurf machen
+project-state-action-open = Öffnen
+project-state-action-closed = Schließen
+project-state-action-published = Veröffentlichen
+project-state-action-archived = Archivieren
+project-state-not-open = Status des Projekts: { $state }. Es werden keine Abgaben angenommen.
+
+# Laden
+loading = Lade...
+loading-animation = Lade-Animation
+
+# Dialoge
+modal-cancel = Abbrechen
+modal-delete = Löschen
+
+# Projektlisten
+project-list-already-deleted = Das Projekt wurde bereits gelöscht!
+project-list-delete-failed = Das Projekt konnte nicht gelöscht werden! Details siehe Konsole.
+project-list-none-selected = Fehler: kein ausgewähltes Projekt gefunden. Bitte erneut versuchen.
+project-list-update-failed = Das Projekt konnte nicht angepasst werden. Überprüfe Deine Internetverbindung, versuche es erneut und wende dich sonst an den/die Administrator*in. Details siehe Konsole.
+project-list-state-failed = Der Status des Projekts konnte nicht geändert werden. Details siehe Konsole.
+project-list-result-url = Link zum Ergebnis (z. B. dem Video), leer lassen falls es keinen gibt:
+project-list-pending = Ausstehende Projekte
+project-list-all = Alle Projekte
+project-list-mine = Meine Projekte
+project-list-awaiting-deletion = Auf Löschung warten: { $count } Projekt(e)
+project-list-show = Anzeigen
+project-list-deleted-title = Gelöschte Projekte
+project-list-deleted-explanation = Gelöschte Projekte werden noch eine Weile aufbewahrt und können bis dahin wiederhergestellt werden. Endgültiges Löschen entfernt ein Projekt mit allen Dateien.
+project-list-deleted-projects = Projekte:
+project-list-delete-title = Projekt löschen
+project-list-delete-warning = Warnung!
+project-list-delete-irreversible = Das Projekt kann bis zum endgültigen Löschen aus den gelöschten Projekten wiederhergestellt werden.
+project-list-delete-submissions = Alle Abgaben werden unwiderruflich gelöscht!
+project-list-delete-confirm = Das Projekt „{ $title }“ wirklich löschen?
+project-list-piece = Stück
+project-list-due = Abgabe bis
+project-list-edit = Bearbeiten
+project-list-empty = Es gibt keine Projekte in dieser Kategorie.
+project-list-delete = Löschen
+project-list-duplicate = Duplizieren
+project-list-save-template = Als Vorlage speichern
+project-list-template-name = Name der Vorlage:
+project-list-template-saved = Die Vorlage „{ $name }“ wurde gespeichert.
+project-list-template-failed = Die Vorlage konnte nicht gespeichert werden! Details siehe Konsole.
+
+# Projektdialoge
+project-modal-title-missing = Titel fehlt!
+project-modal-title = Projekt erstellen
+project-modal-create = Erstellen
+project-modal-name = Name des Projektes
+project-modal-due = Abgabedatum
+project-modal-time-zone = Zeitzone
+project-modal-deadline-policy = Abgaben nach der Frist
+project-modal-grace-period = Nachfrist in Stunden
+project-modal-grace-period-invalid = Die Nachfrist muss eine ganze Zahl von Stunden sein!
+project-modal-section-deadlines = Abweichende Fristen einzelner Stimmen
+project-modal-section-deadline-add = Frist für eine Stimme hinzufügen
+project-modal-section-deadline-remove = Entfernen
+project-modal-draft = Als Entwurf anlegen (nur für dich sichtbar)
+project-modal-description = Beschreibung
+project-modal-template = Vorlage verwenden
+project-modal-template-none = Keine Vorlage
+project-modal-template-delete = Vorlage löschen
+project-modal-template-delete-confirm = Die Vorlage „{ $name }“ wirklich löschen?
+project-modal-template-delete-failed = Die Vorlage konnte nicht gelöscht werden! Details siehe Konsole.
+project-modal-start = Projektbeginn (Fristen werden entsprechend verschoben)
+project-modal-copy-material = Übungsmaterial kopieren
+project-modal-material-copy-failed = Das Projekt wurde erstellt, aber das Übungsmaterial konnte nicht kopiert werden! Details siehe Konsole.
+
+# Projektimport
+project-import-title = Projekte importieren
+project-import-format = Wähle eine CSV-Datei mit den Spalten title, description, due, time_zone und due:<Stimme> (z. B. due:Bass1) für Fristen einzelner Stimmen oder eine JSON-Datei mit einer Liste von Projekten mit denselben Feldern. Daten werden wie 2026-10-18 20:00 in der Zeitzone des Projekts angegeben.
+project-import-row = Zeile
+project-import-problems = Probleme
+project-import-valid = { $valid } von { $total } Projekten können erstellt werden.
+project-import-start = { $count } Projekte erstellen
+project-import-summary = { $created } Projekte erstellt, { $failed } fehlgeschlagen.
+project-import-failure = Zeile { $row } ({ $title }): { $error }
+project-import-missing-title = Der Titel fehlt.
+project-import-missing-due = Das Abgabedatum fehlt.
+project-import-invalid-date = „{ $date }“ ist kein gültiges Datum.
+project-import-unknown-section = Unbekannte Stimme „{ $section }“.
+project-import-unknown-time-zone = Unbekannte Zeitzone „{ $zone }“.
+project-import-csv-failed = Die CSV-Datei konnte nicht gelesen werden: { $error }
+project-import-json-failed = Die JSON-Datei konnte nicht gelesen werden: { $error }
+project-import-unknown-column = Unbekannte Spalte „{ $column }“.
+project-import-empty = Die Datei enthält keine Projekte.
+
+# Abgaben
+submission-kind-audio = Audio
+submission-kind-video = Video
+submission-kind-document = Dokument
+submission-kind-other = Sonstiges
+submission-note = Anmerkungen
+submission-note-placeholder = z.B. Takt 15 bitte rausschneiden...
+submission-section = Stimme
+submission-kind = Art
+submission-creator = Ersteller*in
+submission-creator-placeholder = Name des/der Ersteller*in der Abgabe
+
+# Abgabedetails
+submission-details-file-name = Dateiname:
+submission-details-id = Id:
+submission-details-uploaded = Hochgeladen:
+submission-details-author-id = Autor (Id):
+submission-details-author-name = Autor (Name):
+submission-details-playback = Playback (Id):
+submission-details-sync-offset = Versatz zum Playback:
+submission-details-sync-offset-value = { $offset } ms
+submission-details-submitter = Eingereicht von:
+submission-details-preview = Vorschau:
+submission-details-quality = Qualität:
+submission-details-alignment = Versatz zur Referenz:
+submission-details-no-preview = Für „Sonstiges“ kann keine Vorschau erstellt werden. Passe gegebenenfalls die Art der Abgabe über die Schaltfläche „Ändern“ an!
+
+# Abgabelisten
+submission-list-name = Name
+submission-list-comment = Kommentar
+submission-list-author = Autor*in
+submission-list-quality = Qualität
+submission-list-details = Details
+submission-list-download = Herunterladen
+submission-list-change = Ändern
+submission-list-delete = Löschen
+submission-list-empty = Keine Abgaben gefunden
+submission-list-delete-title = Abgabe löschen
+submission-list-delete-irreversible = Die Abgabe kann bis zum endgültigen Löschen vom Projektersteller wiederhergestellt werden.
+submission-list-delete-confirm = Die Abgabe „{ $file }“ wirklich löschen?
+submission-list-none-selected = Fehler! Keine Abgabe ausgewählt!
+submission-list-delete-failed = Fehler beim Löschen der Abgabe.
+submission-list-update-failed = Daten der Abgabe konnten nicht geändert werden. Details siehe Konsole.
+submission-list-late = Verspätet
+submission-list-filter = Abgaben filtern
+submission-list-filter-all = Alle Abgaben
+submission-list-filter-on-time = Pünktliche Abgaben
+submission-list-filter-late = Verspätete Abgaben
+
+# Abgabe ändern
+submission-update-title = Abgabe anpassen
+submission-update-save = Speichern
+submission-update-none-selected = Keine Abgabe zur Änderung ausgewählt. Bitte das Fenster schließen und es erneut versuchen!
+submission-update-select-failed = Bitte merke Dir die Schritte, die du ausgeführt hast. Ein Fehler ist aufgetreten, wende dich an den/die Administrator*in
+
+# Material
+material-category-audio = Audio
+material-category-video = Video
+material-category-sheet = Noten
+material-category-other = Sonstiges
+material-add = Übungsmaterial hinzufügen
+material-delete-title = Material wirklich löschen?
+material-description = Beschreibung:
+material-file-name = Dateiname:
+material-none-selected = Kein zu löschendes Element ausgewählt!
+material-upload-failed = Ein Fehler ist aufgetreten. Bitte versuche es erneut und wende dich dann an den/die Administrator*in
+material-already-deleted = Die Datei wurde bereits gelöscht!
+material-delete-failed = Die Datei konnte nicht gelöscht werden! Details siehe Konsole.
+material-load-failed = Fehler: Konnte das Material nicht laden. Überprüfe Deine Internetverbindung, lade die Seite neu und wende Dich ansonsten an den/die Administrator*in.
+material-update-failed = Konnte die neuen Daten nicht speichern! Überprüfe deine Internetverbindung, versuche es erneut und wende dich dann an den/die Administrator*in. Details siehe Konsole.
+material-change = Ändern
+material-markers = Probenmarken
+material-delete = Löschen
+material-trash = Gelöschtes Material
+
+# Audio
+material-audio-title = Playbacks
+material-audio-empty = Noch keine Audiodateien gefunden!
+
+# Noten
+material-sheet-title = Noten
+material-sheet-empty = Noch keine Noten gefunden!
+
+# Videos
+material-video-title = Videos
+material-video-empty = Noch keine Videodateien gefunden!
+
+# Alle Dateien
+material-all-title = Alle Dateien
+material-all-description = Dateibeschreibung
+material-all-link = Link
+material-all-category = Kategorie
+material-all-empty = Noch keine Dateien gefunden!
+
+# Materialdialoge
+material-upload-title = Übungsmaterial hochladen
+material-update-save = Anpassen
+material-update-title = Übungsmaterial anpassen
+material-update-none-selected = Nicht zum Update ausgewählt!
+material-input-title = Name für die Datei
+material-input-title-placeholder = Titel der Datei
+material-input-category = Art der Datei (Playback, ...)
+
+# Upload
+upload-error-details-unavailable = Noch nicht verfügbar, siehe Konsole (F12)
+upload-failed = Beim Upload ist ein Fehler aufgetreten! Bitte versuche es erneut und wende dich dann an den/die Administrator*in. Fehlermeldung: { $message }
+upload-running = Upload läuft
+upload-successful = Upload erfolgreich!
+upload-files-too-large = Dateien dürfen höchstens { $max } MB groß sein. Zu groß: { $files }
+upload-blocked-view-as = Uploads sind gesperrt, während du das Portal als anderes Mitglied ansiehst.
+upload-start = Upload starten
+
+# Aufnahme
+recorder-title = Direkt aufnehmen
+recorder-with-video = Video mit der Kamera aufnehmen
+recorder-level = Pegel des Mikrofons
+recorder-elapsed = Aufnahme: { $time }
+recorder-start = Aufnahme starten
+recorder-stop = Aufnahme beenden
+recorder-retake = Neu aufnehmen
+recorder-use = Aufnahme verwenden
+recorder-used = Die Aufnahme wurde zum Upload hinzugefügt. Prüfe die Angaben und starte den Upload.
+recorder-start-failed = Die Aufnahme konnte nicht gestartet werden! Erlaube den Zugriff auf Mikrofon (und Kamera) und versuche es erneut. Details siehe Konsole.
+recorder-stop-failed = Die Aufnahme konnte nicht beendet werden! Details siehe Konsole.
+recorder-use-failed = Die Aufnahme konnte nicht zum Upload hinzugefügt werden! Details siehe Konsole.
+recorder-playback = Mitsingen zu
+recorder-no-playback = Ohne Playback aufnehmen
+recorder-count-in = Einzähler (Schläge)
+recorder-headphones = Nutze Kopfhörer, damit das Playback nicht mit aufgenommen wird.
+recorder-latency = Latenz: { $latency } ms
+recorder-latency-unknown = Latenz: nicht gemessen
+recorder-calibrate = Latenz messen
+recorder-calibrating = Messe…
+recorder-calibration-not-heard = Die Klicks waren im Mikrofon nicht zu hören! Nimm für die Messung die Kopfhörer ab oder halte sie an das Mikrofon und versuche es erneut.
+recorder-calibration-failed = Die Latenz konnte nicht gemessen werden! Details siehe Konsole.
+
+# Mixer
+mixer-title = Alle Stimmen zusammen
+mixer-play = Abspielen
+mixer-pause = Pause
+mixer-stop = Stopp
+mixer-loading = Lade…
+mixer-position = Position
+mixer-volume = Lautstärke von { $title }
+mixer-mute = Stumm
+mixer-solo = Solo
+mixer-reset = Mischung zurücksetzen
+mixer-load-failed = Die Stimmen konnten nicht geladen werden! Details siehe Konsole.
+
+# Üben
+practice-show = Übungswerkzeuge zeigen
+practice-hide = Übungswerkzeuge ausblenden
+practice-set-start = Anfang der Schleife auf die aktuelle Stelle setzen (Taste A)
+practice-set-end = Ende der Schleife auf die aktuelle Stelle setzen (Taste B)
+practice-loop = Schleife
+practice-save-loop = Schleife speichern
+practice-loop-name = Name der Schleife, z. B. „Takt 57–64“:
+practice-delete-loop = Schleife löschen
+practice-tempo = Tempo: { $percent } %
+practice-transposition = Transposition: { $semitones } Halbtöne
+practice-transpose-failed = Die Aufnahme konnte nicht transponiert werden! Details siehe Konsole.
+practice-shortcuts = Tasten: Leertaste abspielen/pausieren, A/B Anfang/Ende der Schleife, L Schleife an/aus, ←/→ 5 Sekunden zurück/vor, −/+ langsamer/schneller, ↓/↑ einen Halbton tiefer/höher.
+
+# Probenmarken
+markers-title = Probenmarken
+markers-edit-title = Probenmarken bearbeiten
+markers-input = Eine Marke pro Zeile, Zeit und Name:
+markers-input-placeholder = 0:00 Anfang
+markers-count = { $count } Marken
+markers-import = Aus Datei übernehmen (Text oder Audacity-Textmarken):
+markers-import-help = Textmarken exportiert Audacity über „Datei → Exportieren → Textmarken exportieren“. Der Inhalt ersetzt den Text oben und wird erst mit „Speichern“ übernommen.
+markers-invalid-time = Zeile { $line }: „{ $time }“ ist keine Zeit. Schreibe z. B. „1:23 Buchstabe C“.
+markers-missing-name = Zeile { $line }: Der Name der Marke fehlt.
+markers-file-failed = Die Datei konnte nicht gelesen werden! Details siehe Konsole.
+
+# Wellenform
+waveform-loading = Wellenform wird berechnet…
+waveform-failed = Die Wellenform konnte nicht berechnet werden.
+waveform-seek = Klicken, um an diese Stelle zu springen
+waveform-legend = Rot: übersteuert, grau: Stille
+
+# Qualität
+quality-good = In Ordnung
+quality-warnings = Hinweise
+quality-problems = Probleme
+quality-check = Prüfen
+quality-running = Wird geprüft…
+quality-check-failed = Die Qualität der Abgabe konnte nicht geprüft werden. Details siehe Konsole.
+quality-checking = Die Qualität von „{ $file }“ wird geprüft…
+quality-failed = Die Qualität von „{ $file }“ konnte nicht geprüft werden. Details siehe Konsole.
+quality-upload-anyway = Die Aufnahme kann trotzdem hochgeladen werden.
+quality-measurements = Messwerte
+quality-duration = Länge
+quality-sample-rate = Abtastrate
+quality-sample-rate-value = { $rate } kHz
+quality-sample-rate-unknown = unbekannt
+quality-channels = Kanäle
+quality-peak = Spitzenpegel / True Peak
+quality-peak-value = { $peak } dBFS / { $true_peak } dBTP
+quality-clipping = Übersteuerte Stellen
+quality-loudness = Lautheit
+quality-loudness-value = { $loudness } LUFS
+quality-silence = Stille am Anfang / Ende
+quality-warning-silent = Die Aufnahme ist stumm. Ist das richtige Mikrofon ausgewählt?
+quality-warning-sample-rate = Die Aufnahme hat nur { $rate } kHz und klingt dumpf.
+quality-warning-clipping = Die Aufnahme ist an { $count } Stellen übersteuert. Weiter weg vom Mikrofon oder leiser aufnehmen.
+quality-warning-true-peak = Die Aufnahme erreicht { $peak } dBTP und kann beim Umwandeln verzerren.
+quality-warning-quiet = Die Aufnahme ist sehr leise ({ $loudness } LUFS). Näher am Mikrofon aufnehmen.
+quality-warning-long-silence = Mehr als die Hälfte der Aufnahme ist Stille am Anfang oder Ende.
+quality-warning-mono = Die Kanäle löschen sich in Mono gegenseitig aus. Wahrscheinlich ist einer verpolt.
+
+# Intonation
+pitch-open = Intonation prüfen
+pitch-title = Intonation
+pitch-local = Die Analyse läuft im Browser, es wird keine Aufnahme hochgeladen.
+pitch-reference = Vergleichen mit
+pitch-reference-file = Eigene Datei (MIDI, MusicXML oder Audio)
+pitch-part = Stimme
+pitch-offset = Versatz (ms)
+pitch-analyse = Analysieren
+pitch-running = Wird analysiert…
+pitch-failed = Die Abgabe konnte nicht analysiert werden. Details siehe Konsole.
+pitch-reference-failed = Die Referenz konnte nicht geladen werden. Details siehe Konsole.
+pitch-summary = { $share } % der Töne liegen innerhalb von ±{ $cents } Cent der Referenz, die mittlere Abweichung (Median) ist { $median } Cent.
+pitch-nothing-compared = Keine Stelle, an der Abgabe und Referenz zugleich klingen. Stimmt der Versatz?
+pitch-legend = Grau: Referenz, grün: sauber, orange: leicht daneben, rot: daneben. Oktaven zählen nicht als Abweichung.
+score-invalid-midi = Die MIDI-Datei ist fehlerhaft ({ $reason }).
+score-unsupported-timing = MIDI-Dateien mit SMPTE-Zeitangaben werden nicht unterstützt.
+score-invalid-xml = Die Datei ist kein unkomprimiertes MusicXML ({ $reason }).
+score-no-notes = Die Datei enthält keine Noten.
+
+# Ausrichtung
+alignment-reference = Ausrichten an
+alignment-align = Alle ausrichten
+alignment-running = Richte aus ({ $done } von { $total })…
+alignment-export = Versätze exportieren
+alignment-aligning = Wird ausgerichtet…
+alignment-failed = Die Abgabe konnte nicht ausgerichtet werden. Details siehe Konsole.
+alignment-not-found = Kein Versatz gefunden, die Abgabe ist vielleicht stumm.
+alignment-offset-value = Die Referenz beginnt bei { $offset } ms (Sicherheit: { $confidence } %)
+alignment-uncertain = Unsicher
+alignment-uncertain-hint = Andere Versätze passen fast genauso gut. Prüfe den Versatz nach Gehör, bevor du ihn verwendest.
+alignment-reference-failed = Die Referenz konnte nicht geladen werden. Details siehe Konsole.
+
+# Vorschau-Mix
+preview-mix-title = Vorschau-Mix
+preview-mix-hint = Alle Audio-Abgaben zusammen, nach ihrem Versatz zur Referenz ausgerichtet. Auf dem Server wird nichts verändert.
+preview-mix-gain = Lautstärke
+preview-mix-pan = Links / rechts
+preview-mix-gain-of = Lautstärke von { $name }
+preview-mix-pan-of = Position von { $name } von links nach rechts
+preview-mix-offset = (Versatz { $offset } ms)
+preview-mix-export = Als WAV exportieren
+preview-mix-exporting = Exportiere…
+preview-mix-load-failed = Die Abgaben des Mixes konnten nicht geladen werden. Details siehe Konsole.
+preview-mix-export-failed = Der Mix konnte nicht exportiert werden. Details siehe Konsole.
+
+# Als Mitglied ansehen
+view-as-open = Als Mitglied ansehen
+view-as-failed = Die Ansicht konnte nicht gewechselt werden. Details siehe Konsole.
+view-as-banner = Ansicht als { $name } (Id: { $id }; Stimme: { $section }). Änderungen sind in dieser Ansicht gesperrt.
+view-as-stop = Ansicht beenden
+view-as-id-not-a-number = Die Id muss eine Zahl sein!
+view-as-default-name = Mitglied { $id }
+view-as-confirm = Ansicht wechseln
+view-as-explanation = Das Portal wird mit den Rechten und Abgaben des Mitglieds angezeigt. Änderungen sind in dieser Ansicht gesperrt.
+view-as-id = Id
+view-as-name = Name
+
+# Kalender
+calendar-name = Fristen des Abgabeportals
+calendar-event-summary = Abgabe: { $title }
+calendar-event-description = Die Abgaben für „{ $title }“ sind fällig. Hochladen unter { $url }
+
+# Papierkorb
+trash-empty = Nichts gelöscht.
+trash-name = Name
+trash-purge-at = Endgültig gelöscht
+trash-purge-unknown = Unbekannt
+trash-restore = Wiederherstellen
+trash-purge = Endgültig löschen
+trash-purge-confirm = „{ $name }“ endgültig löschen? Kann nicht rückgängig gemacht werden!
+trash-failed = Die gelöschten Einträge konnten nicht geändert werden! Bitte die Internetverbindung prüfen, erneut versuchen und sonst den Administrator kontaktieren. Details siehe Konsole.
+
+# Chöre
+choir-page-title = { $choir } Abgabeportal
+choir-select = Chor
+
+# Anmeldung
+jwt-cookie-failed = Konnte kein Cookie setzen. Sind Cookies für diese Seite eingeschaltet? Cookies sind erforderlich, damit diese Seite funktioniert.
+
+# App
+app-config-error = Fehler beim Laden der Einstellungen
+app-loading-config = Lade Einstellungen...
+
+# Konfiguration
+config-fetch-failed = Die Konfiguration konnte nicht geladen werden: { $error }
+config-parse-failed = Die Konfiguration ist fehlerhaft (Zeile { $line }, Spalte { $column }): { $error }
+config-unsupported-version = Die Konfiguration hat Version { $version }, unterstützt wird höchstens Version { $supported }.
+config-invalid = Die Konfiguration ist ungültig: { $problems }
+config-domain-mismatch = `backend_domain` ({ $domain }) passt nicht zum Host von `backend_url` ({ $host })
+config-sections-empty = `sections` ist leer
+config-section-without-value = `sections` enthält eine Stimme ohne `value`
+config-section-duplicate = `sections` enthält die Stimme { $section } mehrfach
+config-upload-size-zero = `max_upload_size_mb` muss größer als 0 sein
+config-invalid-color = `theme.primary_color` muss eine Hex-Farbe wie #8a1538 sein, ist aber "{ $color }"
+config-locale-empty = `locale` ist leer
+config-url-scheme = muss mit http:// oder https:// beginnen, ist aber "{ $url }"
+config-url-credentials = darf keine Zugangsdaten enthalten: "{ $url }"
+config-url-port = hat einen ungültigen Port: "{ $url }"
+config-url-host = hat keinen gültigen Host: "{ $url }"
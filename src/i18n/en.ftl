@@ -0,0 +1,475 @@
+# English texts of the turnin portal
+
+# Languages
+locale-de = Deutsch
+locale-en = English
+locale-select = Language
+
+# Home page
+home-authentication-failed = Error! Authentication failed. Trying to redirect to the choir portal.
+home-logged-in-as = Logged in as:
+home-section = Voice part:
+home-id = Id:
+home-back-to-portal = Back to the choir portal
+home-new-project = New project
+home-import-projects = Import projects
+home-export-calendar = Deadlines to calendar
+home-projects-load-failed = Could not load the projects! See the console for details.
+home-project-create-failed = Could not create the project! See the console for details.
+
+# Project page
+project-load-failed = Could not load the project! See the console for details.
+project-submissions-load-failed = Could not load the submissions! See the console for details.
+project-upload-failed = An error occurred while uploading! Please try again and contact the administrator if it keeps happening.
+project-redirecting = Redirecting to the choir portal for authorization... Please go to the choir portal if you are not redirected automatically.
+project-back = Back
+project-download-submissions = Download submissions
+project-other-choir = This project belongs to another choir.
+project-switch-choir = Switch to { $choir }
+project-data = Project details
+project-id = Id:
+project-owner-id = Owner id:
+project-due = Due:
+project-add-to-calendar = Add to calendar
+project-section-deadlines = Deadlines of sections:
+project-section-deadline-default = All others: { $date }
+project-section-deadline = { $section }: { $date }
+project-created = Created:
+project-result = View the result
+project-draft-hidden = This project is still being prepared and is not visible yet.
+project-upload-new-file = Upload a new file
+project-my-submissions = My submissions
+project-all-submissions = All submissions
+project-submission-trash = Deleted submissions
+
+# Progress
+progress-loaded = { $loaded } of { $total } loaded
+
+# Remaining time
+relative-left = { $duration } left
+relative-overdue = overdue by { $duration }
+relative-less-than-minute-left = less than a minute left
+relative-just-overdue = just overdue
+relative-day-one = { $count } day
+relative-day-other = { $count } days
+relative-hour-one = { $count } hour
+relative-hour-other = { $count } hours
+relative-minute-one = { $count } minute
+relative-minute-other = { $count } minutes
+
+# Deadlines
+deadline-policy-open = Keep accepting, mark as late
+deadline-policy-until-due = Stop accepting
+deadline-policy-grace-period = Accept during a grace period
+deadline-policy-locked = Submissions locked
+deadline-locked = This project does not accept submissions.
+deadline-closed = The deadline passed on { $date }, submissions are no longer accepted.
+deadline-passed-late = The deadline has passed, new submissions are marked as late.
+deadline-closes = Submissions are possible until { $date }.
+
+# Project states
+project-state-draft = Draft
+project-state-open = Open
+project-state-closed = Closed
+project-state-published = Published
+project-state-archived = Archived
+project-state-action-draft = Turn into draft
+project-state-action-open = Open
+project-state-action-closed = Close
+project-state-action-published = Publish
+project-state-action-archived = Archive
+project-state-not-open = State of the project: { $state }. Submissions are not accepted.
+
+# Loading
+loading = Loading...
+loading-animation = Loading animation
+
+# Dialogs
+modal-cancel = Cancel
+modal-delete = Delete
+
+# Project lists
+project-list-already-deleted = The project was already deleted!
+project-list-delete-failed = Could not delete the project! See the console for details.
+project-list-none-selected = Error: no project selected. Please try again.
+project-list-update-failed = Could not change the project. Check your internet connection, try again and contact the administrator otherwise. See the console for details.
+project-list-state-failed = Could not change the state of the project. See the console for details.
+project-list-result-url = Link to the result (e.g. the video), leave empty if there is none:
+project-list-pending = Pending projects
+project-list-all = All projects
+project-list-mine = My projects
+project-list-awaiting-deletion = Awaiting deletion: { $count } project(s)
+project-list-show = Show
+project-list-deleted-title = Deleted projects
+project-list-deleted-explanation = Deleted projects are kept for a while and can be restored until then. Purging removes a project and all its files for good.
+project-list-deleted-projects = Projects:
+project-list-delete-title = Delete project
+project-list-delete-warning = Warning!
+project-list-delete-irreversible = The project can be restored from the deleted projects until it is purged.
+project-list-delete-submissions = All submissions are deleted irrevocably!
+project-list-delete-confirm = Really delete the project “{ $title }”?
+project-list-piece = Piece
+project-list-due = Due
+project-list-edit = Edit
+project-list-empty = There are no projects in this category.
+project-list-delete = Delete
+project-list-duplicate = Duplicate
+project-list-save-template = Save as template
+project-list-template-name = Name of the template:
+project-list-template-saved = Saved the template “{ $name }”.
+project-list-template-failed = Could not save the template! See the console for details.
+
+# Project dialogs
+project-modal-title-missing = The title is missing!
+project-modal-title = Create project
+project-modal-create = Create
+project-modal-name = Name of the project
+project-modal-due = Due date
+project-modal-time-zone = Time zone
+project-modal-deadline-policy = Submissions after the deadline
+project-modal-grace-period = Grace period in hours
+project-modal-grace-period-invalid = The grace period must be a whole number of hours!
+project-modal-section-deadlines = Different deadlines of single sections
+project-modal-section-deadline-add = Add deadline for a section
+project-modal-section-deadline-remove = Remove
+project-modal-draft = Create as draft (only visible to you)
+project-modal-description = Description
+project-modal-template = Start from template
+project-modal-template-none = No template
+project-modal-template-delete = Delete template
+project-modal-template-delete-confirm = Really delete the template “{ $name }”?
+project-modal-template-delete-failed = Could not delete the template! See the console for details.
+project-modal-start = Start of the project (deadlines are shifted accordingly)
+project-modal-copy-material = Copy the practice material
+project-modal-material-copy-failed = The project was created, but the practice material could not be copied! See the console for details.
+
+# Project import
+project-import-title = Import projects
+project-import-format = Choose a CSV file with the columns title, description, due, time_zone and due:<voice part> (e.g. due:Bass1) for deadlines of single voice parts, or a JSON file with a list of projects with the same fields. Dates are given like 2026-10-18 20:00, in the time zone of the project.
+project-import-row = Row
+project-import-problems = Problems
+project-import-valid = { $valid } of { $total } projects can be created.
+project-import-start = Create { $count } projects
+project-import-summary = Created { $created } projects, { $failed } failed.
+project-import-failure = Row { $row } ({ $title }): { $error }
+project-import-missing-title = The title is missing.
+project-import-missing-due = The due date is missing.
+project-import-invalid-date = “{ $date }” is not a valid date.
+project-import-unknown-section = Unknown voice part “{ $section }”.
+project-import-unknown-time-zone = Unknown time zone “{ $zone }”.
+project-import-csv-failed = The CSV file could not be read: { $error }
+project-import-json-failed = The JSON file could not be read: { $error }
+project-import-unknown-column = Unknown column “{ $column }”.
+project-import-empty = The file does not contain any projects.
+
+# Submissions
+submission-kind-audio = Audio
+submission-kind-video = Video
+submission-kind-document = Document
+submission-kind-other = Other
+submission-note = Notes
+submission-note-placeholder = e.g. please cut out bar 15...
+submission-section = Voice part
+submission-kind = Kind
+submission-creator = Creator
+submission-creator-placeholder = Name of the creator of the submission
+
+# Submission details
+submission-details-file-name = File name:
+submission-details-id = Id:
+submission-details-uploaded = Uploaded:
+submission-details-author-id = Author (id):
+submission-details-author-name = Author (name):
+submission-details-playback = Playback (id):
+submission-details-sync-offset = Offset to the playback:
+submission-details-sync-offset-value = { $offset } ms
+submission-details-submitter = Submitted by:
+submission-details-preview = Preview:
+submission-details-quality = Quality:
+submission-details-alignment = Offset to the reference:
+submission-details-no-preview = No preview can be shown for “Other”. Change the kind of the submission with the “Change” button if necessary!
+
+# Submission lists
+submission-list-name = Name
+submission-list-comment = Comment
+submission-list-author = Author
+submission-list-quality = Quality
+submission-list-details = Details
+submission-list-download = Download
+submission-list-change = Change
+submission-list-delete = Delete
+submission-list-empty = No submissions found
+submission-list-delete-title = Delete submission
+submission-list-delete-irreversible = The submission can be restored by the project owner until it is purged.
+submission-list-delete-confirm = Really delete the submission “{ $file }”?
+submission-list-none-selected = Error! No submission selected!
+submission-list-delete-failed = Could not delete the submission.
+submission-list-update-failed = Could not change the submission. See the console for details.
+submission-list-late = Late
+submission-list-filter = Filter submissions
+submission-list-filter-all = All submissions
+submission-list-filter-on-time = Submissions on time
+submission-list-filter-late = Late submissions
+
+# Change submission
+submission-update-title = Change submission
+submission-update-save = Save
+submission-update-none-selected = No submission selected to change. Please close the window and try again!
+submission-update-select-failed = Please remember the steps you took. An error occurred, please contact the administrator.
+
+# Material
+material-category-audio = Audio
+material-category-video = Video
+material-category-sheet = Sheet music
+material-category-other = Other
+material-add = Add practice material
+material-delete-title = Really delete the material?
+material-description = Description:
+material-file-name = File name:
+material-none-selected = No material selected for deletion!
+material-upload-failed = An error occurred. Please try again and contact the administrator if it keeps happening.
+material-already-deleted = The file was already deleted!
+material-delete-failed = Could not delete the file! See the console for details.
+material-load-failed = Error: could not load the material. Check your internet connection, reload the page and contact the administrator otherwise.
+material-update-failed = Could not save the changes! Check your internet connection, try again and contact the administrator otherwise. See the console for details.
+material-change = Change
+material-markers = Markers
+material-delete = Delete
+material-trash = Deleted material
+
+# Audio
+material-audio-title = Playbacks
+material-audio-empty = No audio files yet!
+
+# Sheet music
+material-sheet-title = Sheet music
+material-sheet-empty = No sheet music yet!
+
+# Videos
+material-video-title = Videos
+material-video-empty = No video files yet!
+
+# All files
+material-all-title = All files
+material-all-description = Description
+material-all-link = Link
+material-all-category = Category
+material-all-empty = No files yet!
+
+# Material dialogs
+material-upload-title = Upload practice material
+material-update-save = Save
+material-update-title = Change practice material
+material-update-none-selected = Nothing selected to change!
+material-input-title = Name of the file
+material-input-title-placeholder = Title of the file
+material-input-category = Kind of file (playback, ...)
+
+# Upload
+upload-error-details-unavailable = Not available yet, see the console (F12)
+upload-failed = An error occurred during the upload! Please try again and contact the administrator if it keeps happening. Error message: { $message }
+upload-running = Uploading
+upload-successful = Upload successful!
+upload-files-too-large = Files must not be larger than { $max } MB. Too large: { $files }
+upload-blocked-view-as = Uploads are blocked while you view the portal as another member.
+upload-start = Start upload
+
+# Recorder
+recorder-title = Record directly
+recorder-with-video = Record video with the camera
+recorder-level = Level of the microphone
+recorder-elapsed = Recording: { $time }
+recorder-start = Start recording
+recorder-stop = Stop recording
+recorder-retake = Record again
+recorder-use = Use recording
+recorder-used = The recording was added to the upload. Check the details and start the upload.
+recorder-start-failed = Could not start recording! Allow the access to the microphone (and camera) and try again. See the console for details.
+recorder-stop-failed = Could not finish the recording! See the console for details.
+recorder-use-failed = Could not add the recording to the upload! See the console for details.
+recorder-playback = Sing along to
+recorder-no-playback = Record without playback
+recorder-count-in = Count-in (beats)
+recorder-headphones = Use headphones, so the playback is not recorded as well.
+recorder-latency = Latency: { $latency } ms
+recorder-latency-unknown = Latency: not measured
+recorder-calibrate = Measure latency
+recorder-calibrating = Measuring…
+recorder-calibration-not-heard = The clicks could not be heard through the microphone! Take off the headphones or hold them to the microphone for the test and try again.
+recorder-calibration-failed = Could not measure the latency! See the console for details.
+
+# Mixer
+mixer-title = All parts together
+mixer-play = Play
+mixer-pause = Pause
+mixer-stop = Stop
+mixer-loading = Loading…
+mixer-position = Position
+mixer-volume = Volume of { $title }
+mixer-mute = Mute
+mixer-solo = Solo
+mixer-reset = Reset mix
+mixer-load-failed = Could not load the parts! See the console for details.
+
+# Practice
+practice-show = Show practice tools
+practice-hide = Hide practice tools
+practice-set-start = Set the start of the loop to the current position (key A)
+practice-set-end = Set the end of the loop to the current position (key B)
+practice-loop = Loop
+practice-save-loop = Save loop
+practice-loop-name = Name of the loop, e.g. “Bars 57–64”:
+practice-delete-loop = Delete loop
+practice-tempo = Tempo: { $percent } %
+practice-transposition = Transposition: { $semitones } semitones
+practice-transpose-failed = Could not transpose the recording! See the console for details.
+practice-shortcuts = Keys: space play/pause, A/B start/end of the loop, L loop on/off, ←/→ 5 seconds back/forward, −/+ slower/faster, ↓/↑ a semitone lower/higher.
+
+# Rehearsal markers
+markers-title = Rehearsal markers
+markers-edit-title = Edit rehearsal markers
+markers-input = One marker per line, time and name:
+markers-input-placeholder = 0:00 Beginning
+markers-count = { $count } markers
+markers-import = Take from a file (text or Audacity labels):
+markers-import-help = Audacity exports labels with “File → Export → Export Labels”. The content replaces the text above and is only kept with “Save”.
+markers-invalid-time = Line { $line }: “{ $time }” is not a time. Write e.g. “1:23 Letter C”.
+markers-missing-name = Line { $line }: The name of the marker is missing.
+markers-file-failed = Could not read the file! See the console for details.
+
+# Waveform
+waveform-loading = Computing waveform…
+waveform-failed = Could not compute the waveform.
+waveform-seek = Click to jump to this position
+waveform-legend = Red: clipped, grey: silence
+
+# Quality
+quality-good = OK
+quality-warnings = Warnings
+quality-problems = Problems
+quality-check = Check
+quality-running = Checking…
+quality-check-failed = Could not check the quality of the submission. See the console for details.
+quality-checking = Checking the quality of “{ $file }”…
+quality-failed = Could not check the quality of “{ $file }”. See the console for details.
+quality-upload-anyway = The recording can be uploaded anyway.
+quality-measurements = Measurements
+quality-duration = Duration
+quality-sample-rate = Sample rate
+quality-sample-rate-value = { $rate } kHz
+quality-sample-rate-unknown = unknown
+quality-channels = Channels
+quality-peak = Peak / true peak
+quality-peak-value = { $peak } dBFS / { $true_peak } dBTP
+quality-clipping = Clipped passages
+quality-loudness = Loudness
+quality-loudness-value = { $loudness } LUFS
+quality-silence = Silence at start / end
+quality-warning-silent = The recording is silent. Is the right microphone selected?
+quality-warning-sample-rate = The recording has only { $rate } kHz and sounds muffled.
+quality-warning-clipping = The recording is clipped in { $count } places. Record further away from the microphone or more quietly.
+quality-warning-true-peak = The recording reaches { $peak } dBTP and may distort when it is converted.
+quality-warning-quiet = The recording is very quiet ({ $loudness } LUFS). Record closer to the microphone.
+quality-warning-long-silence = More than half of the recording is silence at the start or end.
+quality-warning-mono = The channels cancel each other out in mono. One of them is probably inverted.
+
+# Intonation
+pitch-open = Check intonation
+pitch-title = Intonation
+pitch-local = The analysis runs in the browser, no recording is uploaded.
+pitch-reference = Compare with
+pitch-reference-file = Own file (MIDI, MusicXML or audio)
+pitch-part = Part
+pitch-offset = Offset (ms)
+pitch-analyse = Analyse
+pitch-running = Analysing…
+pitch-failed = Could not analyse the submission. See the console for details.
+pitch-reference-failed = Could not load the reference. See the console for details.
+pitch-summary = { $share } % of the notes are within ±{ $cents } cents of the reference, the median deviation is { $median } cents.
+pitch-nothing-compared = Nowhere do the submission and the reference sound at the same time. Is the offset right?
+pitch-legend = Grey: reference, green: in tune, orange: slightly off, red: off. Octaves don't count as deviation.
+score-invalid-midi = The MIDI file is broken ({ $reason }).
+score-unsupported-timing = MIDI files timed in SMPTE frames are not supported.
+score-invalid-xml = The file is not uncompressed MusicXML ({ $reason }).
+score-no-notes = The file contains no notes.
+
+# Alignment
+alignment-reference = Align to
+alignment-align = Align all
+alignment-running = Aligning ({ $done } of { $total })…
+alignment-export = Export offsets
+alignment-aligning = Aligning…
+alignment-failed = Could not align the submission. See the console for details.
+alignment-not-found = No offset found, the submission might be silent.
+alignment-offset-value = The reference starts at { $offset } ms (confidence: { $confidence } %)
+alignment-uncertain = Uncertain
+alignment-uncertain-hint = Other offsets match almost as well. Check the offset by ear before using it.
+alignment-reference-failed = Could not load the reference. See the console for details.
+
+# Preview mix
+preview-mix-title = Preview mix
+preview-mix-hint = All audio submissions together, lined up by their offsets to the reference. Nothing is changed on the server.
+preview-mix-gain = Volume
+preview-mix-pan = Left / right
+preview-mix-gain-of = Volume of { $name }
+preview-mix-pan-of = Position of { $name } from left to right
+preview-mix-offset = (offset { $offset } ms)
+preview-mix-export = Export WAV
+preview-mix-exporting = Exporting…
+preview-mix-load-failed = Could not load the submissions of the mix. See the console for details.
+preview-mix-export-failed = Could not export the mix. See the console for details.
+
+# View as member
+view-as-open = View as member
+view-as-failed = Could not switch the view. See the console for details.
+view-as-banner = Viewing as { $name } (id: { $id }; voice part: { $section }). Changes are blocked in this view.
+view-as-stop = Leave view
+view-as-id-not-a-number = The id has to be a number!
+view-as-default-name = Member { $id }
+view-as-confirm = Switch view
+view-as-explanation = The portal is shown with the rights and submissions of the member. Changes are blocked in this view.
+view-as-id = Id
+view-as-name = Name
+
+# Calendar
+calendar-name = Turnin portal deadlines
+calendar-event-summary = Deadline: { $title }
+calendar-event-description = Submissions for “{ $title }” are due. Upload them at { $url }
+
+# Trash
+trash-empty = Nothing deleted.
+trash-name = Name
+trash-purge-at = Purged
+trash-purge-unknown = Unknown
+trash-restore = Restore
+trash-purge = Purge
+trash-purge-confirm = Permanently delete "{ $name }"? This can not be undone!
+trash-failed = Could not change the deleted items! Check your internet connection, try again and contact the administrator otherwise. See the console for details.
+
+# Choirs
+choir-page-title = { $choir } turnin portal
+choir-select = Choir
+
+# Authentication
+jwt-cookie-failed = Could not set a cookie. Are cookies enabled for this page? Cookies are required for this page to work.
+
+# App
+app-config-error = Could not load the settings
+app-loading-config = Loading settings...
+
+# Configuration
+config-fetch-failed = Could not load the configuration: { $error }
+config-parse-failed = The configuration is malformed (line { $line }, column { $column }): { $error }
+config-unsupported-version = The configuration has version { $version }, but at most version { $supported } is supported.
+config-invalid = The configuration is invalid: { $problems }
+config-domain-mismatch = `backend_domain` ({ $domain }) does not match the host of `backend_url` ({ $host })
+config-sections-empty = `sections` is empty
+config-section-without-value = `sections` contains a voice part without `value`
+config-section-duplicate = `sections` contains the voice part { $section } more than once
+config-upload-size-zero = `max_upload_size_mb` has to be greater than 0
+config-invalid-color = `theme.primary_color` has to be a hex color like #8a1538, but is "{ $color }"
+config-locale-empty = `locale` is empty
+config-url-scheme = has to start with http:// or https://, but is "{ $url }"
+config-url-credentials = must not contain credentials: "{ $url }"
+config-url-port = has an invalid port: "{ $url }"
+config-url-host = has no valid host: "{ $url }"
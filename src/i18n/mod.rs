@@ -0,0 +1,272 @@
+//! Translations of all texts shown to the user.
+//!
+//! The messages are kept in one catalog per language (`de.ftl`, `en.ftl` next to this file) that is compiled into the app.
+//! The catalogs use a subset of the [Fluent](https://projectfluent.org) syntax: one `id = text` per line,
+//! indented lines continue the previous message, `#` starts a comment and `{ $name }` inserts an argument.
+
+use std::{collections::HashMap, fmt::Display, sync::OnceLock};
+
+use gloo_console::{error, warn};
+use gloo_storage::{LocalStorage, Storage};
+use gloo_utils::window;
+
+use crate::{service::CONFIG, utilities::select_enum::SelectEnum};
+
+const LOCALE_STORAGE_KEY: &str = "locale";
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+static GERMAN: OnceLock<HashMap<&'static str, String>> = OnceLock::new();
+static ENGLISH: OnceLock<HashMap<&'static str, String>> = OnceLock::new();
+
+/// A language the portal is available in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, SelectEnum)]
+pub enum Locale {
+    #[select(value = "de", label = "locale-de")]
+    German,
+    #[select(value = "en", label = "locale-en")]
+    English,
+}
+
+impl Locale {
+    /// Finds the language of a tag like `de-DE` or `en`.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        Self::try_from(language.as_str()).ok()
+    }
+
+    fn catalog(self) -> &'static HashMap<&'static str, String> {
+        match self {
+            Locale::German => GERMAN.get_or_init(|| parse_catalog(include_str!("de.ftl"))),
+            Locale::English => ENGLISH.get_or_init(|| parse_catalog(include_str!("en.ftl"))),
+        }
+    }
+}
+
+/// The language the portal is shown in: the one chosen by the user, otherwise the one of the config.
+/// Outside of the browser (in tests) it is always german.
+pub fn locale() -> Locale {
+    if let Some(locale) = LOCALE.get() {
+        return *locale;
+    }
+
+    if cfg!(not(target_arch = "wasm32")) {
+        return Locale::German;
+    }
+
+    let locale = LocalStorage::get::<String>(LOCALE_STORAGE_KEY)
+        .ok()
+        .and_then(|value| Locale::try_from(value.as_str()).ok())
+        .or_else(|| {
+            CONFIG
+                .get()
+                .and_then(|config| Locale::from_tag(&config.locale))
+        })
+        .unwrap_or(Locale::German);
+
+    // Texts of the loading screen are shown before the config is known, so the choice is only final once it is loaded.
+    if CONFIG.get().is_some() {
+        let _ = LOCALE.set(locale);
+    }
+
+    locale
+}
+
+/// Stores the language chosen by the user and reloads the page, so every text is shown in it.
+pub fn set_locale(locale: Locale) {
+    if let Err(error) = LocalStorage::set(LOCALE_STORAGE_KEY, locale.value()) {
+        error!(format!("Could not store locale: {error}"));
+        return;
+    }
+
+    if let Err(error) = window().location().reload() {
+        error!("Could not reload the page!");
+        error!(error);
+    }
+}
+
+/// The message with the given id in the language of the user.
+pub fn t(id: &str) -> String {
+    translate(locale(), id, &[])
+}
+
+/// The message with the given id in the language of the user, with its `{ $name }` placeholders replaced by the arguments.
+pub fn t_with(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    translate(locale(), id, args)
+}
+
+/// Looks the message up in the catalog of the locale. Falls back to german and then to the id if it is missing.
+pub fn translate(locale: Locale, id: &str, args: &[(&str, &dyn Display)]) -> String {
+    let message = locale
+        .catalog()
+        .get(id)
+        .or_else(|| Locale::German.catalog().get(id));
+
+    match message {
+        Some(message) => insert_args(message, args),
+        None => {
+            warn!(format!("Missing translation for {id}"));
+            id.to_string()
+        }
+    }
+}
+
+fn parse_catalog(source: &'static str) -> HashMap<&'static str, String> {
+    let mut catalog = HashMap::new();
+    let mut current: Option<(&'static str, String)> = None;
+
+    for line in source.lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            if let Some((_, text)) = &mut current {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(line.trim());
+            }
+            continue;
+        }
+
+        if let Some((id, text)) = current.take() {
+            catalog.insert(id, text);
+        }
+
+        if let Some((id, text)) = line.split_once('=') {
+            current = Some((id.trim(), text.trim().to_string()));
+        }
+    }
+
+    if let Some((id, text)) = current {
+        catalog.insert(id, text);
+    }
+
+    catalog
+}
+
+fn insert_args(message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut rest = message;
+
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+
+        result.push_str(&rest[..start]);
+
+        let placeholder = rest[start + 1..end].trim();
+        let value = placeholder
+            .strip_prefix('$')
+            .and_then(|name| args.iter().find(|(arg, _)| *arg == name));
+
+        match value {
+            Some((_, value)) => result.push_str(&value.to_string()),
+            None => result.push_str(&rest[start..=end]),
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod i18n_test {
+    use std::collections::BTreeSet;
+
+    use super::{parse_catalog, translate, Locale};
+
+    #[test]
+    fn catalogs_have_same_messages() {
+        let german = parse_catalog(include_str!("de.ftl"));
+        let english = parse_catalog(include_str!("en.ftl"));
+
+        let german_ids = german.keys().collect::<BTreeSet<_>>();
+        let english_ids = english.keys().collect::<BTreeSet<_>>();
+
+        assert_eq!(
+            german_ids.difference(&english_ids).collect::<Vec<_>>(),
+            Vec::<&&&str>::new(),
+            "missing in english"
+        );
+        assert_eq!(
+            english_ids.difference(&german_ids).collect::<Vec<_>>(),
+            Vec::<&&&str>::new(),
+            "missing in german"
+        );
+    }
+
+    #[test]
+    fn used_messages_exist() {
+        let german = parse_catalog(include_str!("de.ftl"));
+        let source_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+        let mut missing = Vec::new();
+
+        for path in source_files(source_dir.as_ref()) {
+            let source = std::fs::read_to_string(&path).unwrap();
+
+            for call in ["t(\"", "t_with(\"", "label = \""] {
+                for (index, _) in source.match_indices(call) {
+                    let preceding = source[..index].chars().next_back();
+                    if preceding.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                        continue;
+                    }
+
+                    let id_start = index + call.len();
+                    let id_end = id_start + source[id_start..].find('"').unwrap();
+                    let id = &source[id_start..id_end];
+
+                    if !german.contains_key(id) {
+                        missing.push(format!("{}: {id}", path.display()));
+                    }
+                }
+            }
+        }
+
+        assert_eq!(missing, Vec::<String>::new());
+    }
+
+    fn source_files(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files.extend(source_files(&path));
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                files.push(path);
+            }
+        }
+        files
+    }
+
+    #[test]
+    fn multiline_messages_and_comments() {
+        let catalog = parse_catalog("# comment\nfirst = one\n    two\nsecond = three\n");
+        assert_eq!(catalog["first"], "one\ntwo");
+        assert_eq!(catalog["second"], "three");
+    }
+
+    #[test]
+    fn arguments_inserted() {
+        assert_eq!(
+            translate(Locale::English, "view-as-default-name", &[("id", &7)]),
+            "Member 7"
+        );
+    }
+
+    #[test]
+    fn unknown_placeholders_kept() {
+        assert_eq!(super::insert_args("a { $b } c", &[]), "a { $b } c");
+    }
+
+    #[test]
+    fn locale_from_tag() {
+        assert_eq!(Locale::from_tag("de-DE"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("en_GB"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("fr"), None);
+    }
+}
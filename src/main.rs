@@ -1,7 +1,13 @@
 use gloo_console::{error, info, warn};
-use gloo_dialogs::alert;
-use service::{get_config, Config, CONFIG};
-use utilities::requests::fetch::FetchError;
+use gloo_utils::document;
+use i18n::{locale, t};
+use service::{
+    config::ConfigError,
+    get_config,
+    section::{load_sections, SectionDefinition, SECTIONS},
+    Config, CONFIG,
+};
+use utilities::select_enum::SelectEnum;
 use yew::{html, Component, Context, Html};
 use yew_router::prelude::*;
 
@@ -10,9 +16,13 @@ mod components;
 mod pages;
 use pages::project::ProjectComponent;
 
-use crate::components::jwt_context::JWTProvider;
+use crate::components::{
+    choir::ChoirHeader, jwt_context::JWTProvider, locale_select::LocaleSelect,
+    view_as::ViewAsBanner,
+};
 use pages::home::Home;
 
+mod i18n;
 mod service;
 mod utilities;
 
@@ -27,12 +37,13 @@ pub enum Route {
 }
 
 pub enum AppWrapperMsg {
-    LoadedConfig(Config),
-    LoadConfigError(FetchError),
+    LoadedConfig(Box<Config>, Vec<SectionDefinition>),
+    LoadConfigError(ConfigError),
 }
 
 pub struct AppWrapper {
     content_loaded: bool,
+    config_error: Option<String>,
 }
 
 impl Component for AppWrapper {
@@ -42,14 +53,15 @@ impl Component for AppWrapper {
     fn create(_ctx: &Context<Self>) -> Self {
         Self {
             content_loaded: false,
+            config_error: None,
         }
     }
 
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            AppWrapperMsg::LoadedConfig(config) => {
+            AppWrapperMsg::LoadedConfig(config, sections) => {
                 info!(&format!("config loaded: {:#?}", config));
-                match CONFIG.set(config.clone()) {
+                match CONFIG.set(*config.clone()) {
                     Ok(()) => {
                         info!("Set config")
                     }
@@ -60,14 +72,23 @@ impl Component for AppWrapper {
                         ));
                     }
                 }
+                if let Some(root) = document().document_element() {
+                    if let Err(error) = root.set_attribute("lang", &locale().value()) {
+                        warn!("Could not set the language of the page");
+                        warn!(error);
+                    }
+                }
+                if SECTIONS.set(sections).is_err() {
+                    warn!("Sections were used before they were loaded, keeping the previous ones");
+                }
                 self.content_loaded = true;
 
                 true
             }
             AppWrapperMsg::LoadConfigError(error) => {
                 error!("Could not load config!");
-                error!(error.to_string());
-                alert("Fehler: Die Konfiguration konnte nicht geladen werden.");
+                error!(format!("{error:?}"));
+                self.config_error = Some(error.to_string());
                 true
             }
         }
@@ -79,6 +100,19 @@ impl Component for AppWrapper {
             html! {
                 <App/>
             }
+        } else if let Some(config_error) = &self.config_error {
+            html! {
+                <div class="container">
+                    <div class="row mt-2">
+                        <div class="col">
+                            <div class="alert alert-danger">
+                                <h4>{ t("app-config-error") }</h4>
+                                <p class="mb-0">{ config_error }</p>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+            }
         } else {
             info!("Rendering config not loaded");
             html! {
@@ -86,9 +120,9 @@ impl Component for AppWrapper {
                     <div class="row mt-2">
                         <div class="col text-center">
                             <h3>
-                            { "Lade Einstellungen..." }
+                            { t("app-loading-config") }
                             <div class="spinner-border text-danger" style="width: 3rem; height: 3rem;" role="status">
-                            <span class="visually-hidden">{ "Lade-Animation" } </span>
+                            <span class="visually-hidden">{ t("loading-animation") } </span>
                             </div>
                             </h3>
                         </div>
@@ -104,7 +138,10 @@ impl Component for AppWrapper {
                 let config = get_config().await;
 
                 match config {
-                    Ok(config) => AppWrapperMsg::LoadedConfig(config),
+                    Ok(config) => {
+                        let sections = load_sections(&config).await;
+                        AppWrapperMsg::LoadedConfig(Box::new(config), sections)
+                    }
                     Err(error) => AppWrapperMsg::LoadConfigError(error),
                 }
             });
@@ -132,6 +169,9 @@ impl Component for App {
         html! {
             <JWTProvider>
             <BrowserRouter>
+                <ViewAsBanner/>
+                <LocaleSelect/>
+                <ChoirHeader/>
                 <main>
                         <Switch<Route> render={switch} />
                 </main>
@@ -1,16 +1,23 @@
 use crate::{
     components::{
+        admin_only::AdminOnly,
         jwt_context::get_token_data,
         project::{
+            calendar::download_calendar,
+            import::{ProjectImportModal, MODAL_IMPORT_PROJECTS},
             list::ProjectLists,
             modals::{ProjectCreateModal, MODAL_NEW_PROJECT},
+            viewer_due,
         },
+        view_as::{is_viewing_as, ViewAsModal, MODAL_VIEW_AS},
     },
+    i18n::t,
     service::{
         project::{
             get_all_projects, get_deleted_projects, get_my_projects, get_pending_projects,
-            ProjectTo,
+            ProjectState, ProjectTo,
         },
+        template::{get_templates, ProjectTemplate, TemplateContent},
         CONFIG,
     },
     utilities::{date::now, requests::fetch::FetchError},
@@ -28,6 +35,9 @@ pub struct Home {
     my_projects: Option<Vec<ProjectTo>>,
     all_projects: Option<Vec<ProjectTo>>,
     deleted_projects: Option<Vec<ProjectTo>>,
+    templates: Vec<ProjectTemplate>,
+    /// Content of a duplicated project for the modal of new projects.
+    prefill: Option<TemplateContent>,
 }
 
 pub enum Msg {
@@ -40,6 +50,14 @@ pub enum Msg {
     CreateProjectFail(FetchError),
     ProjectDeleted(i64),
     ProjectChanged(ProjectTo),
+    ProjectRestored(ProjectTo),
+    ProjectPurged(i64),
+    ProjectDuplicate(ProjectTo),
+    TemplatesLoaded(Vec<ProjectTemplate>),
+    TemplatesLoadError(FetchError),
+    TemplateSaved(ProjectTemplate),
+    TemplateDeleted(i64),
+    ExportCalendar,
 }
 
 impl Component for Home {
@@ -52,6 +70,8 @@ impl Component for Home {
             my_projects: None,
             all_projects: None,
             deleted_projects: None,
+            templates: Vec::new(),
+            prefill: None,
         }
     }
 
@@ -62,23 +82,29 @@ impl Component for Home {
             Ok(user) => user,
             Err(_error) => {
                 return html! {
-                    { "Fehler! Authentifizierung nicht erfolgreich. Versuche Weiterleitung ins Chorportal." }
+                    { t("home-authentication-failed") }
                 }
             }
         };
 
         let chorportal_url = CONFIG.get().unwrap().auth_website.clone();
+        let view_as_enabled = CONFIG.get().unwrap().features.view_as;
 
         html! {
             <>
             <div class="container">
                 <div class="row mt-2">
                     <div class="col">
-                        { "Angemeldet als: " }
+                        { t("home-logged-in-as") }
+                        { " " }
                         <i>{ &user.name }</i>
-                        { "; Stimme: " }
+                        { "; " }
+                        { t("home-section") }
+                        { " " }
                         <i>{ user.section }</i>
-                        { "; Id: " }
+                        { "; " }
+                        { t("home-id") }
+                        { " " }
                         <i>{ user.user_id }</i>
                     </div>
                 </div>
@@ -86,11 +112,20 @@ impl Component for Home {
                 <div class="row mt-2">
                     <div class="col">
                         <a href={chorportal_url}>
-                        <button class="btn btn-outline-danger">{ "Zurück zum Chorportal" }</button>
+                        <button class="btn btn-outline-danger">{ t("home-back-to-portal") }</button>
                         </a>
                     </div>
                     <div class="col text-end">
-                        <button class="btn btn-outline-danger" data-bs-toggle="modal" data-bs-target={format!("#{MODAL_NEW_PROJECT}")}>{ "Neues Projekt" }</button>
+                        if view_as_enabled {
+                            <AdminOnly>
+                                <button class="btn btn-outline-secondary me-2" data-bs-toggle="modal" data-bs-target={format!("#{MODAL_VIEW_AS}")}>{ t("view-as-open") }</button>
+                            </AdminOnly>
+                        }
+                        <button class="btn btn-outline-secondary me-2" onclick={ ctx.link().callback(|_| Msg::ExportCalendar) }>{ t("home-export-calendar") }</button>
+                        <AdminOnly>
+                            <button class="btn btn-outline-danger me-2" disabled={ is_viewing_as() } data-bs-toggle="modal" data-bs-target={format!("#{MODAL_IMPORT_PROJECTS}")}>{ t("home-import-projects") }</button>
+                        </AdminOnly>
+                        <button class="btn btn-outline-danger" disabled={ is_viewing_as() } data-bs-toggle="modal" data-bs-target={format!("#{MODAL_NEW_PROJECT}")}>{ t("home-new-project") }</button>
                     </div>
                 </div>
                 <ProjectLists
@@ -100,12 +135,27 @@ impl Component for Home {
                     deleted_projects={self.deleted_projects.clone()}
                     project_delete={ ctx.link().callback(Msg::ProjectDeleted) }
                     project_change={ ctx.link().callback(Msg::ProjectChanged)}
+                    project_restore={ ctx.link().callback(Msg::ProjectRestored) }
+                    project_purge={ ctx.link().callback(Msg::ProjectPurged) }
+                    project_duplicate={ ctx.link().callback(Msg::ProjectDuplicate) }
+                    template_saved={ ctx.link().callback(Msg::TemplateSaved) }
                 />
             </div>
             <ProjectCreateModal
                 on_success={ctx.link().callback(Msg::CreateProjectSuccess)}
                 on_error={ctx.link().callback(Msg::CreateProjectFail)}
+                templates={ self.templates.clone() }
+                prefill={ self.prefill.clone() }
+                on_template_deleted={ ctx.link().callback(Msg::TemplateDeleted) }
             />
+            <AdminOnly>
+                <ProjectImportModal on_created={ ctx.link().callback(Msg::CreateProjectSuccess) }/>
+            </AdminOnly>
+            if view_as_enabled {
+                <AdminOnly>
+                    <ViewAsModal/>
+                </AdminOnly>
+            }
 
             </>
         }
@@ -134,6 +184,13 @@ impl Component for Home {
                 }
             });
 
+            ctx.link().send_future(async {
+                match get_templates().await {
+                    Ok(templates) => Msg::TemplatesLoaded(templates),
+                    Err(error) => Msg::TemplatesLoadError(error),
+                }
+            });
+
             let user = match get_token_data() {
                 Ok(data) => data,
                 Err(_) => {
@@ -152,46 +209,51 @@ impl Component for Home {
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::PendingProjectsLoaded(mut projects) => {
+                remove_other_choirs(&mut projects);
                 sort_projects(&mut projects);
                 self.pending_projects = Some(projects);
                 true
             }
             Msg::AllProjectsLoaded(mut projects) => {
+                remove_other_choirs(&mut projects);
                 sort_projects(&mut projects);
                 self.all_projects = Some(projects);
                 true
             }
             Msg::MyProjectsLoaded(mut projects) => {
+                remove_other_choirs(&mut projects);
                 sort_projects(&mut projects);
                 self.my_projects = Some(projects);
                 true
             }
             Msg::DeletedProjectsLoaded(mut projects) => {
+                remove_other_choirs(&mut projects);
                 sort_projects(&mut projects);
                 self.deleted_projects = Some(projects);
                 true
             }
             Msg::ProjectsLoadError(error) => {
                 log_fetch_error(error);
-                alert("Could not download list!");
+                alert(&t("home-projects-load-failed"));
                 true
             }
             Msg::CreateProjectSuccess(project) => {
-                if project.due > now() {
+                if accepts_submissions(&project) {
                     add_and_sort(&mut self.pending_projects, &project);
                 }
 
                 add_and_sort(&mut self.my_projects, &project);
                 add_and_sort(&mut self.all_projects, &project);
+                self.prefill = None;
 
                 true
             }
             Msg::CreateProjectFail(error) => {
                 log_fetch_error(error);
-                alert("Konnte Projekt nicht erstellen! Details ggfs. siehe Konsole.");
+                alert(&t("home-project-create-failed"));
                 false
             }
             Msg::ProjectDeleted(project_id) => {
@@ -199,12 +261,75 @@ impl Component for Home {
                 delete_project(&mut self.my_projects, project_id);
                 delete_project(&mut self.all_projects, project_id);
 
+                ctx.link().send_future(async {
+                    match get_deleted_projects().await {
+                        Ok(projects) => Msg::DeletedProjectsLoaded(projects),
+                        Err(error) => Msg::ProjectsLoadError(error),
+                    }
+                });
+
+                true
+            }
+            Msg::ProjectRestored(project) => {
+                delete_project(&mut self.deleted_projects, project.id);
+
+                if accepts_submissions(&project) {
+                    add_and_sort(&mut self.pending_projects, &project);
+                }
+
+                let user_id = get_token_data().map(|user| user.user_id);
+                if user_id == Ok(project.creator) {
+                    add_and_sort(&mut self.my_projects, &project);
+                }
+                add_and_sort(&mut self.all_projects, &project);
+
+                true
+            }
+            Msg::ProjectPurged(project_id) => {
+                delete_project(&mut self.deleted_projects, project_id);
+                true
+            }
+            Msg::ProjectDuplicate(project) => {
+                self.prefill = Some(TemplateContent::from_project(&project));
+                true
+            }
+            Msg::TemplatesLoaded(mut templates) => {
+                templates.sort_by(|a, b| a.name.cmp(&b.name));
+                self.templates = templates;
                 true
             }
+            Msg::TemplatesLoadError(error) => {
+                // Projects can be created without templates, so the user is not bothered.
+                log_fetch_error(error);
+                false
+            }
+            Msg::TemplateSaved(template) => {
+                self.templates.push(template);
+                self.templates.sort_by(|a, b| a.name.cmp(&b.name));
+                true
+            }
+            Msg::TemplateDeleted(template_id) => {
+                self.templates.retain(|template| template.id != template_id);
+                true
+            }
+            Msg::ExportCalendar => {
+                let mut projects: Vec<ProjectTo> = Vec::new();
+                let lists = [&self.pending_projects, &self.my_projects];
+                let my_choir = my_choir();
+                for project in lists.into_iter().flatten().flatten() {
+                    if !project.belongs_to_other_choir(my_choir)
+                        && !projects.iter().any(|known| known.id == project.id)
+                    {
+                        projects.push(project.clone());
+                    }
+                }
+                download_calendar("deadlines.ics", &projects);
+                false
+            }
             Msg::ProjectChanged(project) => {
                 if let Some(projects) = &mut self.pending_projects {
                     projects.retain(|x| x.id != project.id);
-                    if project.due > now() {
+                    if accepts_submissions(&project) {
                         projects.push(project.clone());
                         sort_projects(projects);
                     }
@@ -241,8 +366,25 @@ fn add_and_sort(vec: &mut Option<Vec<ProjectTo>>, project: &ProjectTo) {
     }
 }
 
+/// Whether the project belongs in the list of pending projects.
+fn accepts_submissions(project: &ProjectTo) -> bool {
+    project.state == ProjectState::Open && viewer_due(project) > now()
+}
+
+/// The choir the viewer is signed in for, if they sing in several.
+fn my_choir() -> Option<i64> {
+    get_token_data().ok().and_then(|user| user.choir_id)
+}
+
+/// Members of several choirs only see the projects of the choir they are signed in for.
+fn remove_other_choirs(projects: &mut Vec<ProjectTo>) {
+    let my_choir = my_choir();
+    projects.retain(|project| !project.belongs_to_other_choir(my_choir));
+}
+
+/// Sorts by the deadline of the section of the viewer, so everyone sees their own next deadline first.
 fn sort_projects(projects: &mut [ProjectTo]) {
-    projects.sort_unstable_by(|p1, p2| p1.due.cmp(&p2.due).then(p1.title.cmp(&p2.title)));
+    projects.sort_by_cached_key(|project| (viewer_due(project), project.title.clone()));
 }
 
 fn log_fetch_error(error: FetchError) {
@@ -256,6 +398,9 @@ fn log_fetch_error(error: FetchError) {
             error!(serde_error.to_string());
         }
         FetchError::StatusCode(status) => error!("Got status code {} from request!", status),
+        FetchError::ReadOnly => {
+            error!("Changes are blocked while viewing the portal as another member!")
+        }
     }
 }
 
@@ -3,29 +3,42 @@ use std::path::PathBuf;
 use crate::{
     components::{
         admin_only::AdminOrOwner,
+        countdown::Countdown,
         iframe::IFrame,
-        jwt_context::get_token_data,
+        jwt_context::{get_token_data, switch_choir},
         loading_spinner::LoadingSpinner,
         material::Material,
+        project::calendar::download_calendar,
+        recorder::{Playback, Recorder},
         submission::{
-            list::SubmissionList, InputSubmissionCreatorName, InputSubmissionKind,
-            InputSubmissionNote, InputSubmissionSection,
+            list::SubmissionList, quality::QualityCheck, InputSubmissionCreatorName,
+            InputSubmissionKind, InputSubmissionNote, InputSubmissionSection,
         },
+        trash::{TrashItem, TrashList},
         upload::Upload,
     },
+    i18n::{t, t_with},
     service::{
-        project::{all_submissions_link, project_data, submission_upload_url, ProjectTo},
+        material::{material_by_project, material_url, MaterialCategory, MaterialTo},
+        project::{
+            all_submissions_link, project_data, submission_upload_url, ProjectState, ProjectTo,
+        },
         submission::{
+            deleted_submissions_by_project, purge_submission, restore_submission,
             submissions_by_project, submissions_by_project_and_user, Submission, SubmissionKind,
         },
     },
-    utilities::{date::format_datetime_human_readable, requests::fetch::FetchError},
+    utilities::{
+        date::{
+            format_datetime_human_readable, format_datetime_in_time_zone, local_time_zone, now,
+        },
+        requests::fetch::FetchError,
+    },
 };
 
-use gloo_utils::document;
-use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use wasm_bindgen::UnwrapThrowExt;
 
-use web_sys::{HtmlInputElement, HtmlSelectElement, InputEvent};
+use web_sys::{File, HtmlInputElement, InputEvent};
 use yew::{html, Component, Properties, TargetCast};
 
 use gloo_console::error;
@@ -42,13 +55,34 @@ pub enum Msg {
     SubmissionDeleted(i64),
     SubmissionUpdated(Submission),
     SubmissionFileInput(InputEvent),
+    SubmissionRecorded((SubmissionKind, File)),
+    PlaybacksLoaded(Vec<MaterialTo>),
+    PlaybacksLoadError(FetchError),
+    Trash(TrashMessage),
+}
+
+pub enum TrashMessage {
+    Loaded(Vec<Submission>),
+    Restore(i64),
+    Restored(Submission),
+    Purge(i64),
+    Purged(i64),
+    Error(FetchError),
 }
 
 pub struct ProjectComponent {
     project_data: Option<ProjectTo>,
     all_submissions: Option<Vec<Submission>>,
     my_submissions: Vec<Submission>,
+    /// Deleted submissions that can be restored. Only loaded for the owner of the project and admins.
+    deleted_submissions: Option<Vec<Submission>>,
     selected_submission_kind: SubmissionKind,
+    /// Audio material members can sing along to while recording.
+    playbacks: Vec<Playback>,
+    /// Files about to be uploaded, checked for problems of the recording.
+    selected_files: Vec<File>,
+    /// Whether the file in the upload form is the one recorded on the page.
+    recording_attached: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Properties)]
@@ -66,7 +100,11 @@ impl Component for ProjectComponent {
             project_data: None,
             all_submissions: None,
             my_submissions: Vec::new(),
+            deleted_submissions: None,
             selected_submission_kind: SubmissionKind::Other,
+            playbacks: Vec::new(),
+            selected_files: Vec::new(),
+            recording_attached: false,
         }
     }
 
@@ -80,6 +118,22 @@ impl Component for ProjectComponent {
 
                 let project_id = metadata.id;
 
+                if metadata.belongs_to_other_choir(user.choir_id) {
+                    // Nothing of the project is loaded until the user switched to its choir.
+                    self.project_data = Some(metadata);
+                    return true;
+                }
+
+                let user_id = user.user_id;
+                ctx.link().send_future(async move {
+                    let submissions = submissions_by_project_and_user(project_id, user_id).await;
+
+                    match submissions {
+                        Ok(contributions) => Msg::MySubmissionsLoaded(contributions),
+                        Err(error) => Msg::SubmissionsLoadError(error),
+                    }
+                });
+
                 if user.is_admin || user.user_id == metadata.creator {
                     ctx.link().send_future(async move {
                         let submissions = submissions_by_project(project_id).await;
@@ -89,14 +143,22 @@ impl Component for ProjectComponent {
                             Err(error) => Msg::SubmissionsLoadError(error),
                         }
                     });
+                    load_deleted_submissions(ctx, project_id);
                 }
 
+                ctx.link().send_future(async move {
+                    match material_by_project(project_id).await {
+                        Ok(material) => Msg::PlaybacksLoaded(material),
+                        Err(error) => Msg::PlaybacksLoadError(error),
+                    }
+                });
+
                 self.project_data = Some(metadata);
 
                 true
             }
             Msg::MetadataLoadError(error) => {
-                alert("Could not download data!");
+                alert(&t("project-load-failed"));
                 match error {
                     FetchError::JsError(js_error) => error!(js_error),
                     FetchError::WrongContentType => {
@@ -109,6 +171,9 @@ impl Component for ProjectComponent {
                     FetchError::StatusCode(status) => {
                         error!("Got status {} while downloading metadata", status);
                     }
+                    FetchError::ReadOnly => {
+                        error!("Changes are blocked while viewing the portal as another member!");
+                    }
                 }
                 true
             }
@@ -125,7 +190,7 @@ impl Component for ProjectComponent {
             }
             Msg::SubmissionsLoadError(error) => {
                 gloo_console::error!(format!("{:?}", error));
-                alert("Could not get submissions! For more info see the console log.");
+                alert(&t("project-submissions-load-failed"));
 
                 false
             }
@@ -134,8 +199,55 @@ impl Component for ProjectComponent {
                 if let Some(all_submissions) = &mut self.all_submissions {
                     all_submissions.retain(|submission| submission.id != id);
                 }
+                if self.deleted_submissions.is_some() {
+                    load_deleted_submissions(ctx, ctx.props().id);
+                }
                 true
             }
+            Msg::Trash(message) => match message {
+                TrashMessage::Loaded(submissions) => {
+                    self.deleted_submissions = Some(submissions);
+                    true
+                }
+                TrashMessage::Restore(submission_id) => {
+                    ctx.link().send_future(async move {
+                        match restore_submission(submission_id).await {
+                            Ok(submission) => Msg::Trash(TrashMessage::Restored(submission)),
+                            Err(error) => Msg::Trash(TrashMessage::Error(error)),
+                        }
+                    });
+                    false
+                }
+                TrashMessage::Restored(submission) => {
+                    if let Some(deleted) = &mut self.deleted_submissions {
+                        deleted.retain(|x| x.id != submission.id);
+                    }
+                    ctx.link().send_message(Msg::SubmissionUpdated(submission));
+                    true
+                }
+                TrashMessage::Purge(submission_id) => {
+                    ctx.link().send_future(async move {
+                        match purge_submission(submission_id).await {
+                            Ok(()) => Msg::Trash(TrashMessage::Purged(submission_id)),
+                            Err(error) => Msg::Trash(TrashMessage::Error(error)),
+                        }
+                    });
+                    false
+                }
+                TrashMessage::Purged(submission_id) => {
+                    if let Some(deleted) = &mut self.deleted_submissions {
+                        deleted.retain(|x| x.id != submission_id);
+                    }
+                    true
+                }
+                TrashMessage::Error(error) => {
+                    error!(format!(
+                        "Could not change the trash of the submissions: {error}"
+                    ));
+                    alert(&t("trash-failed"));
+                    false
+                }
+            },
             Msg::SubmissionUploaded(text) => {
                 let submission: Submission = serde_json::from_str(&text).unwrap_throw();
                 if let Some(submissions) = &mut self.all_submissions {
@@ -143,10 +255,12 @@ impl Component for ProjectComponent {
                 }
                 self.my_submissions.push(submission);
                 self.sort_submissions();
+                self.selected_files.clear();
+                self.recording_attached = false;
                 true
             }
             Msg::SubmissionUploadError(response_text) => {
-                alert("Beim hochladen ist ein Fehler aufgetreten! Versuche es erneut und wende dich dann an den/die Administrator*in");
+                alert(&t("project-upload-failed"));
                 error!(format!(
                     "Error while uploading submission! Response text: {}",
                     response_text
@@ -176,39 +290,50 @@ impl Component for ProjectComponent {
             Msg::SubmissionFileInput(change) => {
                 let target: HtmlInputElement = change.target_dyn_into().unwrap_throw();
                 let files = target.files().unwrap_throw();
+                self.selected_files = (0..files.length())
+                    .filter_map(|index| files.item(index))
+                    .collect();
+                self.recording_attached = false;
 
-                if files.length() < 1 {
-                    return false;
-                }
-
-                let file = files.item(0).unwrap_throw();
+                let Some(file) = self.selected_files.first() else {
+                    return true;
+                };
                 let name = file.name();
 
                 let path = PathBuf::from(name);
                 let ext = path.extension();
 
-                let mut kind = ext.and_then(|x| x.to_str().map(SubmissionKind::from));
-
-                if matches!(kind, Some(SubmissionKind::Other)) {
-                    kind = None;
-                }
+                let kind = ext
+                    .and_then(|x| x.to_str())
+                    .and_then(SubmissionKind::from_extension);
 
                 if let Some(kind) = kind {
                     self.selected_submission_kind = kind;
-                    let element: HtmlSelectElement = document()
-                        .get_element_by_id("selectSubmissionKind")
-                        .unwrap_throw()
-                        .dyn_into()
-                        .unwrap_throw();
-                    element.set_selected_index(match kind {
-                        SubmissionKind::Video => 0,
-                        SubmissionKind::Audio => 1,
-                        SubmissionKind::Document => 2,
-                        SubmissionKind::Other => 3,
-                    });
-                    return true;
                 }
 
+                true
+            }
+            Msg::SubmissionRecorded((kind, file)) => {
+                self.selected_submission_kind = kind;
+                self.selected_files = vec![file];
+                self.recording_attached = true;
+                true
+            }
+            Msg::PlaybacksLoaded(material) => {
+                self.playbacks = material
+                    .into_iter()
+                    .filter(|material| material.category == MaterialCategory::Audio)
+                    .map(|material| Playback {
+                        material_id: material.id,
+                        url: material_url(material.project_id, &material.file_technical_name),
+                        title: material.title,
+                    })
+                    .collect();
+                true
+            }
+            Msg::PlaybacksLoadError(error) => {
+                // The material list shows its own error, recording without playback still works.
+                error!(format!("Could not load playbacks: {error}"));
                 false
             }
         }
@@ -218,55 +343,119 @@ impl Component for ProjectComponent {
         let token_data = get_token_data();
 
         let token_data = match token_data {
-            Err(_) => {
-                return html! { "Leite um ins Chorportal zur Authorisierung... Bitte gehe ins Chorportal, falls du nicht automatisch weiter geleitet wirst." }
-            }
+            Err(_) => return html! { t("project-redirecting") },
             Ok(data) => data,
         };
 
         let my_section = token_data.section;
         let my_name = token_data.name;
+        let my_choir = token_data.choir_id;
+        let my_choirs = token_data.choirs;
 
         match &self.project_data {
-            Some(metadata) => html! {
+            Some(metadata) if !metadata.is_visible_to(token_data.user_id, token_data.is_admin) => {
+                html! {
+                    <div class="alert alert-secondary mt-2">{ t("project-draft-hidden") }</div>
+                }
+            }
+            Some(metadata) if metadata.belongs_to_other_choir(my_choir) => {
+                html! {
+                    <div class="alert alert-warning mt-2">
+                        { t("project-other-choir") }
+                        if let Some(choir) = my_choirs.iter().find(|choir| Some(choir.id) == metadata.choir_id) {
+                            { " " }
+                            <button type="button" class="btn btn-sm btn-warning" onclick={ let id = choir.id; move |_| switch_choir(id) }>
+                                { t_with("project-switch-choir", &[("choir", &choir.name)]) }
+                            </button>
+                        }
+                    </div>
+                }
+            }
+            Some(metadata) => {
+                let my_due = metadata.due_for(&my_section);
+                let calendar_project = metadata.clone();
+                let upload_closed_reason = metadata.upload_closed_reason(&my_section, now());
+                let uploads_close_at = metadata.uploads_close_at(&my_section);
+
+                html! {
                 <>
                 <div class="row mt-2">
                     <div class="col-auto">
-                        <a href="/"> <button type="button" class="btn btn-outline-danger"> { "Zurück" } </button></a>
+                        <a href="/"> <button type="button" class="btn btn-outline-danger"> { t("project-back") } </button></a>
                     </div>
                     <AdminOrOwner owner_id={ metadata.creator }>
                         <div class="col">
                             <a href={ all_submissions_link(metadata.id) }>
-                            <button class="btn btn-danger">{ "Abgaben downloaden" } </button>
+                            <button class="btn btn-danger">{ t("project-download-submissions") } </button>
                             </a>
                         </div>
                     </AdminOrOwner>
                 </div>
                 <div class="row mt-2">
                     <div class="col">
-                        <h1>{ &metadata.title }</h1>
+                        <h1>
+                            { &metadata.title }
+                            { " " }
+                            <span class={ metadata.state.css_class() }>{ metadata.state }</span>
+                        </h1>
+                        if let (ProjectState::Published | ProjectState::Archived, Some(result_url)) = (metadata.state, &metadata.result_url) {
+                            <p>
+                                <a href={ result_url.clone() } target="_blank" class="btn btn-danger">{ t("project-result") }</a>
+                            </p>
+                        }
                         <IFrame content={metadata.description.clone()}/>
                     </div>
                 </div>
                 <div class="row mt-2">
                     <div class="col">
                         <h4>
-                            { "Projektdaten" }
+                            { t("project-data") }
                         </h4>
                         <table class="table">
                             <tr>
-                            <td>{ "Id: " } </td><td> { metadata.id }</td>
+                            <td>{ t("project-id") } </td><td> { metadata.id }</td>
                             </tr>
                             <tr>
-                            <td>{ "Besitzer-Id: " } </td>
+                            <td>{ t("project-owner-id") } </td>
                             <td> { metadata.creator }</td>
                             </tr>
                             <tr>
-                            <td>{ "Abgabe bis: " } </td>
-                            <th> { format_datetime_human_readable(&metadata.due) } </th>
+                            <td>{ t("project-due") } </td>
+                            <th>
+                                { format_datetime_human_readable(&my_due) }
+                                { " " }
+                                <Countdown due={ my_due }/>
+                                if let Some(zone) = metadata.due_time_zone.as_ref().filter(|zone| **zone != local_time_zone()) {
+                                    <br/>
+                                    <small class="text-muted">{ format_datetime_in_time_zone(&my_due, zone) }</small>
+                                }
+                                <br/>
+                                <button class="btn btn-sm btn-outline-secondary mt-1" onclick={ move |_| download_calendar(&format!("project-{}.ics", calendar_project.id), std::slice::from_ref(&calendar_project)) }>
+                                    { t("project-add-to-calendar") }
+                                </button>
+                            </th>
                             </tr>
+                            if !metadata.deadline_rules.section_deadlines.is_empty() {
+                                <tr>
+                                <td>{ t("project-section-deadlines") } </td>
+                                <td>
+                                    { t_with("project-section-deadline-default", &[("date", &format_datetime_human_readable(&metadata.due))]) }
+                                    {
+                                        for metadata.deadline_rules.section_deadlines.iter().map(|deadline| html! {
+                                            <>
+                                                <br/>
+                                                { t_with("project-section-deadline", &[
+                                                    ("section", &deadline.section),
+                                                    ("date", &format_datetime_human_readable(&deadline.due)),
+                                                ]) }
+                                            </>
+                                        })
+                                    }
+                                </td>
+                                </tr>
+                            }
                             <tr>
-                            <td>{ "Erstellt: " } </td>
+                            <td>{ t("project-created") } </td>
                             <td> { format_datetime_human_readable(&metadata.created_at) } </td>
                             </tr>
                         </table>
@@ -275,9 +464,16 @@ impl Component for ProjectComponent {
 
                 <Material id={ctx.props().id} project_owner={metadata.creator}/>
 
+                if metadata.state != ProjectState::Open {
+                <div class="row mt-2">
+                    <div class="col">
+                        <div class="alert alert-secondary">{ t_with("project-state-not-open", &[("state", &metadata.state)]) }</div>
+                    </div>
+                </div>
+                } else {
                 <div class="row mt-2">
                     <div class="col">
-                        <h4>{ "Neue Datei hochladen" }</h4>
+                        <h4>{ t("project-upload-new-file") }</h4>
                         <form id="inputSubmissionUpload" class="" name="formMaterial" enctype="multipart/form-data">
                             <div class="row">
                                 <div class="col">
@@ -290,7 +486,32 @@ impl Component for ProjectComponent {
                                     <InputSubmissionKind id={ "selectSubmissionKind".to_string() } selected={ self.selected_submission_kind }/>
                                 </div>
                                 <div class="col-auto">
-                                    <InputSubmissionSection id={ "selectSubmissionSection".to_string() } selected={ crate::service::submission::Section::from(my_section) }/>
+                                    <InputSubmissionSection id={ "selectSubmissionSection".to_string() } selected={ my_section }/>
+                                </div>
+                            </div>
+                            if my_due < now() && upload_closed_reason.is_none() {
+                                <div class="row mt-2">
+                                    <div class="col">
+                                        <div class="alert alert-warning mb-0">
+                                            { t("deadline-passed-late") }
+                                            if let Some(closes) = uploads_close_at {
+                                                { " " }
+                                                { t_with("deadline-closes", &[("date", &format_datetime_human_readable(&closes))]) }
+                                            }
+                                        </div>
+                                    </div>
+                                </div>
+                            }
+                            <div class="row mt-2">
+                                <div class="col">
+                                    <Recorder
+                                        form_id="inputSubmissionUpload"
+                                        field_name="file"
+                                        on_recorded={ ctx.link().callback(Msg::SubmissionRecorded) }
+                                        disabled={ upload_closed_reason.is_some() }
+                                        playbacks={ self.playbacks.clone() }
+                                        attached={ self.recording_attached }
+                                    />
                                 </div>
                             </div>
                             <div class="row mt-2">
@@ -302,15 +523,18 @@ impl Component for ProjectComponent {
                                         multiple=true success_callback={ ctx.link().callback(Msg::SubmissionUploaded) }
                                         failure_callback={ ctx.link().callback(Msg::SubmissionUploadError) }
                                         input_callback={ ctx.link().callback(Msg::SubmissionFileInput) }
+                                        disabled_reason={ upload_closed_reason }
                                     />
+                                    <QualityCheck files={ self.selected_files.clone() } />
                                 </div>
                             </div>
                         </form>
                     </div>
                 </div>
+                }
                 <div class="row mt-2">
                     <div class="col">
-                        <h4>{ "Meine Abgaben" }</h4>
+                        <h4>{ t("project-my-submissions") }</h4>
                     </div>
                 </div>
                 <div class="row mt-2">
@@ -318,6 +542,7 @@ impl Component for ProjectComponent {
                         <SubmissionList
                             id="mySubmissionsList"
                             submissions={ self.my_submissions.clone() }
+                            project={ metadata.clone() }
                             submission_delete={ ctx.link().callback(Msg::SubmissionDeleted) }
                             submission_update={ ctx.link().callback(Msg::SubmissionUpdated) }
                         />
@@ -326,7 +551,7 @@ impl Component for ProjectComponent {
                 if let Some(all_submissions) = &self.all_submissions {
                     <div class="row mt-2">
                         <div class="col">
-                            <h4>{ "Alle Abgaben" }</h4>
+                            <h4>{ t("project-all-submissions") }</h4>
                         </div>
                     </div>
                     <div class="row mt-2">
@@ -334,14 +559,38 @@ impl Component for ProjectComponent {
                             <SubmissionList
                             id="allSubmissionsList"
                             submissions={ all_submissions.clone() }
+                            project={ metadata.clone() }
+                            references={ self.playbacks.clone() }
+                            preview_mix=true
                             submission_delete={ ctx.link().callback(Msg::SubmissionDeleted) }
                             submission_update={ ctx.link().callback(Msg::SubmissionUpdated) }
                         />
                         </div>
                     </div>
                 }
+                if let Some(deleted_submissions) = &self.deleted_submissions {
+                    <div class="row mt-2">
+                        <div class="col">
+                            <h4>{ t("project-submission-trash") }</h4>
+                        </div>
+                    </div>
+                    <div class="row mt-2">
+                        <div class="col">
+                            <TrashList
+                                items={ deleted_submissions.iter().map(|submission| TrashItem {
+                                    id: submission.id,
+                                    name: format!("{} ({})", submission.file_name, submission.creator_name),
+                                    purge_at: submission.purge_at,
+                                }).collect::<Vec<_>>() }
+                                on_restore={ ctx.link().callback(|id| Msg::Trash(TrashMessage::Restore(id))) }
+                                on_purge={ ctx.link().callback(|id| Msg::Trash(TrashMessage::Purge(id))) }
+                            />
+                        </div>
+                    </div>
+                }
                 </>
-            },
+                }
+            }
             None => html! {
                 <LoadingSpinner />
             },
@@ -351,22 +600,6 @@ impl Component for ProjectComponent {
     fn rendered(&mut self, ctx: &yew::Context<Self>, first_render: bool) {
         if first_render {
             load_data(ctx);
-
-            let project_id = ctx.props().id;
-
-            let user = match get_token_data() {
-                Ok(data) => data,
-                Err(_) => return,
-            };
-
-            ctx.link().send_future(async move {
-                let submissions = submissions_by_project_and_user(project_id, user.user_id).await;
-
-                match submissions {
-                    Ok(contributions) => Msg::MySubmissionsLoaded(contributions),
-                    Err(error) => Msg::SubmissionsLoadError(error),
-                }
-            });
         }
     }
 }
@@ -386,6 +619,15 @@ impl ProjectComponent {
     }
 }
 
+fn load_deleted_submissions(ctx: &yew::Context<ProjectComponent>, project_id: i64) {
+    ctx.link().send_future(async move {
+        match deleted_submissions_by_project(project_id).await {
+            Ok(submissions) => Msg::Trash(TrashMessage::Loaded(submissions)),
+            Err(error) => Msg::Trash(TrashMessage::Error(error)),
+        }
+    });
+}
+
 fn load_data(ctx: &yew::Context<ProjectComponent>) {
     let project_id = ctx.props().id;
     ctx.link().send_future(async move {
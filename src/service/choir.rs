@@ -0,0 +1,21 @@
+use serde::Deserialize;
+
+use crate::utilities::requests::fetch::{get_request_struct, FetchError};
+
+use super::backend;
+
+/// Appearance of a choir, loaded at runtime so every choir of the deployment can look like itself.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct ChoirBranding {
+    pub name: String,
+    /// Color of buttons and highlights as css hex color, e.g. `#8a1538`.
+    #[serde(default)]
+    pub primary_color: Option<String>,
+    #[serde(default)]
+    pub logo_url: Option<String>,
+}
+
+pub async fn get_choir_branding(choir_id: i64) -> Result<ChoirBranding, FetchError> {
+    let backend_url = backend();
+    get_request_struct(&format!("{backend_url}/choirs/{choir_id}/branding")).await
+}
@@ -1,29 +1,32 @@
-use std::fmt::Display;
-
 use serde::{Deserialize, Serialize};
-use time::PrimitiveDateTime;
-use yew::{html, ToHtml};
+use time::OffsetDateTime;
 
 use crate::{
-    service::backend,
+    service::{backend, in_choir},
     utilities::requests::fetch::{
         delete_request, get_request_struct, post_request_struct, FetchError,
     },
+    utilities::select_enum::SelectEnum,
 };
 
 pub fn material_url(project_id: i64, file_technical_name: &str) -> String {
     let backend_url = backend();
-    format!("{backend_url}/materials/{project_id}/{file_technical_name}")
+    in_choir(format!(
+        "{backend_url}/materials/{project_id}/{file_technical_name}"
+    ))
 }
 
 pub fn material_upload_url(project_id: i64) -> String {
     let backend_url = backend();
-    format!("{backend_url}/projects/{project_id}/material")
+    in_choir(format!("{backend_url}/projects/{project_id}/material"))
 }
 
 pub async fn material_by_project(project_id: i64) -> Result<Vec<MaterialTo>, FetchError> {
     let backend_url = backend();
-    get_request_struct(&format!("{backend_url}/projects/{project_id}/material")).await
+    get_request_struct(&in_choir(format!(
+        "{backend_url}/projects/{project_id}/material"
+    )))
+    .await
 }
 
 pub async fn update_material(
@@ -40,6 +43,63 @@ pub async fn delete_material(material_id: i64) -> Result<(), FetchError> {
     delete_request(&url).await
 }
 
+/// Deleted material of the project that can still be restored.
+pub async fn deleted_material_by_project(project_id: i64) -> Result<Vec<MaterialTo>, FetchError> {
+    let backend_url = backend();
+    get_request_struct(&in_choir(format!(
+        "{backend_url}/projects/{project_id}/deletedMaterial"
+    )))
+    .await
+}
+
+pub async fn restore_material(material_id: i64) -> Result<MaterialTo, FetchError> {
+    let backend_url = backend();
+    post_request_struct(
+        &format!("{backend_url}/materials/{material_id}/restore"),
+        (),
+    )
+    .await
+}
+
+/// Removes deleted material and its file for good.
+pub async fn purge_material(material_id: i64) -> Result<(), FetchError> {
+    let backend_url = backend();
+    delete_request(&format!("{backend_url}/materials/{material_id}/purge")).await
+}
+
+/// Replaces the rehearsal markers of audio or video material.
+pub async fn update_markers(
+    material_id: i64,
+    markers: Vec<Marker>,
+) -> Result<MaterialTo, FetchError> {
+    let backend_url = backend();
+    post_request_struct(
+        &format!("{backend_url}/materials/{material_id}/markers"),
+        UpdateMarkers { markers },
+    )
+    .await
+}
+
+/// Copies all material of one project into another one, e.g. the click tracks of a duplicated project.
+pub async fn copy_material(
+    from_project_id: i64,
+    to_project_id: i64,
+) -> Result<Vec<MaterialTo>, FetchError> {
+    let backend_url = backend();
+    post_request_struct(
+        &in_choir(format!(
+            "{backend_url}/projects/{to_project_id}/material/copy"
+        )),
+        CopyMaterial { from_project_id },
+    )
+    .await
+}
+
+#[derive(Clone, Serialize)]
+pub struct CopyMaterial {
+    pub from_project_id: i64,
+}
+
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct MaterialTo {
     pub id: i64,
@@ -48,55 +108,76 @@ pub struct MaterialTo {
     pub file_name: String,
     pub file_technical_name: String,
     pub creator: i64,
-    pub upload_at: PrimitiveDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub upload_at: OffsetDateTime,
     pub category: MaterialCategory,
+    /// When deleted material is removed for good. Only set for deleted material.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub purge_at: Option<OffsetDateTime>,
+    /// Named positions in audio or video material, e.g. "Letter C", sorted by position.
+    #[serde(default)]
+    pub markers: Vec<Marker>,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize, Eq, PartialOrd, Ord)]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct Marker {
+    pub position_ms: i64,
+    pub name: String,
+}
+
+#[derive(Clone, Serialize)]
+pub struct UpdateMarkers {
+    pub markers: Vec<Marker>,
+}
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Serialize, Deserialize, Eq, PartialOrd, Ord, SelectEnum,
+)]
 pub enum MaterialCategory {
+    #[select(value = "Audio", label = "material-category-audio", alias = "audio")]
     Audio,
+    #[select(value = "Video", label = "material-category-video", alias = "video")]
     Video,
+    #[select(
+        value = "Sheet",
+        label = "material-category-sheet",
+        alias = "sheet",
+        alias = "sheetMusic",
+        alias = "sheetmusic"
+    )]
     SheetMusic,
+    #[select(value = "Other", label = "material-category-other", alias = "other")]
     Other,
 }
 
-impl TryFrom<&str> for MaterialCategory {
-    fn try_from(string: &str) -> Result<Self, Self::Error> {
-        let result = match string {
-            "Audio" | "audio" => MaterialCategory::Audio,
-            "Video" | "video" => MaterialCategory::Video,
-            "Sheet" | "sheet" | "sheetMusic" | "sheetmusic" => MaterialCategory::SheetMusic,
-            "Other" | "other" => MaterialCategory::Other,
-            _ => return Err(()),
-        };
-
-        Ok(result)
-    }
-
-    type Error = ();
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Deserialize, Serialize)]
+pub struct UpdateMaterial {
+    pub title: String,
+    pub category: MaterialCategory,
 }
 
-impl Display for MaterialCategory {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let category = match self {
-            MaterialCategory::Audio => "Audio",
-            MaterialCategory::Video => "Video",
-            MaterialCategory::SheetMusic => "Noten",
-            MaterialCategory::Other => "Sonstiges",
-        };
+#[cfg(test)]
+mod material_category_test {
+    use crate::utilities::select_enum::SelectEnum;
 
-        write!(f, "{}", category)
-    }
-}
+    use super::MaterialCategory;
 
-impl ToHtml for MaterialCategory {
-    fn to_html(&self) -> yew::Html {
-        html!(self)
+    #[test]
+    fn value_round_trip() {
+        for category in MaterialCategory::options() {
+            assert_eq!(
+                MaterialCategory::try_from(category.value().as_str()),
+                Ok(category)
+            );
+        }
     }
-}
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Deserialize, Serialize)]
-pub struct UpdateMaterial {
-    pub title: String,
-    pub category: MaterialCategory,
+    #[test]
+    fn aliases_accepted() {
+        assert_eq!(
+            MaterialCategory::try_from("sheetMusic"),
+            Ok(MaterialCategory::SheetMusic)
+        );
+        assert_eq!(MaterialCategory::try_from("Noten"), Err(()));
+    }
 }
@@ -1,31 +1,46 @@
+pub mod choir;
+pub mod config;
 pub mod material;
 pub mod project;
+pub mod section;
 pub mod submission;
+pub mod template;
 
-use std::sync::OnceLock;
+pub use config::{get_config, Config, CONFIG};
 
-use gloo_console::info;
-use serde::Deserialize;
+use crate::components::jwt_context::get_token_data;
 
-use crate::utilities::requests::fetch::{get_request_struct, FetchError};
-
-pub static CONFIG: OnceLock<Config> = OnceLock::new();
+fn backend() -> String {
+    CONFIG.get().expect("Config unset").backend_url.to_owned()
+}
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct Config {
-    pub backend_domain: String,
-    pub backend_url: String,
-    /// Website the "Back" button leads to
-    pub auth_website: String,
-    /// URL that authenticates the user and leads them back to this website immediately.
-    pub auth_url: String,
+/// Adds the choir of the token to a request for data of a project, so the backend only answers within that choir.
+/// Unchanged in single choir deployments, whose tokens carry no choir.
+fn in_choir(url: String) -> String {
+    match get_token_data().ok().and_then(|user| user.choir_id) {
+        Some(choir_id) => with_choir(url, choir_id),
+        None => url,
+    }
 }
 
-pub async fn get_config() -> Result<Config, FetchError> {
-    info!("Loading config");
-    get_request_struct("/config/config.json").await
+fn with_choir(url: String, choir_id: i64) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{separator}choir={choir_id}")
 }
 
-fn backend() -> String {
-    CONFIG.get().expect("Config unset").backend_url.to_owned()
+#[cfg(test)]
+mod service_test {
+    use super::with_choir;
+
+    #[test]
+    fn choir_is_added_as_parameter() {
+        assert_eq!(
+            with_choir("https://backend/projects/1/material".to_string(), 3),
+            "https://backend/projects/1/material?choir=3"
+        );
+        assert_eq!(
+            with_choir("https://backend/projects/1?x=y".to_string(), 3),
+            "https://backend/projects/1?x=y&choir=3"
+        );
+    }
 }
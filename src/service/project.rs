@@ -1,17 +1,38 @@
 use serde::{Deserialize, Serialize};
-use time::PrimitiveDateTime;
+use time::{Duration, OffsetDateTime};
 
-use crate::utilities::requests::fetch::{
-    delete_request, get_request_struct, post_request_struct, FetchError,
+use crate::{
+    i18n::{t, t_with},
+    utilities::{
+        date::format_datetime_human_readable,
+        requests::fetch::{delete_request, get_request_struct, post_request_struct, FetchError},
+        select_enum::SelectEnum,
+    },
 };
 
-use super::backend;
+use super::{backend, in_choir, section::Section, submission::Submission};
 
 pub async fn delete_project(project_id: i64) -> Result<(), FetchError> {
     let backend_url = backend();
     delete_request(&format!("{backend_url}/projects/{project_id}")).await
 }
 
+/// Brings a deleted project back.
+pub async fn restore_project(project_id: i64) -> Result<ProjectTo, FetchError> {
+    let backend_url = backend();
+    post_request_struct::<(), ProjectTo>(
+        &format!("{backend_url}/projects/{project_id}/restore"),
+        (),
+    )
+    .await
+}
+
+/// Removes a deleted project with all its files for good.
+pub async fn purge_project(project_id: i64) -> Result<(), FetchError> {
+    let backend_url = backend();
+    delete_request(&format!("{backend_url}/projects/{project_id}/purge")).await
+}
+
 pub async fn project_data(project_id: i64) -> Result<ProjectTo, FetchError> {
     let backend_url = backend();
     get_request_struct::<ProjectTo>(&format!("{backend_url}/projects/{}", project_id)).await
@@ -19,43 +40,54 @@ pub async fn project_data(project_id: i64) -> Result<ProjectTo, FetchError> {
 
 pub fn all_submissions_link(project_id: i64) -> String {
     let backend_url = backend();
-    format!("{backend_url}/projects/{project_id}/allSubmissions")
+    in_choir(format!(
+        "{backend_url}/projects/{project_id}/allSubmissions"
+    ))
 }
 
 pub fn submission_upload_url(project_id: i64) -> String {
     let backend_url = backend();
-    format!("{backend_url}/projects/{project_id}")
+    in_choir(format!("{backend_url}/projects/{project_id}"))
 }
 
 pub async fn get_pending_projects() -> Result<Vec<ProjectTo>, FetchError> {
     let backend_url = backend();
-    get_request_struct::<Vec<ProjectTo>>(&format!("{backend_url}/projects/pending")).await
+    get_request_struct::<Vec<ProjectTo>>(&in_choir(format!("{backend_url}/projects/pending"))).await
 }
 
 pub async fn get_my_projects() -> Result<Vec<ProjectTo>, FetchError> {
     let backend_url = backend();
-    get_request_struct::<Vec<ProjectTo>>(&format!("{backend_url}/projects/myProjects")).await
+    get_request_struct::<Vec<ProjectTo>>(&in_choir(format!("{backend_url}/projects/myProjects")))
+        .await
 }
 
 pub async fn get_all_projects() -> Result<Vec<ProjectTo>, FetchError> {
     let backend_url = backend();
-    get_request_struct::<Vec<ProjectTo>>(&format!("{backend_url}/projects/all")).await
+    get_request_struct::<Vec<ProjectTo>>(&in_choir(format!("{backend_url}/projects/all"))).await
 }
 
 pub async fn get_deleted_projects() -> Result<Vec<ProjectTo>, FetchError> {
     let backend_url = backend();
-    get_request_struct::<Vec<ProjectTo>>(&format!("{backend_url}/projects/deleted")).await
+    get_request_struct::<Vec<ProjectTo>>(&in_choir(format!("{backend_url}/projects/deleted"))).await
 }
 
 pub async fn create_project(
     title: String,
     description: String,
-    due_date: PrimitiveDateTime,
+    due_date: OffsetDateTime,
+    due_time_zone: String,
+    deadline_rules: DeadlineRules,
+    state: ProjectState,
+    choir_id: Option<i64>,
 ) -> Result<ProjectTo, FetchError> {
     let body = CreateProjectBody {
         title,
         description,
         due_date,
+        due_time_zone,
+        deadline_rules,
+        state,
+        choir_id,
     };
 
     let backend_url = backend();
@@ -68,12 +100,16 @@ pub async fn update_project(
     project_id: i64,
     title: String,
     description: String,
-    due: PrimitiveDateTime,
+    due: OffsetDateTime,
+    due_time_zone: String,
+    deadline_rules: DeadlineRules,
 ) -> Result<ProjectTo, FetchError> {
     let body = UpdateProject {
         title,
         description,
         due,
+        due_time_zone,
+        deadline_rules,
     };
     let backend_url = backend();
     post_request_struct::<UpdateProject, ProjectTo>(
@@ -83,26 +119,402 @@ pub async fn update_project(
     .await
 }
 
+/// Moves the project to another state. The result is only kept for published projects.
+pub async fn change_project_state(
+    project_id: i64,
+    state: ProjectState,
+    result_url: Option<String>,
+) -> Result<ProjectTo, FetchError> {
+    let body = ChangeProjectState { state, result_url };
+    let backend_url = backend();
+    post_request_struct::<ChangeProjectState, ProjectTo>(
+        &format!("{backend_url}/projects/{project_id}/state"),
+        body,
+    )
+    .await
+}
+
 #[derive(Deserialize, PartialEq, Eq, Clone, Debug)]
 pub struct ProjectTo {
     pub id: i64,
     pub title: String,
     pub description: String,
     pub creator: i64,
-    pub created_at: PrimitiveDateTime,
-    pub due: PrimitiveDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub due: OffsetDateTime,
+    /// Time zone the deadline was set in, e.g. `Europe/Berlin`. Missing for projects created before it was stored.
+    #[serde(default)]
+    pub due_time_zone: Option<String>,
+    #[serde(flatten)]
+    pub deadline_rules: DeadlineRules,
+    /// Projects created before states were introduced are open.
+    #[serde(default)]
+    pub state: ProjectState,
+    /// Link to the final result (e.g. the video) of a published project.
+    #[serde(default)]
+    pub result_url: Option<String>,
+    /// When a deleted project is removed for good. Only set for deleted projects.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub purge_at: Option<OffsetDateTime>,
+    /// The choir the project belongs to. Missing for projects of single choir deployments.
+    #[serde(default)]
+    pub choir_id: Option<i64>,
 }
 
 #[derive(Clone, Serialize)]
 pub struct CreateProjectBody {
     pub title: String,
     pub description: String,
-    pub due_date: PrimitiveDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub due_date: OffsetDateTime,
+    pub due_time_zone: String,
+    #[serde(flatten)]
+    pub deadline_rules: DeadlineRules,
+    pub state: ProjectState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub choir_id: Option<i64>,
 }
 
 #[derive(Clone, Serialize)]
 pub struct UpdateProject {
     pub title: String,
     pub description: String,
-    pub due: PrimitiveDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub due: OffsetDateTime,
+    pub due_time_zone: String,
+    #[serde(flatten)]
+    pub deadline_rules: DeadlineRules,
+}
+
+#[derive(Clone, Serialize)]
+pub struct ChangeProjectState {
+    pub state: ProjectState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_url: Option<String>,
+}
+
+/// Stage of the lifecycle of a project.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    SelectEnum,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectState {
+    /// Materials are being prepared, only the owner (and admins) can see the project.
+    #[select(value = "draft", label = "project-state-draft")]
+    Draft,
+    /// Members can submit.
+    #[default]
+    #[select(value = "open", label = "project-state-open")]
+    Open,
+    /// Submissions are being reviewed.
+    #[select(value = "closed", label = "project-state-closed")]
+    Closed,
+    /// The final result is available.
+    #[select(value = "published", label = "project-state-published")]
+    Published,
+    #[select(value = "archived", label = "project-state-archived")]
+    Archived,
+}
+
+impl ProjectState {
+    /// The states a project in this state can be moved to by its owner or an admin.
+    pub fn transitions(self) -> &'static [ProjectState] {
+        match self {
+            ProjectState::Draft => &[ProjectState::Open],
+            ProjectState::Open => &[ProjectState::Draft, ProjectState::Closed],
+            ProjectState::Closed => &[ProjectState::Open, ProjectState::Published],
+            ProjectState::Published => &[ProjectState::Closed, ProjectState::Archived],
+            ProjectState::Archived => &[ProjectState::Published],
+        }
+    }
+
+    /// Label of the button that moves a project into this state.
+    pub fn action_label(self) -> String {
+        t(match self {
+            ProjectState::Draft => "project-state-action-draft",
+            ProjectState::Open => "project-state-action-open",
+            ProjectState::Closed => "project-state-action-closed",
+            ProjectState::Published => "project-state-action-published",
+            ProjectState::Archived => "project-state-action-archived",
+        })
+    }
+
+    /// Bootstrap class of the badge showing the state.
+    pub fn css_class(self) -> &'static str {
+        match self {
+            ProjectState::Draft => "badge bg-secondary",
+            ProjectState::Open => "badge bg-success",
+            ProjectState::Closed => "badge bg-warning text-dark",
+            ProjectState::Published => "badge bg-primary",
+            ProjectState::Archived => "badge bg-light text-dark",
+        }
+    }
+}
+
+/// How uploads are handled once the deadline of a project has passed.
+/// Projects without a policy accept uploads forever, like before deadlines were enforced.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, SelectEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadlinePolicy {
+    /// Uploads are accepted forever, late ones are marked.
+    #[default]
+    #[select(value = "open", label = "deadline-policy-open")]
+    Open,
+    #[select(value = "until_due", label = "deadline-policy-until-due")]
+    UntilDue,
+    /// Uploads are accepted until the grace period after the deadline has passed.
+    #[select(value = "grace_period", label = "deadline-policy-grace-period")]
+    GracePeriod,
+    /// No uploads are accepted at all.
+    #[select(value = "locked", label = "deadline-policy-locked")]
+    Locked,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadlineRules {
+    #[serde(default)]
+    pub deadline_policy: DeadlinePolicy,
+    /// Hours uploads are still accepted after the deadline. Only used with [DeadlinePolicy::GracePeriod].
+    #[serde(default)]
+    pub grace_period_hours: u32,
+    /// Deadlines of sections that differ from the one of the project.
+    #[serde(default)]
+    pub section_deadlines: Vec<SectionDeadline>,
+}
+
+/// Deadline of one section, e.g. if the sections record one after another.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionDeadline {
+    pub section: Section,
+    #[serde(with = "time::serde::rfc3339")]
+    pub due: OffsetDateTime,
+}
+
+impl ProjectTo {
+    /// Deadline of the section: its own one if set, otherwise the one of the project.
+    pub fn due_for(&self, section: &Section) -> OffsetDateTime {
+        self.deadline_rules
+            .section_deadlines
+            .iter()
+            .find(|deadline| deadline.section == *section)
+            .map(|deadline| deadline.due)
+            .unwrap_or(self.due)
+    }
+
+    /// Moment from which on uploads of the section are refused. [None] if they are accepted forever, locked projects are closed from the start.
+    pub fn uploads_close_at(&self, section: &Section) -> Option<OffsetDateTime> {
+        let rules = &self.deadline_rules;
+        let due = self.due_for(section);
+        match rules.deadline_policy {
+            DeadlinePolicy::Open => None,
+            DeadlinePolicy::UntilDue => Some(due),
+            DeadlinePolicy::GracePeriod => {
+                Some(due + Duration::hours(rules.grace_period_hours.into()))
+            }
+            DeadlinePolicy::Locked => Some(OffsetDateTime::UNIX_EPOCH),
+        }
+    }
+
+    /// Explains why the section can not upload anymore, [None] while it can.
+    pub fn upload_closed_reason(&self, section: &Section, now: OffsetDateTime) -> Option<String> {
+        if self.state != ProjectState::Open {
+            return Some(t_with("project-state-not-open", &[("state", &self.state)]));
+        }
+
+        match self.deadline_rules.deadline_policy {
+            DeadlinePolicy::Locked => Some(t("deadline-locked")),
+            _ => match self.uploads_close_at(section) {
+                Some(closed) if now >= closed => Some(t_with(
+                    "deadline-closed",
+                    &[("date", &format_datetime_human_readable(&closed))],
+                )),
+                _ => None,
+            },
+        }
+    }
+
+    /// Drafts are only visible to their owner and admins.
+    pub fn is_visible_to(&self, user_id: i64, is_admin: bool) -> bool {
+        self.state != ProjectState::Draft || is_admin || self.creator == user_id
+    }
+
+    /// Projects of other choirs are only shown after switching to their choir. Projects without a choir belong to all.
+    pub fn belongs_to_other_choir(&self, choir_id: Option<i64>) -> bool {
+        self.choir_id.is_some() && self.choir_id != choir_id
+    }
+
+    /// Whether the submission was uploaded after the deadline of the section of its creator.
+    pub fn is_late(&self, submission: &Submission) -> bool {
+        submission.upload_at > self.due_for(&submission.creator_section)
+    }
+}
+
+#[cfg(test)]
+mod project_test {
+    use time::macros::datetime;
+
+    use crate::service::section::Section;
+
+    use super::{DeadlinePolicy, DeadlineRules, ProjectState, ProjectTo, SectionDeadline};
+
+    fn section(value: &str) -> Section {
+        serde_json::from_str(&format!("\"{value}\"")).unwrap()
+    }
+
+    fn project(deadline_policy: DeadlinePolicy, grace_period_hours: u32) -> ProjectTo {
+        ProjectTo {
+            id: 1,
+            title: "Messiah".to_string(),
+            description: String::new(),
+            creator: 1,
+            created_at: datetime!(2026-10-01 12:00 UTC),
+            due: datetime!(2026-10-18 22:00 UTC),
+            due_time_zone: None,
+            deadline_rules: DeadlineRules {
+                deadline_policy,
+                grace_period_hours,
+                section_deadlines: vec![SectionDeadline {
+                    section: section("Bass1"),
+                    due: datetime!(2026-11-01 22:00 UTC),
+                }],
+            },
+            state: ProjectState::Open,
+            result_url: None,
+            purge_at: None,
+            choir_id: None,
+        }
+    }
+
+    #[test]
+    fn open_projects_never_close() {
+        let project = project(DeadlinePolicy::Open, 0);
+        assert_eq!(project.uploads_close_at(&section("Soprano1")), None);
+        assert_eq!(
+            project.upload_closed_reason(&section("Soprano1"), datetime!(2030-01-01 0:00 UTC)),
+            None
+        );
+    }
+
+    #[test]
+    fn grace_period_added_to_due_date() {
+        let project = project(DeadlinePolicy::GracePeriod, 48);
+        assert_eq!(
+            project.uploads_close_at(&section("Soprano1")),
+            Some(datetime!(2026-10-20 22:00 UTC))
+        );
+        assert_eq!(
+            project.upload_closed_reason(&section("Soprano1"), datetime!(2026-10-20 21:59 UTC)),
+            None
+        );
+        assert!(project
+            .upload_closed_reason(&section("Soprano1"), datetime!(2026-10-20 22:00 UTC))
+            .is_some());
+    }
+
+    #[test]
+    fn until_due_and_locked() {
+        let until_due = project(DeadlinePolicy::UntilDue, 48);
+        assert_eq!(
+            until_due.upload_closed_reason(&section("Soprano1"), datetime!(2026-10-18 21:00 UTC)),
+            None
+        );
+        assert!(until_due
+            .upload_closed_reason(&section("Soprano1"), datetime!(2026-10-18 22:00 UTC))
+            .is_some());
+
+        let locked = project(DeadlinePolicy::Locked, 0);
+        assert!(locked
+            .upload_closed_reason(&section("Soprano1"), datetime!(2026-10-01 12:00 UTC))
+            .is_some());
+    }
+
+    #[test]
+    fn section_deadline_overrides_project_deadline() {
+        let project = project(DeadlinePolicy::UntilDue, 0);
+        assert_eq!(
+            project.due_for(&section("Bass1")),
+            datetime!(2026-11-01 22:00 UTC)
+        );
+        assert_eq!(project.due_for(&section("Alto1")), project.due);
+        assert_eq!(
+            project.upload_closed_reason(&section("Bass1"), datetime!(2026-10-20 12:00 UTC)),
+            None
+        );
+        assert!(project
+            .upload_closed_reason(&section("Alto1"), datetime!(2026-10-20 12:00 UTC))
+            .is_some());
+    }
+
+    #[test]
+    fn only_open_projects_accept_uploads() {
+        let mut project = project(DeadlinePolicy::Open, 0);
+        project.state = ProjectState::Closed;
+        assert!(project
+            .upload_closed_reason(&section("Soprano1"), datetime!(2026-10-02 12:00 UTC))
+            .is_some());
+    }
+
+    #[test]
+    fn drafts_hidden_from_members() {
+        let mut project = project(DeadlinePolicy::Open, 0);
+        project.state = ProjectState::Draft;
+        assert!(project.is_visible_to(1, false));
+        assert!(project.is_visible_to(2, true));
+        assert!(!project.is_visible_to(2, false));
+    }
+
+    #[test]
+    fn transitions_can_be_undone() {
+        for state in [
+            ProjectState::Draft,
+            ProjectState::Open,
+            ProjectState::Closed,
+            ProjectState::Published,
+            ProjectState::Archived,
+        ] {
+            for next in state.transitions() {
+                assert_ne!(*next, state);
+                assert!(
+                    next.transitions().contains(&state),
+                    "{state:?} -> {next:?} can not be undone"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn projects_of_other_choirs_are_recognised() {
+        let mut project = project(DeadlinePolicy::Open, 0);
+        assert!(!project.belongs_to_other_choir(Some(2)));
+
+        project.choir_id = Some(1);
+        assert!(!project.belongs_to_other_choir(Some(1)));
+        assert!(project.belongs_to_other_choir(Some(2)));
+        assert!(project.belongs_to_other_choir(None));
+    }
+
+    #[test]
+    fn rules_default_for_old_projects() {
+        let rules: DeadlineRules = serde_json::from_str("{}").unwrap();
+        assert_eq!(rules, DeadlineRules::default());
+
+        let rules: DeadlineRules = serde_json::from_str(
+            r#"{ "deadline_policy": "grace_period", "grace_period_hours": 24 }"#,
+        )
+        .unwrap();
+        assert_eq!(rules.deadline_policy, DeadlinePolicy::GracePeriod);
+    }
 }
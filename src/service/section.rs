@@ -0,0 +1,239 @@
+use std::{cmp::Ordering, collections::HashMap, fmt::Display, sync::OnceLock};
+
+use gloo_console::{info, warn};
+use serde::{Deserialize, Serialize};
+use yew::{html, ToHtml};
+
+use crate::{
+    i18n::{locale, Locale},
+    service::Config,
+    utilities::{requests::fetch::get_request_struct, select_enum::SelectEnum},
+};
+
+/// The voice parts (or instruments) of the ensemble, in the order they are sorted in.
+/// Set once at startup by [load_sections].
+pub static SECTIONS: OnceLock<Vec<SectionDefinition>> = OnceLock::new();
+
+/// A voice part of the ensemble as it is exchanged with the backend and stored in the token, e.g. `Soprano1`.
+/// Which parts exist and how they are labelled depends on the ensemble, see [SectionDefinition].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Section(String);
+
+/// Configures a voice part: the value used by the backend and the label shown to the users.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionDefinition {
+    pub value: String,
+    pub label: String,
+    /// Translations of the label by language, e.g. `{ "en": "Soprano 1" }`.
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+impl SectionDefinition {
+    /// The label in the given language, or the untranslated one if there is no translation.
+    pub fn localized_label(&self, locale: Locale) -> &str {
+        self.labels.get(&locale.value()).unwrap_or(&self.label)
+    }
+}
+
+impl Section {
+    /// The configured label of the section in the language of the user.
+    /// Falls back to the value for sections that are not configured (anymore).
+    pub fn label(&self) -> String {
+        sections()
+            .iter()
+            .find(|definition| definition.value == self.0)
+            .map(|definition| definition.localized_label(locale()).to_string())
+            .unwrap_or_else(|| self.0.clone())
+    }
+
+    /// The first configured section, used as preselection if nothing else is known.
+    pub fn first() -> Self {
+        sections()
+            .first()
+            .map(|definition| Self(definition.value.clone()))
+            .unwrap_or_else(|| Self("Instrument".to_string()))
+    }
+
+    fn position(&self, sections: &[SectionDefinition]) -> usize {
+        sections
+            .iter()
+            .position(|definition| definition.value == self.0)
+            .unwrap_or(sections.len())
+    }
+}
+
+impl SelectEnum for Section {
+    fn options() -> Vec<Self> {
+        sections().iter().map(Section::from).collect()
+    }
+
+    fn value(&self) -> String {
+        self.0.clone()
+    }
+
+    fn label(&self) -> String {
+        Section::label(self)
+    }
+}
+
+impl From<&SectionDefinition> for Section {
+    fn from(definition: &SectionDefinition) -> Self {
+        Self(definition.value.clone())
+    }
+}
+
+impl TryFrom<&str> for Section {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        sections()
+            .iter()
+            .find(|definition| definition.value == value)
+            .map(Section::from)
+            .ok_or(())
+    }
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+impl ToHtml for Section {
+    fn to_html(&self) -> yew::Html {
+        html!(self.label())
+    }
+}
+
+/// Sections are ordered like they are configured, unknown sections are put last.
+impl Ord for Section {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_sections(self, other, sections())
+    }
+}
+
+impl PartialOrd for Section {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn compare_sections(a: &Section, b: &Section, sections: &[SectionDefinition]) -> Ordering {
+    a.position(sections)
+        .cmp(&b.position(sections))
+        .then_with(|| a.0.cmp(&b.0))
+}
+
+/// The configured sections, or the default ones before they are loaded.
+pub fn sections() -> &'static [SectionDefinition] {
+    SECTIONS.get_or_init(default_sections)
+}
+
+/// Determines the sections of the ensemble: the ones from the config if set, otherwise the ones the backend provides.
+/// Falls back to [default_sections] if the backend does not know any.
+pub async fn load_sections(config: &Config) -> Vec<SectionDefinition> {
+    if let Some(sections) = &config.sections {
+        return sections.clone();
+    }
+
+    let url = format!("{}/sections", config.backend_url);
+
+    match get_request_struct::<Vec<SectionDefinition>>(&url).await {
+        Ok(sections) if !sections.is_empty() => {
+            info!("Loaded sections from backend");
+            sections
+        }
+        Ok(_) => {
+            warn!("Backend did not provide sections, using defaults");
+            default_sections()
+        }
+        Err(error) => {
+            warn!(format!(
+                "Could not load sections from backend, using defaults. Error: {error}"
+            ));
+            default_sections()
+        }
+    }
+}
+
+/// The sections of an SSAATTBB choir with conductor and instruments.
+pub fn default_sections() -> Vec<SectionDefinition> {
+    [
+        ("Soprano1", "Sopran 1", "Soprano 1"),
+        ("Soprano2", "Sopran 2", "Soprano 2"),
+        ("Alto1", "Alt 1", "Alto 1"),
+        ("Alto2", "Alt 2", "Alto 2"),
+        ("Tenor1", "Tenor 1", "Tenor 1"),
+        ("Tenor2", "Tenor 2", "Tenor 2"),
+        ("Bass1", "Bass 1", "Bass 1"),
+        ("Bass2", "Bass 2", "Bass 2"),
+        ("Conductor", "Dirigent", "Conductor"),
+        ("Instrument", "Instrument", "Instrument"),
+    ]
+    .into_iter()
+    .map(|(value, label, english)| SectionDefinition {
+        value: value.to_string(),
+        label: label.to_string(),
+        labels: HashMap::from([(Locale::English.value(), english.to_string())]),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod section_test {
+    use std::{cmp::Ordering, collections::HashMap};
+
+    use crate::{i18n::Locale, utilities::select_enum::SelectEnum};
+
+    use super::{compare_sections, default_sections, Section, SectionDefinition};
+
+    fn satb() -> Vec<SectionDefinition> {
+        ["Soprano", "Alto", "Tenor", "Bass"]
+            .into_iter()
+            .map(|value| SectionDefinition {
+                value: value.to_string(),
+                label: value.to_string(),
+                labels: HashMap::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sorted_like_configured() {
+        let sections = satb();
+        let alto = Section("Alto".to_string());
+        let tenor = Section("Tenor".to_string());
+        assert_eq!(compare_sections(&alto, &tenor, &sections), Ordering::Less);
+        assert_eq!(
+            compare_sections(&tenor, &alto, &sections),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn unknown_sorted_last() {
+        let sections = satb();
+        let bass = Section("Bass".to_string());
+        let unknown = Section("Alto1".to_string());
+        assert_eq!(
+            compare_sections(&unknown, &bass, &sections),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn labels_translated() {
+        let conductor = &default_sections()[8];
+        assert_eq!(conductor.localized_label(Locale::German), "Dirigent");
+        assert_eq!(conductor.localized_label(Locale::English), "Conductor");
+    }
+
+    #[test]
+    fn deserialized_from_plain_value() {
+        let section: Section = serde_json::from_str("\"Tenor2\"").unwrap();
+        assert_eq!(section.value(), "Tenor2");
+    }
+}
@@ -1,17 +1,12 @@
-use std::fmt::Display;
-
 use serde::{Deserialize, Serialize};
-use time::PrimitiveDateTime;
-use yew::{html, ToHtml};
+use time::OffsetDateTime;
 
-use crate::{
-    components::jwt_context::{self},
-    utilities::requests::fetch::{
-        delete_request, get_request_struct, post_request_struct, FetchError,
-    },
+use crate::utilities::{
+    requests::fetch::{delete_request, get_request_struct, post_request_struct, FetchError},
+    select_enum::SelectEnum,
 };
 
-use super::backend;
+use super::{backend, in_choir, section::Section};
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
 pub struct Submission {
@@ -24,126 +19,44 @@ pub struct Submission {
     pub creator: i64,
     pub creator_name: String,
     pub creator_section: Section,
-    pub upload_at: PrimitiveDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub upload_at: OffsetDateTime,
     pub kind: SubmissionKind,
-}
-
-#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, PartialOrd, Eq, Ord)]
-pub enum Section {
-    Soprano1,
-    Soprano2,
-    Alto1,
-    Alto2,
-    Tenor1,
-    Tenor2,
-    Bass1,
-    Bass2,
-    Conductor,
-    Instrument,
-}
-
-impl Display for Section {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let content = match self {
-            Section::Soprano1 => "Sopran 1",
-            Section::Alto1 => "Alt 1",
-            Section::Tenor1 => "Tenor 1",
-            Section::Bass1 => "Bass 1",
-            Section::Soprano2 => "Sopran 2",
-            Section::Alto2 => "Alt 2",
-            Section::Tenor2 => "Tenor 2",
-            Section::Bass2 => "Bass 2",
-            Section::Conductor => "Dirigent",
-            Section::Instrument => "Instrument",
-        };
-
-        write!(f, "{}", content)
-    }
-}
-
-impl ToHtml for Section {
-    fn to_html(&self) -> yew::Html {
-        html!(self)
-    }
-}
-
-impl TryFrom<&str> for Section {
-    type Error = ();
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let result = match value {
-            "Soprano1" => Self::Soprano1,
-            "Alto1" => Self::Alto1,
-            "Tenor1" => Self::Tenor1,
-            "Bass1" => Self::Bass1,
-            "Soprano2" => Self::Soprano2,
-            "Alto2" => Self::Alto2,
-            "Tenor2" => Self::Tenor2,
-            "Bass2" => Self::Bass2,
-            "Conductor" => Self::Conductor,
-            "Instrument" => Self::Instrument,
-            _ => return Err(()),
-        };
-
-        Ok(result)
-    }
-}
-
-impl From<jwt_context::Section> for Section {
-    fn from(section: jwt_context::Section) -> Self {
-        match section {
-            jwt_context::Section::Soprano1 => Self::Soprano1,
-            jwt_context::Section::Alto1 => Self::Alto1,
-            jwt_context::Section::Tenor1 => Self::Tenor1,
-            jwt_context::Section::Bass1 => Self::Bass1,
-            jwt_context::Section::Soprano2 => Self::Soprano2,
-            jwt_context::Section::Alto2 => Self::Alto2,
-            jwt_context::Section::Tenor2 => Self::Tenor2,
-            jwt_context::Section::Bass2 => Self::Bass2,
-            jwt_context::Section::Conductor => Self::Conductor,
-            jwt_context::Section::Instrument => Self::Instrument,
-        }
-    }
-}
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+    /// When a deleted submission is removed for good. Only set for deleted submissions.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub purge_at: Option<OffsetDateTime>,
+    /// Material the submission was recorded along to, if it was recorded on the project page.
+    #[serde(default)]
+    pub playback_material_id: Option<i64>,
+    /// Milliseconds into the submission at which it matches the start of the playback, latency included.
+    #[serde(default)]
+    pub sync_offset_ms: Option<i64>,
+}
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, SelectEnum,
+)]
 pub enum SubmissionKind {
+    #[select(value = "audio", label = "submission-kind-audio")]
     Audio,
+    #[select(value = "video", label = "submission-kind-video")]
     Video,
+    #[select(value = "document", label = "submission-kind-document")]
     Document,
+    #[select(value = "other", label = "submission-kind-other")]
     Other,
 }
 
-impl Display for SubmissionKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let content = match self {
-            SubmissionKind::Audio => "Audio",
-            SubmissionKind::Video => "Video",
-            SubmissionKind::Document => "Dokument",
-            SubmissionKind::Other => "Sonstiges",
-        };
-
-        write!(f, "{}", content)
-    }
-}
-
-impl ToHtml for SubmissionKind {
-    fn to_html(&self) -> yew::Html {
-        html! {
-            self
-        }
-    }
-}
-
-/// mainly for matching file extensions
-impl From<&str> for SubmissionKind {
-    fn from(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "wav" | "mp3" | "flac" | "wma" | "aac" | "ogg" | "audio" => SubmissionKind::Audio,
-            "mp4" | "avi" | "mov" | "flv" | "f4v" | "swf" | "wmv" | "avchd" | "mkv" | "webm"
-            | "video" => SubmissionKind::Video,
-            "pdf" | "document" => SubmissionKind::Document,
-            _ => SubmissionKind::Other,
+impl SubmissionKind {
+    /// Guesses the kind of a submission from the extension of its file. Returns [None] for unknown extensions.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "wav" | "mp3" | "flac" | "wma" | "aac" | "ogg" => Some(SubmissionKind::Audio),
+            "mp4" | "avi" | "mov" | "flv" | "f4v" | "swf" | "wmv" | "avchd" | "mkv" | "webm" => {
+                Some(SubmissionKind::Video)
+            }
+            "pdf" => Some(SubmissionKind::Document),
+            _ => None,
         }
     }
 }
@@ -158,10 +71,9 @@ pub struct UpdateSubmission {
 
 pub async fn submissions_by_project(project_id: i64) -> Result<Vec<Submission>, FetchError> {
     let backend_url = backend();
-    get_request_struct::<Vec<Submission>>(&format!(
-        "{backend_url}/projects/{}/submissions",
-        project_id
-    ))
+    get_request_struct::<Vec<Submission>>(&in_choir(format!(
+        "{backend_url}/projects/{project_id}/submissions"
+    )))
     .await
 }
 
@@ -170,9 +82,9 @@ pub async fn submissions_by_project_and_user(
     user_id: i64,
 ) -> Result<Vec<Submission>, FetchError> {
     let backend_url = backend();
-    get_request_struct::<Vec<Submission>>(&format!(
+    get_request_struct::<Vec<Submission>>(&in_choir(format!(
         "{backend_url}/projects/{project_id}/submissions/{user_id}"
-    ))
+    )))
     .await
 }
 
@@ -183,7 +95,9 @@ pub fn submission_download_url(submission_id: i64) -> String {
 
 pub fn submission_stream_url(project_id: i64, file_technical_name: &str) -> String {
     let backend_url = backend();
-    format!("{backend_url}/submissions/stream/{project_id}/{file_technical_name}")
+    in_choir(format!(
+        "{backend_url}/submissions/stream/{project_id}/{file_technical_name}"
+    ))
 }
 
 pub async fn update_submission(
@@ -202,3 +116,29 @@ pub async fn delete_submission(submission_id: i64) -> Result<(), FetchError> {
     let backend_url = backend();
     delete_request(&format!("{backend_url}/submissions/{submission_id}")).await
 }
+
+/// Deleted submissions of the project that can still be restored.
+pub async fn deleted_submissions_by_project(
+    project_id: i64,
+) -> Result<Vec<Submission>, FetchError> {
+    let backend_url = backend();
+    get_request_struct::<Vec<Submission>>(&in_choir(format!(
+        "{backend_url}/projects/{project_id}/deletedSubmissions"
+    )))
+    .await
+}
+
+pub async fn restore_submission(submission_id: i64) -> Result<Submission, FetchError> {
+    let backend_url = backend();
+    post_request_struct(
+        &format!("{backend_url}/submissions/{submission_id}/restore"),
+        (),
+    )
+    .await
+}
+
+/// Removes a deleted submission and its file for good.
+pub async fn purge_submission(submission_id: i64) -> Result<(), FetchError> {
+    let backend_url = backend();
+    delete_request(&format!("{backend_url}/submissions/{submission_id}/purge")).await
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use time::{Date, Duration, PrimitiveDateTime};
+
+use crate::utilities::{
+    date::{local_time_zone, wall_time_in_time_zone},
+    requests::fetch::{delete_request, get_request_struct, post_request_struct, FetchError},
+};
+
+use super::{
+    backend, in_choir,
+    project::{DeadlinePolicy, ProjectTo},
+    section::Section,
+};
+
+pub async fn get_templates() -> Result<Vec<ProjectTemplate>, FetchError> {
+    let backend_url = backend();
+    get_request_struct::<Vec<ProjectTemplate>>(&in_choir(format!(
+        "{backend_url}/project-templates"
+    )))
+    .await
+}
+
+pub async fn create_template(
+    name: String,
+    content: TemplateContent,
+    choir_id: Option<i64>,
+) -> Result<ProjectTemplate, FetchError> {
+    let body = CreateTemplate {
+        name,
+        content,
+        choir_id,
+    };
+    let backend_url = backend();
+    post_request_struct::<CreateTemplate, ProjectTemplate>(
+        &format!("{backend_url}/project-templates"),
+        body,
+    )
+    .await
+}
+
+pub async fn delete_template(template_id: i64) -> Result<(), FetchError> {
+    let backend_url = backend();
+    delete_request(&format!("{backend_url}/project-templates/{template_id}")).await
+}
+
+/// A saved starting point for new projects, e.g. for a piece that is sung every season.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct ProjectTemplate {
+    pub id: i64,
+    pub name: String,
+    #[serde(flatten)]
+    pub content: TemplateContent,
+}
+
+#[derive(Clone, Serialize)]
+pub struct CreateTemplate {
+    pub name: String,
+    #[serde(flatten)]
+    pub content: TemplateContent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub choir_id: Option<i64>,
+}
+
+/// Everything a new project is prefilled with. Deadlines are kept relative to the start date of the project,
+/// so they can be moved to any season.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateContent {
+    pub title: String,
+    pub description: String,
+    /// Time zone the deadlines are counted in. The one of the viewer if missing.
+    #[serde(default)]
+    pub due_time_zone: Option<String>,
+    /// Minutes from midnight of the start date to the deadline, as shown on clocks in `due_time_zone`.
+    pub due_after_minutes: i64,
+    #[serde(default)]
+    pub deadline_policy: DeadlinePolicy,
+    #[serde(default)]
+    pub grace_period_hours: u32,
+    #[serde(default)]
+    pub section_deadlines: Vec<RelativeSectionDeadline>,
+    /// Project whose material is offered to be copied into new projects.
+    #[serde(default)]
+    pub material_project_id: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelativeSectionDeadline {
+    pub section: Section,
+    pub due_after_minutes: i64,
+}
+
+impl TemplateContent {
+    /// Copies the project. Its deadlines are taken relative to the day it was created on.
+    pub fn from_project(project: &ProjectTo) -> Self {
+        let time_zone = project
+            .due_time_zone
+            .clone()
+            .unwrap_or_else(local_time_zone);
+        let start = wall_time_in_time_zone(project.created_at, &time_zone).date();
+        let rules = &project.deadline_rules;
+
+        Self {
+            title: project.title.clone(),
+            description: project.description.clone(),
+            due_after_minutes: minutes_after(
+                start,
+                wall_time_in_time_zone(project.due, &time_zone),
+            ),
+            deadline_policy: rules.deadline_policy,
+            grace_period_hours: rules.grace_period_hours,
+            section_deadlines: rules
+                .section_deadlines
+                .iter()
+                .map(|deadline| RelativeSectionDeadline {
+                    section: deadline.section.clone(),
+                    due_after_minutes: minutes_after(
+                        start,
+                        wall_time_in_time_zone(deadline.due, &time_zone),
+                    ),
+                })
+                .collect(),
+            material_project_id: Some(project.id),
+            due_time_zone: Some(time_zone),
+        }
+    }
+
+    /// Deadline of a project starting on that day, as shown on clocks in `due_time_zone`.
+    pub fn due_from(&self, start: Date) -> PrimitiveDateTime {
+        shift(start, self.due_after_minutes)
+    }
+
+    /// Section deadlines of a project starting on that day, as shown on clocks in `due_time_zone`.
+    pub fn section_deadlines_from(&self, start: Date) -> Vec<(Section, PrimitiveDateTime)> {
+        self.section_deadlines
+            .iter()
+            .map(|deadline| {
+                (
+                    deadline.section.clone(),
+                    shift(start, deadline.due_after_minutes),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Counted on the clock, so deadlines keep their time of day across daylight saving time.
+fn minutes_after(start: Date, wall: PrimitiveDateTime) -> i64 {
+    (wall - start.midnight()).whole_minutes()
+}
+
+fn shift(start: Date, minutes: i64) -> PrimitiveDateTime {
+    start.midnight() + Duration::minutes(minutes)
+}
+
+#[cfg(test)]
+mod template_test {
+    use time::macros::{date, datetime};
+
+    use super::{minutes_after, RelativeSectionDeadline, TemplateContent};
+
+    #[test]
+    fn deadline_moves_with_start_date() {
+        let content = TemplateContent {
+            due_after_minutes: minutes_after(date!(2025 - 10 - 01), datetime!(2025-10-18 23:59)),
+            ..TemplateContent::default()
+        };
+
+        assert_eq!(
+            content.due_from(date!(2026 - 09 - 15)),
+            datetime!(2026-10-02 23:59)
+        );
+    }
+
+    #[test]
+    fn section_deadlines_move_with_start_date() {
+        let section = serde_json::from_str("\"Bass1\"").unwrap();
+        let content = TemplateContent {
+            section_deadlines: vec![RelativeSectionDeadline {
+                section,
+                due_after_minutes: 90,
+            }],
+            ..TemplateContent::default()
+        };
+
+        let deadlines = content.section_deadlines_from(date!(2026 - 03 - 29));
+        assert_eq!(deadlines[0].1, datetime!(2026-03-29 1:30));
+    }
+
+    #[test]
+    fn deadline_before_start_date_stays_before() {
+        assert_eq!(
+            minutes_after(date!(2026 - 10 - 18), datetime!(2026-10-17 23:00)),
+            -60
+        );
+    }
+
+    #[test]
+    fn template_without_optional_fields_parses() {
+        let content: TemplateContent = serde_json::from_str(
+            r#"{ "title": "Messiah", "description": "", "due_after_minutes": 1440 }"#,
+        )
+        .unwrap();
+        assert_eq!(content.material_project_id, None);
+        assert!(content.section_deadlines.is_empty());
+    }
+}
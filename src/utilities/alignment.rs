@@ -0,0 +1,258 @@
+//! Finds where the reference playback starts in a submission. Voice and playback sound different, so their onsets are
+//! compared instead of the samples: both are reduced to how much louder they get every few milliseconds, and the lag
+//! at which these envelopes match best is the offset.
+
+use std::f64::consts::PI;
+
+/// Values of the onset envelope per second. The offset is found to a step of this.
+const ENVELOPE_RATE: f64 = 200.0;
+
+/// Offsets beyond this are not searched, longer lead-ins are not realistic.
+const MAX_OFFSET_SECONDS: f64 = 60.0;
+
+/// Peaks this close to the best one belong to it and are not taken as competing offsets.
+const PEAK_WIDTH_SECONDS: f64 = 0.1;
+
+/// Offsets matching less than this much better than the runner-up are shown as uncertain.
+const RELIABLE_CONFIDENCE: f64 = 0.3;
+
+/// Keeps the logarithm finite in silence.
+const ENERGY_FLOOR: f64 = 1e-4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Alignment {
+    /// Milliseconds into the submission at which the reference starts. Negative if the submission starts later.
+    pub offset_ms: i64,
+    /// How much better the offset matches than any other, from 0 (ambiguous) to 1 (unique).
+    pub confidence: f64,
+}
+
+impl Alignment {
+    pub fn is_reliable(&self) -> bool {
+        self.confidence >= RELIABLE_CONFIDENCE
+    }
+}
+
+/// How much louder the audio gets at each step of the envelope.
+pub fn onset_envelope(samples: &[f32], sample_rate: f32) -> Vec<f32> {
+    let samples_per_step = sample_rate as f64 / ENVELOPE_RATE;
+    // Positions are rounded for each step, so long files don't drift.
+    let steps = (samples.len() as f64 / samples_per_step) as usize;
+    let mut previous: Option<f64> = None;
+    (0..steps)
+        .map(|step| {
+            let start = (step as f64 * samples_per_step) as usize;
+            let end = (((step + 1) as f64 * samples_per_step) as usize).min(samples.len());
+            let frame = &samples[start..end];
+            let energy =
+                frame.iter().map(|&s| (s as f64).powi(2)).sum::<f64>() / frame.len().max(1) as f64;
+            let level = (ENERGY_FLOOR + energy.sqrt()).ln();
+            let onset = previous.map_or(0.0, |previous| (level - previous).max(0.0));
+            previous = Some(level);
+            onset as f32
+        })
+        .collect()
+}
+
+/// Cross-correlates the onset envelopes of a submission and the reference.
+/// [None] if either is silent, so there is nothing to align.
+pub fn align(submission: &[f32], reference: &[f32]) -> Option<Alignment> {
+    let centred = |envelope: &[f32]| {
+        let mean = envelope.iter().map(|&v| v as f64).sum::<f64>() / envelope.len().max(1) as f64;
+        envelope
+            .iter()
+            .map(|&v| v as f64 - mean)
+            .collect::<Vec<_>>()
+    };
+    let (submission, reference) = (centred(submission), centred(reference));
+    let norm = |values: &[f64]| values.iter().map(|v| v * v).sum::<f64>().sqrt();
+    let scale = norm(&submission) * norm(&reference);
+    if scale <= 0.0 {
+        return None;
+    }
+
+    let size = (submission.len() + reference.len()).next_power_of_two();
+    let mut spectrum = fft(&submission, size, false);
+    let reference_spectrum = fft(&reference, size, false);
+    for (value, reference) in spectrum.iter_mut().zip(&reference_spectrum) {
+        *value = value.mul(reference.conjugate());
+    }
+    let correlation = fft_complex(spectrum, true);
+
+    // Index `lag` holds the match with the reference starting `lag` steps into the submission, negative lags wrap around.
+    let max_lag = (MAX_OFFSET_SECONDS * ENVELOPE_RATE) as isize;
+    let lags = (-(reference.len() as isize - 1).min(max_lag))
+        ..=(submission.len() as isize - 1).min(max_lag);
+    let at = |lag: isize| correlation[lag.rem_euclid(size as isize) as usize].re / scale;
+
+    let best = lags.clone().max_by(|&a, &b| at(a).total_cmp(&at(b)))?;
+    if at(best) <= 0.0 {
+        return None;
+    }
+    let peak_width = (PEAK_WIDTH_SECONDS * ENVELOPE_RATE) as isize;
+    let second = lags
+        .filter(|lag| (lag - best).abs() > peak_width)
+        .map(at)
+        .fold(0.0, f64::max);
+
+    Some(Alignment {
+        offset_ms: (best as f64 / ENVELOPE_RATE * 1000.0).round() as i64,
+        confidence: (1.0 - second / at(best)).clamp(0.0, 1.0),
+    })
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn mul(self, other: Complex) -> Complex {
+        Complex {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+
+    fn conjugate(self) -> Complex {
+        Complex {
+            re: self.re,
+            im: -self.im,
+        }
+    }
+}
+
+/// The spectrum of the values, padded with zeros to `size`, a power of two.
+fn fft(values: &[f64], size: usize, inverse: bool) -> Vec<Complex> {
+    let mut data = vec![Complex::default(); size];
+    for (slot, &value) in data.iter_mut().zip(values) {
+        slot.re = value;
+    }
+    fft_complex(data, inverse)
+}
+
+/// Iterative radix 2 transform. The inverse is scaled, so it restores the input.
+fn fft_complex(mut data: Vec<Complex>, inverse: bool) -> Vec<Complex> {
+    let size = data.len();
+    let mut j = 0;
+    for i in 1..size {
+        let mut bit = size >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut length = 2;
+    while length <= size {
+        let angle = sign * 2.0 * PI / length as f64;
+        let step = Complex {
+            re: angle.cos(),
+            im: angle.sin(),
+        };
+        for start in (0..size).step_by(length) {
+            let mut twiddle = Complex { re: 1.0, im: 0.0 };
+            for k in 0..length / 2 {
+                let even = data[start + k];
+                let odd = data[start + k + length / 2].mul(twiddle);
+                data[start + k] = Complex {
+                    re: even.re + odd.re,
+                    im: even.im + odd.im,
+                };
+                data[start + k + length / 2] = Complex {
+                    re: even.re - odd.re,
+                    im: even.im - odd.im,
+                };
+                twiddle = twiddle.mul(step);
+            }
+        }
+        length <<= 1;
+    }
+
+    if inverse {
+        for value in &mut data {
+            value.re /= size as f64;
+            value.im /= size as f64;
+        }
+    }
+    data
+}
+
+#[cfg(test)]
+mod alignment_test {
+    use std::f32::consts::PI;
+
+    use super::{align, onset_envelope};
+
+    const SAMPLE_RATE: f32 = 8_000.0;
+
+    /// Pseudo random numbers between 0 and 1, the same in every run.
+    fn random(seed: &mut u64) -> f32 {
+        *seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (*seed >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Notes of irregular length, sung (sine) or played (noise), after `lead_in` seconds of silence.
+    fn notes(lead_in: f32, sung: bool, seed: u64) -> Vec<f32> {
+        let mut rhythm = 7;
+        let mut noise = seed;
+        let mut samples = vec![0.0; (lead_in * SAMPLE_RATE) as usize];
+        for _ in 0..24 {
+            let length = (0.15 + 0.5 * random(&mut rhythm)) * SAMPLE_RATE;
+            for index in 0..length as usize {
+                let decay = 1.0 - index as f32 / length;
+                let sample = if sung {
+                    (2.0 * PI * 330.0 * index as f32 / SAMPLE_RATE).sin()
+                } else {
+                    random(&mut noise) * 2.0 - 1.0
+                };
+                samples.push(0.5 * decay * sample);
+            }
+            samples.extend(vec![0.0; (0.05 * SAMPLE_RATE) as usize]);
+        }
+        samples
+    }
+
+    #[test]
+    fn late_start_of_the_submission_is_found() {
+        let reference = onset_envelope(&notes(0.0, false, 1), SAMPLE_RATE);
+        let submission = onset_envelope(&notes(1.25, true, 2), SAMPLE_RATE);
+
+        let alignment = align(&submission, &reference).unwrap();
+
+        assert!((alignment.offset_ms - 1250).abs() <= 5, "{alignment:?}");
+        assert!(alignment.is_reliable(), "{alignment:?}");
+    }
+
+    #[test]
+    fn early_start_of_the_submission_is_found() {
+        let reference = onset_envelope(&notes(2.0, false, 1), SAMPLE_RATE);
+        let submission = onset_envelope(&notes(0.5, true, 2), SAMPLE_RATE);
+
+        let alignment = align(&submission, &reference).unwrap();
+
+        assert!((alignment.offset_ms + 1500).abs() <= 5, "{alignment:?}");
+    }
+
+    #[test]
+    fn silence_cannot_be_aligned() {
+        let reference = onset_envelope(&notes(0.0, false, 1), SAMPLE_RATE);
+        let silence = onset_envelope(&vec![0.0; 8_000], SAMPLE_RATE);
+
+        assert_eq!(align(&silence, &reference), None);
+        assert_eq!(align(&reference, &[]), None);
+    }
+
+    #[test]
+    fn envelope_has_a_value_every_five_milliseconds() {
+        assert_eq!(onset_envelope(&vec![0.0; 44_100], 44_100.0).len(), 200);
+    }
+}
@@ -0,0 +1,244 @@
+//! Decodes audio files in the browser, so they can be analysed in WASM.
+
+use js_sys::{Array, Float32Array, Reflect, Uint8Array};
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use web_sys::File;
+
+#[wasm_bindgen(module = "/js/custom/audio.js")]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    async fn decode_audio_url(url: &str) -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(catch)]
+    async fn decode_audio_file(file: &File) -> Result<JsValue, JsValue>;
+}
+
+/// Samples this loud are most likely clipped.
+pub const CLIPPING_LEVEL: f32 = 0.99;
+
+/// Quieter samples are taken for silence, about -50 dBFS.
+pub const SILENCE_LEVEL: f32 = 0.003;
+
+/// Samples of an audio file, between -1 and 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedAudio {
+    /// The browser resamples while decoding, so this is the same for all files.
+    pub sample_rate: f32,
+    pub channels: Vec<Vec<f32>>,
+    /// Sample rate the file was recorded with, if it could be read from its header.
+    pub source_sample_rate: Option<u32>,
+}
+
+impl DecodedAudio {
+    pub fn duration_seconds(&self) -> f64 {
+        let length = self.channels.first().map_or(0, Vec::len);
+        if self.sample_rate > 0.0 {
+            length as f64 / self.sample_rate as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// The average of all channels.
+    pub fn mono(&self) -> Vec<f32> {
+        match self.channels.as_slice() {
+            [] => Vec::new(),
+            [single] => single.clone(),
+            channels => {
+                let length = channels.iter().map(Vec::len).min().unwrap_or_default();
+                let count = channels.len() as f32;
+                (0..length)
+                    .map(|index| channels.iter().map(|channel| channel[index]).sum::<f32>() / count)
+                    .collect()
+            }
+        }
+    }
+
+    fn from_js(value: &JsValue) -> Result<Self, String> {
+        let field = |name: &str| {
+            Reflect::get(value, &JsValue::from_str(name)).map_err(|error| format!("{error:?}"))
+        };
+        let sample_rate = field("sample_rate")?
+            .as_f64()
+            .ok_or("The sample rate is missing")? as f32;
+        let channels = Array::from(&field("channels")?)
+            .iter()
+            .map(|channel| Float32Array::from(channel).to_vec())
+            .collect();
+        let header = Uint8Array::from(field("header")?).to_vec();
+        Ok(Self {
+            sample_rate,
+            channels,
+            source_sample_rate: source_sample_rate(&header),
+        })
+    }
+}
+
+/// Downloads and decodes the audio file at the url.
+pub async fn decode_url(url: &str) -> Result<DecodedAudio, String> {
+    let value = decode_audio_url(url)
+        .await
+        .map_err(|error| format!("{error:?}"))?;
+    DecodedAudio::from_js(&value)
+}
+
+/// Decodes an audio file chosen by the user.
+pub async fn decode_file(file: &File) -> Result<DecodedAudio, String> {
+    let value = decode_audio_file(file)
+        .await
+        .map_err(|error| format!("{error:?}"))?;
+    DecodedAudio::from_js(&value)
+}
+
+/// Reads the sample rate from the start of a WAV, FLAC, MP3 or Ogg file.
+pub fn source_sample_rate(header: &[u8]) -> Option<u32> {
+    let u32_le = |at: usize| Some(u32::from_le_bytes(header.get(at..at + 4)?.try_into().ok()?));
+    let find = |pattern: &[u8]| {
+        header
+            .windows(pattern.len())
+            .position(|window| window == pattern)
+    };
+
+    let rate = if header.starts_with(b"RIFF") && header.get(8..12) == Some(b"WAVE") {
+        let mut chunk = 12;
+        loop {
+            let size = u32_le(chunk + 4)? as usize;
+            if header.get(chunk..chunk + 4)? == b"fmt " {
+                break u32_le(chunk + 12)?;
+            }
+            // Chunks are padded to an even length. Sizes of broken files must not wrap around to the same chunk.
+            chunk = chunk
+                .checked_add(8)?
+                .checked_add(size)?
+                .checked_add(size % 2)?;
+        }
+    } else if header.starts_with(b"fLaC") {
+        // The first metadata block is the stream info, the rate has 20 bits after the block and frame sizes.
+        let bytes = header.get(18..21)?;
+        (bytes[0] as u32) << 12 | (bytes[1] as u32) << 4 | (bytes[2] as u32) >> 4
+    } else if header.starts_with(b"OggS") {
+        if let Some(vorbis) = find(b"\x01vorbis") {
+            u32_le(vorbis + 12)?
+        } else {
+            // Opus is always decoded at 48 kHz, but keeps the rate it was recorded with.
+            u32_le(find(b"OpusHead")? + 12)?
+        }
+    } else {
+        mp3_sample_rate(header)?
+    };
+    (rate > 0).then_some(rate)
+}
+
+fn mp3_sample_rate(header: &[u8]) -> Option<u32> {
+    let mut start = 0;
+    if header.starts_with(b"ID3") {
+        // The size of an ID3 tag is stored with 7 bits per byte.
+        let size = header
+            .get(6..10)?
+            .iter()
+            .fold(0, |size, &byte| size << 7 | (byte & 0x7f) as usize);
+        let footer = if header.get(5)? & 0x10 != 0 { 10 } else { 0 };
+        start = 10 + size + footer;
+    }
+
+    let frame = header.get(start..start + 3)?;
+    let version = (frame[1] >> 3) & 0b11;
+    let layer = (frame[1] >> 1) & 0b11;
+    let rate_index = ((frame[2] >> 2) & 0b11) as usize;
+    if frame[0] != 0xff || frame[1] & 0xe0 != 0xe0 || version == 1 || layer == 0 || rate_index == 3
+    {
+        return None;
+    }
+    let rate = [44_100, 48_000, 32_000][rate_index];
+    Some(match version {
+        // MPEG 1
+        3 => rate,
+        // MPEG 2
+        2 => rate / 2,
+        // MPEG 2.5
+        _ => rate / 4,
+    })
+}
+
+#[cfg(test)]
+mod audio_test {
+    use super::{source_sample_rate, DecodedAudio};
+
+    #[test]
+    fn channels_are_mixed_to_mono() {
+        let audio = DecodedAudio {
+            sample_rate: 4.0,
+            channels: vec![vec![1.0, 0.5, 0.0, -1.0], vec![0.0, 0.5, 1.0, -1.0]],
+            source_sample_rate: None,
+        };
+
+        assert_eq!(audio.mono(), vec![0.5, 0.5, 0.5, -1.0]);
+        assert_eq!(audio.duration_seconds(), 1.0);
+    }
+
+    #[test]
+    fn empty_audio_has_no_duration() {
+        let audio = DecodedAudio {
+            sample_rate: 44100.0,
+            channels: Vec::new(),
+            source_sample_rate: None,
+        };
+
+        assert!(audio.mono().is_empty());
+        assert_eq!(audio.duration_seconds(), 0.0);
+    }
+
+    #[test]
+    fn wav_sample_rate_is_read_from_fmt_chunk() {
+        let mut header = b"RIFF\0\0\0\0WAVELIST\x03\0\0\0abc\0fmt \x10\0\0\0\x01\0\x01\0".to_vec();
+        header.extend(8000u32.to_le_bytes());
+
+        assert_eq!(source_sample_rate(&header), Some(8000));
+    }
+
+    #[test]
+    fn wav_with_oversized_chunk_has_no_sample_rate() {
+        let mut header = b"RIFF\0\0\0\0WAVELIST".to_vec();
+        header.extend((u32::MAX - 7).to_le_bytes());
+        header.extend([0; 32]);
+
+        assert_eq!(source_sample_rate(&header), None);
+    }
+
+    #[test]
+    fn flac_sample_rate_is_read_from_stream_info() {
+        let mut header = b"fLaC\0\0\0\x22".to_vec();
+        header.extend([0x10, 0x00, 0x10, 0x00, 0, 0, 0, 0, 0, 0]);
+        // 44100 Hz, 2 channels, 16 bits.
+        header.extend([0x0a, 0xc4, 0x42, 0xf0]);
+
+        assert_eq!(source_sample_rate(&header), Some(44_100));
+    }
+
+    #[test]
+    fn mp3_sample_rate_is_read_after_id3_tag() {
+        let mut header = b"ID3\x04\0\0\0\0\x01\x00".to_vec();
+        header.extend([0; 128]);
+        // MPEG 2 layer 3, 22050 Hz.
+        header.extend([0xff, 0xf3, 0x90, 0xc4]);
+
+        assert_eq!(source_sample_rate(&header), Some(22_050));
+    }
+
+    #[test]
+    fn ogg_sample_rate_is_read() {
+        let mut vorbis = b"OggS\0\x02\x01vorbis\0\0\0\0\x01".to_vec();
+        vorbis.extend(16_000u32.to_le_bytes());
+        let mut opus = b"OggS\0\x02OpusHead\x01\x01\x38\x01".to_vec();
+        opus.extend(48_000u32.to_le_bytes());
+
+        assert_eq!(source_sample_rate(&vorbis), Some(16_000));
+        assert_eq!(source_sample_rate(&opus), Some(48_000));
+    }
+
+    #[test]
+    fn unknown_formats_have_no_sample_rate() {
+        assert_eq!(source_sample_rate(b"\x1aE\xdf\xa3webm"), None);
+        assert_eq!(source_sample_rate(b"RIFF\0\0\0\0WAVE"), None);
+        assert_eq!(source_sample_rate(&[]), None);
+    }
+}
@@ -61,51 +61,6 @@ where
     convert_enum_callback_fallible::<InputEvent, Enum, Error>(callback, input_event_to_string)
 }
 
-/// # Overview
-/// This function converts the [Callback] fired by the change of an HTML ```<select>``` into a [Callback] containing any ```Enum``` that implements ```from<&str>```.
-/// The from has to have a match for the  ```value``` of any ```<option>``` inside of the ```<select>```. There is a fallible option for enums that dont implement [From], but only [TryFrom].
-///
-/// Note, that the function is backwards - it takes the output callback (with the enum value) as parameter,
-/// and returns a ```Callback<InputEvent>``` that you then can add to the ```onchange``` of a ```<select>``` like so:
-/// ```rust
-/// enum E {
-///     V1,
-///     V2
-/// }
-///
-/// impl From<&str> for E {
-///
-///     fn from(s: &str) -> Result<Self, Self::Error> {
-///         let result = match s {
-///             "1" => Self::V1,
-///             _ => Self::V2,
-///         };
-///
-///         Ok(result)
-///     }
-/// }
-///
-/// let outer_callback: Callback<Result<String>> = Callback::noop();
-/// html!{
-///     <select onchange={ select_to_enum_callback_infallible(outer_callback) }>
-///         <option value="1">1</option>
-///         <option value="2">2</option>
-///     </select>
-/// };
-///
-/// ```
-///
-/// # Panic
-/// Unwrap_throw()s when invoked on any other than an HTML ```<select>``` element.
-pub fn select_to_enum_callback_infallible<Enum>(callback: Callback<Enum>) -> Callback<InputEvent>
-where
-    Enum: for<'a> From<&'a str> + 'static,
-{
-    convert_enum_callback_infallible::<InputEvent, Enum>(callback, input_event_to_string)
-
-    //callback.reform(input_event_to_string)
-}
-
 /// This function converts a [Callback] containing InputEvent into an enum (or struct) that implements [TryFrom] for &str.
 /// The input transform function provided is used to convert the InputEvent into a String, which is then used to constuct the enum.
 pub fn convert_enum_callback_fallible<Input, Output, Error>(
@@ -133,25 +88,6 @@ fn input_event_to_string(event: InputEvent) -> String {
         .value()
 }
 
-/// This function converts a [Callback] containing InputEvent into an enum (or struct) that implements [From] for &str.
-/// The input transform function provided is used to convert the InputEvent into a String, which is then used to constuct the enum.
-pub fn convert_enum_callback_infallible<Input, Output>(
-    callback: Callback<Output>,
-    input_transform: fn(Input) -> String,
-) -> Callback<Input>
-where
-    Input: 'static,
-    Output: for<'a> From<&'a str> + 'static,
-{
-    convert_callback(
-        callback,
-        Box::new(move |input| {
-            let intermediate = input_transform(input);
-            intermediate.as_str().into()
-        }),
-    )
-}
-
 pub fn convert_callback<Input, Output>(
     callback: Callback<Output>,
     function: Box<dyn Fn(Input) -> Output>,
@@ -1,16 +1,204 @@
-use time::{macros::format_description, OffsetDateTime, PrimitiveDateTime};
+use gloo_console::warn;
+use time::{macros::format_description, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 
-pub fn format_datetime_human_readable(date: &PrimitiveDateTime) -> String {
-    let format = format_description!("[day].[month].[year] [hour]:[minute] Uhr");
-    date.format(&format).unwrap()
+use crate::i18n::{locale, Locale};
+
+#[wasm_bindgen(module = "/js/custom/time_zone.js")]
+extern "C" {
+    fn viewer_time_zone() -> String;
+    fn supported_time_zones() -> String;
+    #[wasm_bindgen(catch)]
+    fn time_zone_offset_seconds(zone: &str, unix_seconds: f64) -> Result<f64, JsValue>;
+}
+
+/// Formats the date in the time zone of the viewer, with the offset to UTC shown.
+pub fn format_datetime_human_readable(date: &OffsetDateTime) -> String {
+    format_datetime(&to_viewer_time(*date), locale(), false)
+}
+
+/// Like [format_datetime_human_readable], with seconds.
+pub fn format_datetime_human_readable_seconds(date: &OffsetDateTime) -> String {
+    format_datetime(&to_viewer_time(*date), locale(), true)
+}
+
+/// Formats the date in the given time zone, with its name shown, e.g. `18.10.2026 23:59 Uhr (America/New_York)`.
+pub fn format_datetime_in_time_zone(date: &OffsetDateTime, zone: &str) -> String {
+    match offset_in_time_zone(zone, *date) {
+        Some(offset) => {
+            let local = date.to_offset(offset);
+            let wall = PrimitiveDateTime::new(local.date(), local.time());
+            format!("{} ({zone})", format_wall_time(&wall, locale(), false))
+        }
+        None => format_datetime_human_readable(date),
+    }
+}
+
+/// Formats the date like it is usual in the language, e.g. `18.10.2026 15:05 Uhr (UTC+02:00)` or `Oct 18, 2026 3:05 PM (UTC+02:00)`.
+fn format_datetime(date: &OffsetDateTime, locale: Locale, seconds: bool) -> String {
+    let wall = PrimitiveDateTime::new(date.date(), date.time());
+    let offset = date
+        .offset()
+        .format(format_description!(
+            "[offset_hour sign:mandatory]:[offset_minute]"
+        ))
+        .unwrap();
+
+    format!("{} (UTC{offset})", format_wall_time(&wall, locale, seconds))
 }
 
-pub fn format_datetime_human_readable_seconds(date: &PrimitiveDateTime) -> String {
-    let format = format_description!("[day].[month].[year] [hour]:[minute]:[second] Uhr");
-    date.format(&format).unwrap()
+fn format_wall_time(date: &PrimitiveDateTime, locale: Locale, seconds: bool) -> String {
+    let formatted = match (locale, seconds) {
+        (Locale::German, false) => {
+            date.format(format_description!("[day].[month].[year] [hour]:[minute] Uhr"))
+        }
+        (Locale::German, true) => date.format(format_description!(
+            "[day].[month].[year] [hour]:[minute]:[second] Uhr"
+        )),
+        (Locale::English, false) => date.format(format_description!(
+            "[month repr:short] [day padding:none], [year] [hour repr:12 padding:none]:[minute] [period]"
+        )),
+        (Locale::English, true) => date.format(format_description!(
+            "[month repr:short] [day padding:none], [year] [hour repr:12 padding:none]:[minute]:[second] [period]"
+        )),
+    };
+
+    formatted.unwrap()
+}
+
+pub fn now() -> OffsetDateTime {
+    OffsetDateTime::now_utc()
+}
+
+/// The date in the time zone of the viewer at that moment, so daylight saving time is respected.
+pub fn to_viewer_time(date: OffsetDateTime) -> OffsetDateTime {
+    let offset = UtcOffset::local_offset_at(date).unwrap_or(UtcOffset::UTC);
+    date.to_offset(offset)
 }
 
-pub fn now() -> PrimitiveDateTime {
-    let now = OffsetDateTime::now_local().unwrap();
-    PrimitiveDateTime::new(now.date(), now.time())
+/// Name of the time zone of the viewer, e.g. `Europe/Berlin`.
+pub fn local_time_zone() -> String {
+    viewer_time_zone()
+}
+
+/// All time zones that can be chosen, the one of the viewer first.
+pub fn time_zones() -> Vec<String> {
+    let local = local_time_zone();
+    let mut zones = vec![local.clone()];
+    zones.extend(
+        supported_time_zones()
+            .lines()
+            .filter(|zone| *zone != local)
+            .map(str::to_string),
+    );
+    zones
+}
+
+/// The offset of the time zone to UTC at that moment. [None] if the browser does not know the time zone.
+pub fn offset_in_time_zone(zone: &str, date: OffsetDateTime) -> Option<UtcOffset> {
+    match time_zone_offset_seconds(zone, date.unix_timestamp() as f64) {
+        Ok(seconds) => UtcOffset::from_whole_seconds(seconds as i32).ok(),
+        Err(error) => {
+            warn!(format!("Unknown time zone {zone}"));
+            warn!(error);
+            None
+        }
+    }
+}
+
+/// The date as it is shown on clocks in the time zone.
+pub fn wall_time_in_time_zone(date: OffsetDateTime, zone: &str) -> PrimitiveDateTime {
+    let local = date.to_offset(offset_in_time_zone(zone, date).unwrap_or(UtcOffset::UTC));
+    PrimitiveDateTime::new(local.date(), local.time())
+}
+
+/// The moment at which clocks in the time zone show the wall time.
+pub fn wall_time_to_instant(wall: PrimitiveDateTime, zone: &str) -> OffsetDateTime {
+    resolve_wall_time(wall, |date| {
+        offset_in_time_zone(zone, date).unwrap_or(UtcOffset::UTC)
+    })
+}
+
+/// Formats a duration as minutes and seconds, e.g. `1:15`.
+pub fn format_elapsed(seconds: i64) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Finds the moment a wall time refers to, given the offset of its time zone at any moment.
+/// The offset is looked up twice, so wall times right after a change of the offset (daylight saving time) are resolved correctly.
+fn resolve_wall_time(
+    wall: PrimitiveDateTime,
+    offset_at: impl Fn(OffsetDateTime) -> UtcOffset,
+) -> OffsetDateTime {
+    let guess = wall.assume_utc();
+    let first = offset_at(guess);
+    let candidate = wall.assume_offset(first);
+    let second = offset_at(candidate);
+
+    if first == second {
+        candidate
+    } else {
+        wall.assume_offset(second)
+    }
+}
+
+#[cfg(test)]
+mod date_test {
+    use time::{
+        macros::{datetime, offset},
+        OffsetDateTime, UtcOffset,
+    };
+
+    use crate::i18n::Locale;
+
+    use super::{format_datetime, format_elapsed, resolve_wall_time};
+
+    #[test]
+    fn german_format() {
+        let date = datetime!(2026-10-08 15:05:09 +02:00);
+        assert_eq!(
+            format_datetime(&date, Locale::German, false),
+            "08.10.2026 15:05 Uhr (UTC+02:00)"
+        );
+    }
+
+    #[test]
+    fn english_format() {
+        let date = datetime!(2026-10-08 15:05:09 -04:00);
+        assert_eq!(
+            format_datetime(&date, Locale::English, true),
+            "Oct 8, 2026 3:05:09 PM (UTC-04:00)"
+        );
+    }
+
+    /// Central european time: +02:00 in summer until 2026-10-25 01:00 UTC, +01:00 afterwards.
+    fn berlin(date: OffsetDateTime) -> UtcOffset {
+        if date < datetime!(2026-10-25 01:00 UTC) {
+            offset!(+2)
+        } else {
+            offset!(+1)
+        }
+    }
+
+    #[test]
+    fn elapsed_time_is_formatted() {
+        assert_eq!(format_elapsed(0), "0:00");
+        assert_eq!(format_elapsed(75), "1:15");
+        assert_eq!(format_elapsed(3600), "60:00");
+    }
+
+    #[test]
+    fn wall_time_resolved_with_fixed_offset() {
+        let instant = resolve_wall_time(datetime!(2026-10-18 23:59), |_| offset!(-5));
+        assert_eq!(instant, datetime!(2026-10-19 04:59 UTC));
+    }
+
+    #[test]
+    fn wall_time_resolved_around_daylight_saving_change() {
+        let summer = resolve_wall_time(datetime!(2026-10-24 23:59), berlin);
+        assert_eq!(summer, datetime!(2026-10-24 21:59 UTC));
+
+        let winter = resolve_wall_time(datetime!(2026-10-25 23:59), berlin);
+        assert_eq!(winter, datetime!(2026-10-25 22:59 UTC));
+    }
 }
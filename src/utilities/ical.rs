@@ -0,0 +1,213 @@
+//! Writes iCalendar files ([RFC 5545](https://www.rfc-editor.org/rfc/rfc5545)), so deadlines can be added to any calendar app.
+
+use time::{macros::format_description, Duration, OffsetDateTime, UtcOffset};
+
+const PRODUCT_ID: &str = "-//Chormaeleon//Turnin Portal//EN";
+
+/// Lines longer than this many bytes (without the line break) are folded.
+const MAX_LINE_LENGTH: usize = 75;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Calendar {
+    pub name: String,
+    pub events: Vec<CalendarEvent>,
+}
+
+/// An event at a single moment, e.g. a deadline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalendarEvent {
+    /// Identifies the event across exports, so calendar apps update it instead of adding it again.
+    pub uid: String,
+    pub start: OffsetDateTime,
+    pub summary: String,
+    pub description: String,
+    pub url: Option<String>,
+    /// How long before the start reminders are shown.
+    pub alarms: Vec<Duration>,
+}
+
+impl Calendar {
+    /// The content of the `.ics` file. `now` is stored as the time the events were exported.
+    pub fn to_ics(&self, now: OffsetDateTime) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            format!("PRODID:{PRODUCT_ID}"),
+            "CALSCALE:GREGORIAN".to_string(),
+            "METHOD:PUBLISH".to_string(),
+            format!("X-WR-CALNAME:{}", escape_text(&self.name)),
+        ];
+
+        for event in &self.events {
+            event.write_lines(&mut lines, now);
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        lines
+            .iter()
+            .map(|line| fold_line(line))
+            .map(|line| line + "\r\n")
+            .collect()
+    }
+}
+
+impl CalendarEvent {
+    fn write_lines(&self, lines: &mut Vec<String>, now: OffsetDateTime) {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", escape_text(&self.uid)));
+        lines.push(format!("DTSTAMP:{}", format_utc(now)));
+        lines.push(format!("DTSTART:{}", format_utc(self.start)));
+        lines.push(format!("DTEND:{}", format_utc(self.start)));
+        lines.push(format!("SUMMARY:{}", escape_text(&self.summary)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&self.description)));
+        if let Some(url) = &self.url {
+            lines.push(format!("URL:{url}"));
+        }
+
+        for alarm in &self.alarms {
+            lines.push("BEGIN:VALARM".to_string());
+            lines.push("ACTION:DISPLAY".to_string());
+            lines.push(format!("DESCRIPTION:{}", escape_text(&self.summary)));
+            lines.push(format!("TRIGGER:{}", format_trigger(*alarm)));
+            lines.push("END:VALARM".to_string());
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+}
+
+fn format_utc(date: OffsetDateTime) -> String {
+    date.to_offset(UtcOffset::UTC)
+        .format(format_description!(
+            "[year][month][day]T[hour][minute][second]Z"
+        ))
+        .unwrap()
+}
+
+/// The trigger of a reminder shown that long before the event.
+fn format_trigger(before: Duration) -> String {
+    let minutes = before.whole_minutes().abs();
+    let sign = if before.is_negative() { "" } else { "-" };
+
+    if minutes == 0 {
+        "PT0M".to_string()
+    } else if minutes % (24 * 60) == 0 {
+        format!("{sign}P{}D", minutes / (24 * 60))
+    } else if minutes % 60 == 0 {
+        format!("{sign}PT{}H", minutes / 60)
+    } else {
+        format!("{sign}PT{minutes}M")
+    }
+}
+
+/// Escapes the characters with special meaning in text values.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Splits lines longer than 75 bytes, continuation lines start with a space.
+/// Multi-byte characters are never split.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / MAX_LINE_LENGTH * 3);
+    let mut line_length = 0;
+
+    for character in line.chars() {
+        if line_length + character.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            // The space counts towards the length of the continuation line.
+            line_length = 1;
+        }
+        folded.push(character);
+        line_length += character.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod ical_test {
+    use time::{macros::datetime, Duration};
+
+    use super::{escape_text, fold_line, format_trigger, Calendar, CalendarEvent};
+
+    fn event() -> CalendarEvent {
+        CalendarEvent {
+            uid: "project-1@example.org".to_string(),
+            start: datetime!(2026-10-18 23:59 +2),
+            summary: "Messiah".to_string(),
+            description: "Deadline".to_string(),
+            url: Some("https://example.org/events/1".to_string()),
+            alarms: vec![Duration::days(1), Duration::hours(2)],
+        }
+    }
+
+    #[test]
+    fn calendar_is_written() {
+        let calendar = Calendar {
+            name: "Choir".to_string(),
+            events: vec![event()],
+        };
+
+        let ics = calendar.to_ics(datetime!(2026-10-01 12:00 UTC));
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert!(ics.contains("\r\nDTSTAMP:20261001T120000Z\r\n"));
+        assert!(ics.contains("\r\nDTSTART:20261018T215900Z\r\n"));
+        assert!(ics.contains("\r\nURL:https://example.org/events/1\r\n"));
+        assert!(ics.contains("\r\nTRIGGER:-P1D\r\n"));
+        assert!(ics.contains("\r\nTRIGGER:-PT2H\r\n"));
+        assert_eq!(ics.matches("BEGIN:VALARM").count(), 2);
+        assert!(!ics.replace("\r\n", "").contains('\n'));
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        assert_eq!(
+            escape_text("Bach; Mozart, Händel\\Haydn\r\nnext"),
+            "Bach\\; Mozart\\, Händel\\\\Haydn\\nnext"
+        );
+    }
+
+    #[test]
+    fn long_lines_are_folded() {
+        let line = format!("SUMMARY:{}", "a".repeat(100));
+
+        let folded = fold_line(&line);
+
+        let lines: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), 75);
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn multi_byte_characters_are_not_split() {
+        let line = format!("SUMMARY:{}", "ä".repeat(100));
+
+        let folded = fold_line(&line);
+
+        for line in folded.split("\r\n") {
+            assert!(line.len() <= 75);
+        }
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn triggers_use_largest_unit() {
+        assert_eq!(format_trigger(Duration::days(2)), "-P2D");
+        assert_eq!(format_trigger(Duration::hours(3)), "-PT3H");
+        assert_eq!(format_trigger(Duration::minutes(90)), "-PT90M");
+        assert_eq!(format_trigger(Duration::ZERO), "PT0M");
+    }
+}
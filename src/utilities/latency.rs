@@ -0,0 +1,107 @@
+//! Measures the round trip latency of the audio setup: clicks are played and recorded again through the microphone,
+//! the delay until they are heard is the latency. Recordings made while listening to a playback are late by that much.
+
+/// How long after a click its echo is searched for, in seconds. Longer latencies are not realistic.
+const SEARCH_WINDOW_SECONDS: f64 = 0.5;
+
+/// Samples quieter than this are never taken for a click, however silent the room is.
+const MIN_THRESHOLD: f32 = 0.02;
+
+/// How many times louder than the noise before the first click a sample has to be to count as click.
+const NOISE_FACTOR: f32 = 8.0;
+
+/// Finds the latency in milliseconds in a recording of clicks played at `click_times` (seconds since the start of the recording).
+/// [None] if fewer than half of the clicks were heard, e.g. because the microphone was too far away from the speakers.
+pub fn detect_latency(samples: &[f32], sample_rate: f32, click_times: &[f64]) -> Option<f64> {
+    if click_times.is_empty() || sample_rate <= 0.0 {
+        return None;
+    }
+
+    let to_index = |seconds: f64| {
+        ((seconds * sample_rate as f64).round().max(0.0) as usize).min(samples.len())
+    };
+    let first_click = to_index(click_times.iter().copied().fold(f64::INFINITY, f64::min));
+    let threshold = (noise_level(&samples[..first_click]) * NOISE_FACTOR).max(MIN_THRESHOLD);
+
+    let mut latencies: Vec<f64> = click_times
+        .iter()
+        .filter_map(|&click| {
+            let start = to_index(click);
+            let end = to_index(click + SEARCH_WINDOW_SECONDS);
+            samples[start..end]
+                .iter()
+                .position(|sample| sample.abs() > threshold)
+                .map(|offset| offset as f64 / sample_rate as f64 * 1000.0)
+        })
+        .collect();
+
+    if latencies.len() * 2 < click_times.len() {
+        return None;
+    }
+
+    latencies.sort_by(f64::total_cmp);
+    Some(latencies[latencies.len() / 2])
+}
+
+/// Root mean square of the samples.
+fn noise_level(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod latency_test {
+    use super::detect_latency;
+
+    const SAMPLE_RATE: f32 = 8000.0;
+
+    /// One second of quiet noise per click, with the echo of each click `latency_ms` after it.
+    fn recording(click_times: &[f64], latency_ms: f64, heard: &[bool]) -> Vec<f32> {
+        let length = ((click_times.last().unwrap() + 1.0) * SAMPLE_RATE as f64) as usize;
+        let mut samples: Vec<f32> = (0..length)
+            .map(|index| if index % 2 == 0 { 0.001 } else { -0.001 })
+            .collect();
+
+        for (click, heard) in click_times.iter().zip(heard) {
+            if !heard {
+                continue;
+            }
+            let start = ((click + latency_ms / 1000.0) * SAMPLE_RATE as f64) as usize;
+            for (offset, sample) in samples[start..start + 40].iter_mut().enumerate() {
+                *sample = if offset % 2 == 0 { 0.6 } else { -0.6 };
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn latency_of_clicks_is_found() {
+        let clicks = [0.5, 1.0, 1.5, 2.0];
+        let samples = recording(&clicks, 120.0, &[true; 4]);
+
+        let latency = detect_latency(&samples, SAMPLE_RATE, &clicks).unwrap();
+
+        assert!((latency - 120.0).abs() < 1.0, "{latency}");
+    }
+
+    #[test]
+    fn some_missed_clicks_are_tolerated() {
+        let clicks = [0.5, 1.0, 1.5, 2.0];
+        let samples = recording(&clicks, 80.0, &[true, false, true, true]);
+
+        let latency = detect_latency(&samples, SAMPLE_RATE, &clicks).unwrap();
+
+        assert!((latency - 80.0).abs() < 1.0, "{latency}");
+    }
+
+    #[test]
+    fn silence_has_no_latency() {
+        let clicks = [0.5, 1.0, 1.5, 2.0];
+        let samples = recording(&clicks, 80.0, &[false, false, false, true]);
+
+        assert_eq!(detect_latency(&samples, SAMPLE_RATE, &clicks), None);
+        assert_eq!(detect_latency(&[], SAMPLE_RATE, &clicks), None);
+    }
+}
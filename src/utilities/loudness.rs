@@ -0,0 +1,187 @@
+//! Integrated loudness after EBU R 128 (ITU-R BS.1770): the channels are K-weighted, their power is measured in
+//! overlapping blocks and averaged over the blocks that are not silent.
+
+use std::f64::consts::PI;
+
+/// Length of a block in seconds.
+const BLOCK_SECONDS: f64 = 0.4;
+
+/// Blocks overlap by three quarters.
+const BLOCK_STEP_SECONDS: f64 = 0.1;
+
+/// Blocks quieter than this are ignored in any case.
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+/// Blocks this much quieter than the average of the loud enough blocks are ignored as well.
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+/// A filter of second order, applied sample by sample.
+#[derive(Clone, Copy, Debug)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+}
+
+impl Biquad {
+    /// The shelf raising high frequencies, as the head does.
+    fn high_shelf(sample_rate: f64) -> Self {
+        let gain_db = 3.999_843_853_973_347;
+        let q = 0.707_175_236_955_419_6;
+        let k = (PI * 1_681.974_450_955_533 / sample_rate).tan();
+        let vh = 10f64.powf(gain_db / 20.0);
+        let vb = vh.powf(0.499_666_774_154_541_6);
+        let a0 = 1.0 + k / q + k * k;
+        Self {
+            b: [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        }
+    }
+
+    /// The high pass leaving out frequencies that are hardly heard.
+    fn high_pass(sample_rate: f64) -> Self {
+        let q = 0.500_327_037_323_877_3;
+        let k = (PI * 38.135_470_876_024_44 / sample_rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+        Self {
+            b: [1.0, -2.0, 1.0],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        }
+    }
+
+    fn apply(&self, samples: &[f64]) -> Vec<f64> {
+        let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+        samples
+            .iter()
+            .map(|&x| {
+                let y = self.b[0] * x + self.b[1] * x1 + self.b[2] * x2
+                    - self.a[0] * y1
+                    - self.a[1] * y2;
+                (x2, x1, y2, y1) = (x1, x, y1, y);
+                y
+            })
+            .collect()
+    }
+}
+
+/// The integrated loudness of the channels in LUFS. [None] if the audio is shorter than a block or silent.
+pub fn integrated_loudness(channels: &[Vec<f32>], sample_rate: f32) -> Option<f64> {
+    let sample_rate = sample_rate as f64;
+    let length = channels.iter().map(Vec::len).min()?;
+    let block = (BLOCK_SECONDS * sample_rate).round() as usize;
+    let step = (BLOCK_STEP_SECONDS * sample_rate).round() as usize;
+    if sample_rate <= 0.0 || block == 0 || length < block {
+        return None;
+    }
+
+    let shelf = Biquad::high_shelf(sample_rate);
+    let high_pass = Biquad::high_pass(sample_rate);
+    let weighted = channels
+        .iter()
+        .map(|channel| {
+            let samples = channel[..length]
+                .iter()
+                .map(|&sample| sample as f64)
+                .collect::<Vec<_>>();
+            high_pass.apply(&shelf.apply(&samples))
+        })
+        .collect::<Vec<_>>();
+
+    // Summed over the channels, front channels are weighted equally.
+    let powers = (0..=(length - block) / step)
+        .map(|index| {
+            let start = index * step;
+            weighted
+                .iter()
+                .map(|channel| {
+                    channel[start..start + block]
+                        .iter()
+                        .map(|sample| sample * sample)
+                        .sum::<f64>()
+                        / block as f64
+                })
+                .sum::<f64>()
+        })
+        .collect::<Vec<_>>();
+
+    let gated_mean = |gate: f64| {
+        let loud = powers
+            .iter()
+            .filter(|&&power| loudness(power) > gate)
+            .collect::<Vec<_>>();
+        (!loud.is_empty()).then(|| loud.iter().copied().sum::<f64>() / loud.len() as f64)
+    };
+
+    let relative_gate = loudness(gated_mean(ABSOLUTE_GATE_LUFS)?) + RELATIVE_GATE_LU;
+    gated_mean(relative_gate.max(ABSOLUTE_GATE_LUFS)).map(loudness)
+}
+
+fn loudness(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+#[cfg(test)]
+mod loudness_test {
+    use std::f32::consts::PI;
+
+    use super::integrated_loudness;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn sine(frequency: f32, dbfs: f32, seconds: f32) -> Vec<f32> {
+        let amplitude = 10f32.powf(dbfs / 20.0);
+        (0..(seconds * SAMPLE_RATE) as usize)
+            .map(|index| amplitude * (2.0 * PI * frequency * index as f32 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("Loudness expected");
+        assert!(
+            (actual - expected).abs() < 0.1,
+            "{actual} LUFS instead of {expected} LUFS"
+        );
+    }
+
+    /// Test cases 1 and 2 of EBU Tech 3341: a stereo sine of 1 kHz is as loud as its level.
+    #[test]
+    fn stereo_sine_has_its_level() {
+        let tone = sine(1_000.0, -23.0, 20.0);
+        assert_close(
+            integrated_loudness(&[tone.clone(), tone], SAMPLE_RATE),
+            -23.0,
+        );
+
+        let tone = sine(1_000.0, -33.0, 20.0);
+        assert_close(
+            integrated_loudness(&[tone.clone(), tone], SAMPLE_RATE),
+            -33.0,
+        );
+    }
+
+    /// Test case 3 of EBU Tech 3341: quiet parts are gated away.
+    #[test]
+    fn quiet_parts_are_gated() {
+        let tone = [
+            sine(1_000.0, -36.0, 10.0),
+            sine(1_000.0, -23.0, 60.0),
+            sine(1_000.0, -36.0, 10.0),
+        ]
+        .concat();
+
+        assert_close(
+            integrated_loudness(&[tone.clone(), tone], SAMPLE_RATE),
+            -23.0,
+        );
+    }
+
+    #[test]
+    fn silence_has_no_loudness() {
+        assert_eq!(integrated_loudness(&[vec![0.0; 48_000]], SAMPLE_RATE), None);
+        assert_eq!(integrated_loudness(&[vec![0.5; 100]], SAMPLE_RATE), None);
+        assert_eq!(integrated_loudness(&[], SAMPLE_RATE), None);
+    }
+}
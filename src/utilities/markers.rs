@@ -0,0 +1,200 @@
+//! Reads and writes rehearsal markers as text, one marker per line: `1:23 Letter C`.
+//! Label tracks exported from Audacity (`start<TAB>end<TAB>label`, in seconds) and `time<TAB>name` lines are read as well.
+
+use crate::{i18n::t_with, service::material::Marker};
+
+/// A line of the text that is not a marker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarkerError {
+    /// Starting at 1.
+    pub line: usize,
+    pub kind: MarkerErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarkerErrorKind {
+    InvalidTime(String),
+    MissingName,
+}
+
+impl MarkerError {
+    pub fn message(&self) -> String {
+        let line = self.line.to_string();
+        match &self.kind {
+            MarkerErrorKind::InvalidTime(time) => {
+                t_with("markers-invalid-time", &[("line", &line), ("time", time)])
+            }
+            MarkerErrorKind::MissingName => t_with("markers-missing-name", &[("line", &line)]),
+        }
+    }
+}
+
+/// The markers of the text, sorted by their position. Empty lines are skipped.
+pub fn parse_markers(content: &str) -> Result<Vec<Marker>, MarkerError> {
+    let mut markers = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim_start_matches('\u{feff}');
+        // Newer Audacity versions add the frequency range of a label in a line starting with a backslash.
+        if line.trim().is_empty() || line.starts_with('\\') {
+            continue;
+        }
+
+        let (time, name) = if line.contains('\t') {
+            let fields = line.split('\t').collect::<Vec<_>>();
+            // Labels have a start, an end and a name; the end is skipped, markers are single points.
+            // Lines of `time<TAB>name` are read as well.
+            let name = match fields.as_slice() {
+                [_, name] => name,
+                [_, _, name, ..] => name,
+                _ => "",
+            };
+            (fields[0].trim(), name.trim())
+        } else {
+            let line = line.trim();
+            let (time, name) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let name = name.trim_start();
+            (time, name.strip_prefix("- ").unwrap_or(name).trim())
+        };
+
+        let position_ms = parse_time(time).ok_or_else(|| MarkerError {
+            line: line_number,
+            kind: MarkerErrorKind::InvalidTime(time.to_string()),
+        })?;
+        if name.is_empty() {
+            return Err(MarkerError {
+                line: line_number,
+                kind: MarkerErrorKind::MissingName,
+            });
+        }
+
+        markers.push(Marker {
+            position_ms,
+            name: name.to_string(),
+        });
+    }
+
+    markers.sort_by_key(|marker| marker.position_ms);
+    Ok(markers)
+}
+
+/// The markers in the format read by [parse_markers].
+pub fn format_markers(markers: &[Marker]) -> String {
+    markers
+        .iter()
+        .map(|marker| {
+            format!(
+                "{} {}\n",
+                format_marker_time(marker.position_ms),
+                marker.name
+            )
+        })
+        .collect()
+}
+
+/// Formats a position as `m:ss`, with tenths of a second if needed, and hours for long recordings.
+pub fn format_marker_time(position_ms: i64) -> String {
+    let tenths = (position_ms.max(0) + 50) / 100;
+    let seconds = tenths / 10;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    let mut formatted = if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    };
+    if tenths % 10 != 0 {
+        formatted.push_str(&format!(".{}", tenths % 10));
+    }
+    formatted
+}
+
+/// Reads `1:23`, `1:02:03`, `83` or `83.5` (with `.` or `,`) as milliseconds.
+fn parse_time(time: &str) -> Option<i64> {
+    let mut seconds = 0.0;
+    for part in time.split(':') {
+        let value: f64 = part.replace(',', ".").parse().ok()?;
+        if !value.is_finite() || value < 0.0 {
+            return None;
+        }
+        seconds = seconds * 60.0 + value;
+    }
+    Some((seconds * 1000.0).round() as i64)
+}
+
+#[cfg(test)]
+mod markers_test {
+    use crate::service::material::Marker;
+
+    use super::{format_marker_time, format_markers, parse_markers, MarkerErrorKind};
+
+    fn marker(position_ms: i64, name: &str) -> Marker {
+        Marker {
+            position_ms,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn text_markers_are_read() {
+        let markers = parse_markers("0:00 Intro\n\n1:23 - Letter C\n57.5 Bar 57\n").unwrap();
+
+        assert_eq!(
+            markers,
+            vec![
+                marker(0, "Intro"),
+                marker(57_500, "Bar 57"),
+                marker(83_000, "Letter C"),
+            ]
+        );
+    }
+
+    #[test]
+    fn audacity_labels_are_read() {
+        let content =
+            "12.500000\t12.500000\tLetter A\n\\\t200.0\t4000.0\n75,250000\t80,000000\tLetter B\n";
+
+        let markers = parse_markers(content).unwrap();
+
+        assert_eq!(
+            markers,
+            vec![marker(12_500, "Letter A"), marker(75_250, "Letter B")]
+        );
+    }
+
+    #[test]
+    fn tab_separated_names_are_read() {
+        let markers = parse_markers("1:23\tLetter C\n90.5\tCoda\n").unwrap();
+
+        assert_eq!(
+            markers,
+            vec![marker(83_000, "Letter C"), marker(90_500, "Coda")]
+        );
+    }
+
+    #[test]
+    fn invalid_lines_are_reported() {
+        let error = parse_markers("0:10 Intro\nbar 57\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.kind, MarkerErrorKind::InvalidTime("bar".to_string()));
+
+        let error = parse_markers("1:10\n").unwrap_err();
+        assert_eq!(error.kind, MarkerErrorKind::MissingName);
+    }
+
+    #[test]
+    fn times_are_formatted() {
+        assert_eq!(format_marker_time(0), "0:00");
+        assert_eq!(format_marker_time(83_000), "1:23");
+        assert_eq!(format_marker_time(57_540), "0:57.5");
+        assert_eq!(format_marker_time(3_723_000), "1:02:03");
+    }
+
+    #[test]
+    fn formatted_markers_are_read_again() {
+        let markers = vec![marker(1_500, "Intro"), marker(3_723_000, "Coda")];
+
+        assert_eq!(parse_markers(&format_markers(&markers)).unwrap(), markers);
+    }
+}
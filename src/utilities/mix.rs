@@ -0,0 +1,289 @@
+//! Settings of the preview mix of the submissions and the WAV file it is exported as.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::service::section::Section;
+
+/// Sections are spread up to this far from the centre of the stereo field, from the first to the last.
+const SECTION_SPREAD: f64 = 0.6;
+
+/// Exported mixes louder than this (about -1 dBFS) are turned down, so they don't clip.
+const EXPORT_PEAK: f32 = 0.89;
+
+/// Gain and position in the stereo field of a section or a single track.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChannelSetting {
+    /// Between 0 and 2, 1 leaves the track as it is.
+    pub gain: f64,
+    /// From -1 (left) to 1 (right). Pans of a track are added to the one of its section.
+    pub pan: f64,
+    pub muted: bool,
+    /// Only for sections: if any section is solo, only the solo sections are heard.
+    #[serde(default)]
+    pub solo: bool,
+}
+
+impl Default for ChannelSetting {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            pan: 0.0,
+            muted: false,
+            solo: false,
+        }
+    }
+}
+
+/// How the conductor wants to hear the ensemble.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MixSettings {
+    /// Sections nobody changed yet are spread over the stereo field in their order.
+    pub sections: BTreeMap<Section, ChannelSetting>,
+    /// By submission id.
+    pub tracks: BTreeMap<i64, ChannelSetting>,
+}
+
+impl MixSettings {
+    /// The setting of the section, which is the `index`th of `count` sections of the mix.
+    pub fn section(&self, section: &Section, index: usize, count: usize) -> ChannelSetting {
+        self.sections
+            .get(section)
+            .copied()
+            .unwrap_or_else(|| ChannelSetting {
+                pan: spread_pan(index, count),
+                ..ChannelSetting::default()
+            })
+    }
+
+    pub fn track(&self, submission_id: i64) -> ChannelSetting {
+        self.tracks.get(&submission_id).copied().unwrap_or_default()
+    }
+
+    /// Gain and pan each track is played with, in the order of the tracks, given as submission id and section.
+    /// The gain leaves headroom for the sum of all tracks, so it doesn't clip.
+    pub fn effective(&self, tracks: &[(i64, Section)]) -> Vec<(f64, f64)> {
+        let sections = sections_of(tracks);
+        let section_settings = sections
+            .iter()
+            .enumerate()
+            .map(|(index, section)| (*section, self.section(section, index, sections.len())))
+            .collect::<BTreeMap<_, _>>();
+        let any_solo = section_settings.values().any(|setting| setting.solo);
+        let headroom = 1.0 / (tracks.len().max(1) as f64).sqrt();
+
+        tracks
+            .iter()
+            .map(|(id, section)| {
+                let section = section_settings[section];
+                let track = self.track(*id);
+                let silent = section.muted || track.muted || (any_solo && !section.solo);
+                let gain = if silent {
+                    0.0
+                } else {
+                    section.gain * track.gain * headroom
+                };
+                (gain, (section.pan + track.pan).clamp(-1.0, 1.0))
+            })
+            .collect()
+    }
+}
+
+/// The sections of the tracks in their configured order, each once.
+pub fn sections_of(tracks: &[(i64, Section)]) -> Vec<&Section> {
+    let mut sections = tracks
+        .iter()
+        .map(|(_, section)| section)
+        .collect::<Vec<_>>();
+    sections.sort();
+    sections.dedup();
+    sections
+}
+
+/// Spreads sections evenly from left to right, a single section is in the centre.
+pub fn spread_pan(index: usize, count: usize) -> f64 {
+    if count <= 1 {
+        return 0.0;
+    }
+    -SECTION_SPREAD + 2.0 * SECTION_SPREAD * index as f64 / (count - 1) as f64
+}
+
+/// Seconds each track has to be delayed, so the reference starts at the same time in all of them.
+/// Offsets are milliseconds into the tracks at which the reference starts. The track with the longest lead-in
+/// starts right away.
+pub fn track_delays(offsets_ms: &[i64]) -> Vec<f64> {
+    let latest = offsets_ms.iter().copied().max().unwrap_or_default();
+    offsets_ms
+        .iter()
+        .map(|offset| (latest - offset) as f64 / 1000.0)
+        .collect()
+}
+
+/// Encodes the channels as 16 bit PCM WAV file. Mixes that would clip are turned down first.
+pub fn encode_wav(channels: &[Vec<f32>], sample_rate: u32) -> Vec<u8> {
+    let length = channels.iter().map(Vec::len).min().unwrap_or_default();
+    let peak = channels
+        .iter()
+        .flat_map(|channel| channel[..length].iter())
+        .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    let scale = if peak > EXPORT_PEAK {
+        EXPORT_PEAK / peak
+    } else {
+        1.0
+    };
+
+    let channel_count = channels.len() as u16;
+    let block_align = channel_count * 2;
+    let data_size = (length * block_align as usize) as u32;
+    let mut wav = Vec::with_capacity(44 + data_size as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&channel_count.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_size.to_le_bytes());
+    for index in 0..length {
+        for channel in channels {
+            let sample = (channel[index] * scale).clamp(-1.0, 1.0);
+            wav.extend_from_slice(&((sample * i16::MAX as f32).round() as i16).to_le_bytes());
+        }
+    }
+    wav
+}
+
+#[cfg(test)]
+mod mix_test {
+    use std::collections::BTreeMap;
+
+    use crate::{service::section::Section, utilities::audio::source_sample_rate};
+
+    use super::{encode_wav, spread_pan, track_delays, ChannelSetting, MixSettings};
+
+    fn section(value: &str) -> Section {
+        serde_json::from_str(&format!("\"{value}\"")).unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn sections_are_spread_from_left_to_right() {
+        assert_close(spread_pan(0, 1), 0.0);
+        assert_close(spread_pan(0, 3), -0.6);
+        assert_close(spread_pan(1, 3), 0.0);
+        assert_close(spread_pan(2, 3), 0.6);
+    }
+
+    #[test]
+    fn section_and_track_settings_are_combined() {
+        let tracks = [
+            (1, section("Soprano1")),
+            (2, section("Bass1")),
+            (3, section("Bass1")),
+            (4, section("Tenor1")),
+        ];
+        let settings = MixSettings {
+            sections: BTreeMap::from([(
+                section("Bass1"),
+                ChannelSetting {
+                    gain: 1.5,
+                    pan: 0.8,
+                    ..ChannelSetting::default()
+                },
+            )]),
+            tracks: BTreeMap::from([
+                (
+                    3,
+                    ChannelSetting {
+                        gain: 0.5,
+                        pan: 0.4,
+                        ..ChannelSetting::default()
+                    },
+                ),
+                (
+                    4,
+                    ChannelSetting {
+                        muted: true,
+                        ..ChannelSetting::default()
+                    },
+                ),
+            ]),
+        };
+
+        let effective = settings.effective(&tracks);
+
+        // Four tracks leave a headroom of a half. Sopranos are left, as the first of three sections.
+        assert_close(effective[0].0, 0.5);
+        assert_close(effective[0].1, -0.6);
+        assert_close(effective[1].0, 0.75);
+        assert_close(effective[1].1, 0.8);
+        assert_close(effective[2].0, 0.375);
+        assert_close(effective[2].1, 1.0);
+        assert_close(effective[3].0, 0.0);
+    }
+
+    #[test]
+    fn solo_sections_silence_the_others() {
+        let tracks = [(1, section("Soprano1")), (2, section("Alto1"))];
+        let settings = MixSettings {
+            sections: BTreeMap::from([(
+                section("Alto1"),
+                ChannelSetting {
+                    solo: true,
+                    ..ChannelSetting::default()
+                },
+            )]),
+            tracks: BTreeMap::new(),
+        };
+
+        let gains = settings
+            .effective(&tracks)
+            .into_iter()
+            .map(|(gain, _)| gain)
+            .collect::<Vec<_>>();
+
+        assert_close(gains[0], 0.0);
+        assert_close(gains[1], 1.0 / 2.0f64.sqrt());
+    }
+
+    #[test]
+    fn longest_lead_in_starts_first() {
+        assert_eq!(track_delays(&[500, 1500, -250]), vec![1.0, 0.0, 1.75]);
+        assert_eq!(track_delays(&[]), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn wav_has_header_and_interleaved_samples() {
+        let wav = encode_wav(&[vec![0.5, -0.5], vec![0.0, 0.25]], 48_000);
+
+        assert_eq!(wav.len(), 44 + 8);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()), 44);
+        assert_eq!(source_sample_rate(&wav), Some(48_000));
+        let samples = wav[44..]
+            .chunks(2)
+            .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+            .collect::<Vec<_>>();
+        assert_eq!(samples, vec![16_384, 0, -16_384, 8_192]);
+    }
+
+    #[test]
+    fn loud_mixes_are_turned_down() {
+        let wav = encode_wav(&[vec![2.0, -1.0]], 44_100);
+
+        let samples = wav[44..]
+            .chunks(2)
+            .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+            .collect::<Vec<_>>();
+        assert_eq!(samples, vec![29_163, -14_581]);
+    }
+}
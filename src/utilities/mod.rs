@@ -1,11 +1,28 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
 use gloo_utils::document;
 
-use wasm_bindgen::{JsCast, UnwrapThrowExt};
-use web_sys::HtmlAnchorElement;
+use gloo_timers::callback::Timeout;
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
 
+pub mod alignment;
+pub mod audio;
 pub mod callback;
 pub mod date;
+pub mod ical;
+pub mod latency;
+pub mod loudness;
+pub mod markers;
+pub mod mix;
+pub mod pitch;
+pub mod project_import;
+pub mod quality;
+pub mod relative_time;
 pub(crate) mod requests;
+pub mod score;
+pub mod select_enum;
+pub mod waveform;
 
 #[allow(dead_code)]
 pub fn download_from_link(url: &str) {
@@ -19,3 +36,42 @@ pub fn download_from_link(url: &str) {
     ele.click();
     ele.remove();
 }
+
+/// Lets the browser save the content as a file, without a round trip to the backend.
+pub fn download_file(file_name: &str, content_type: &str, content: &str) {
+    let ele: HtmlAnchorElement = document()
+        .create_element("a")
+        .unwrap()
+        .dyn_into()
+        .unwrap_throw();
+    ele.set_href(&format!(
+        "data:{content_type};charset=utf-8;base64,{}",
+        STANDARD.encode(content)
+    ));
+    ele.set_download(file_name);
+    ele.click();
+    ele.remove();
+}
+
+/// Lets the browser save binary content as a file, e.g. rendered audio too large for a data url.
+pub fn download_bytes(file_name: &str, content_type: &str, content: &[u8]) -> Result<(), JsValue> {
+    let options = BlobPropertyBag::new();
+    options.set_type(content_type);
+    let blob = Blob::new_with_u8_array_sequence_and_options(
+        &Array::of1(&Uint8Array::from(content)),
+        &options,
+    )?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let ele: HtmlAnchorElement = document().create_element("a")?.dyn_into()?;
+    ele.set_href(&url);
+    ele.set_download(file_name);
+    ele.click();
+    ele.remove();
+    // Some browsers start the download only after the click returned.
+    Timeout::new(60_000, move || {
+        let _ = Url::revoke_object_url(&url);
+    })
+    .forget();
+    Ok(())
+}
@@ -0,0 +1,299 @@
+//! Tracks the pitch of a single voice with YIN (de Cheveigné and Kawahara, 2002) and compares it to a reference.
+//! Pitches are MIDI note numbers with cents as fraction, so `69.5` is a quarter tone above A4.
+
+use crate::utilities::score::Note;
+
+/// Time between two pitch estimates in seconds.
+pub const HOP_SECONDS: f64 = 0.01;
+
+/// Deviations up to this many cents count as in tune.
+pub const IN_TUNE_CENTS: f64 = 25.0;
+
+/// Audio is downsampled to about this rate first, the voice has no fundamentals above it.
+const ANALYSIS_RATE: f32 = 11_025.0;
+
+/// Lowest pitch tracked, a low C of the basses.
+const MIN_FREQUENCY: f32 = 65.0;
+
+/// Highest pitch tracked, a high C of the sopranos.
+const MAX_FREQUENCY: f32 = 1_100.0;
+
+/// Dips of the normalised difference below this are taken for the period. Lower values find fewer, but surer pitches.
+const YIN_THRESHOLD: f32 = 0.15;
+
+/// Frames quieter than this (RMS) are taken for breaths and rests.
+const MIN_RMS: f32 = 0.01;
+
+/// The pitch over time, one estimate every [HOP_SECONDS].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PitchTrack {
+    /// [None] where nothing is sung.
+    pub frames: Vec<Option<f32>>,
+}
+
+impl PitchTrack {
+    pub fn at(&self, seconds: f64) -> Option<f32> {
+        if seconds < 0.0 {
+            return None;
+        }
+        self.frames
+            .get((seconds / HOP_SECONDS).round() as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// The notes as they would be sung exactly.
+    pub fn from_notes(notes: &[Note]) -> Self {
+        let end = notes.iter().map(|note| note.end).fold(0.0, f64::max);
+        let mut frames = vec![None; (end / HOP_SECONDS).ceil() as usize];
+        for note in notes {
+            let first = (note.start / HOP_SECONDS).round() as usize;
+            let last = ((note.end / HOP_SECONDS).round() as usize).min(frames.len());
+            for frame in frames.iter_mut().take(last).skip(first) {
+                *frame = Some(note.pitch as f32);
+            }
+        }
+        Self { frames }
+    }
+}
+
+pub fn track_pitch(samples: &[f32], sample_rate: f32) -> PitchTrack {
+    let factor = ((sample_rate / ANALYSIS_RATE).round() as usize).max(1);
+    // Averaging before dropping samples keeps high partials from folding down.
+    let samples = samples
+        .chunks(factor)
+        .map(|chunk| chunk.iter().sum::<f32>() / chunk.len() as f32)
+        .collect::<Vec<_>>();
+    let rate = sample_rate / factor as f32;
+
+    let tau_min = (rate / MAX_FREQUENCY).floor() as usize;
+    let tau_max = (rate / MIN_FREQUENCY).ceil() as usize;
+    let window = tau_max;
+    let hop = (HOP_SECONDS * rate as f64).round() as usize;
+    let frame_count = samples.len().div_ceil(hop.max(1));
+
+    let frames = (0..frame_count)
+        .map(|index| {
+            let start = index * hop;
+            let frame = samples.get(start..start + window + tau_max)?;
+            let rms = (frame[..window].iter().map(|x| x * x).sum::<f32>() / window as f32).sqrt();
+            if rms < MIN_RMS {
+                return None;
+            }
+            let period = yin_period(frame, window, tau_min, tau_max)?;
+            Some(frequency_to_pitch(rate / period))
+        })
+        .collect();
+
+    PitchTrack { frames }
+}
+
+/// The period of the frame in samples, between two samples if that fits better.
+fn yin_period(frame: &[f32], window: usize, tau_min: usize, tau_max: usize) -> Option<f32> {
+    let difference = (0..=tau_max)
+        .map(|tau| {
+            (0..window)
+                .map(|j| (frame[j] - frame[j + tau]).powi(2))
+                .sum::<f32>()
+        })
+        .collect::<Vec<_>>();
+
+    // Normalised by the mean of the smaller lags, so the zero lag doesn't win.
+    let mut normalised = vec![1.0; tau_max + 1];
+    let mut sum = 0.0;
+    for tau in 1..=tau_max {
+        sum += difference[tau];
+        normalised[tau] = if sum > 0.0 {
+            difference[tau] * tau as f32 / sum
+        } else {
+            1.0
+        };
+    }
+
+    let mut tau = (tau_min.max(2)..tau_max).find(|&tau| normalised[tau] < YIN_THRESHOLD)?;
+    while tau + 1 < tau_max && normalised[tau + 1] < normalised[tau] {
+        tau += 1;
+    }
+
+    let (before, at, after) = (normalised[tau - 1], normalised[tau], normalised[tau + 1]);
+    let curvature = before + after - 2.0 * at;
+    let shift = if curvature.abs() > f32::EPSILON {
+        (before - after) / (2.0 * curvature)
+    } else {
+        0.0
+    };
+    Some(tau as f32 + shift.clamp(-1.0, 1.0))
+}
+
+pub fn frequency_to_pitch(frequency: f32) -> f32 {
+    69.0 + 12.0 * (frequency / 440.0).log2()
+}
+
+/// How a submission matches the reference, frame by frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Intonation {
+    /// Pitch of the submission and the reference, one every [HOP_SECONDS] of the submission.
+    pub frames: Vec<(Option<f32>, Option<f32>)>,
+}
+
+impl Intonation {
+    /// Compares the tracks, the reference starts `offset_seconds` into the submission.
+    pub fn compare(sung: &PitchTrack, reference: &PitchTrack, offset_seconds: f64) -> Self {
+        let frames = sung
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(index, &pitch)| {
+                let reference = reference.at(index as f64 * HOP_SECONDS - offset_seconds);
+                (pitch, reference)
+            })
+            .collect();
+        Self { frames }
+    }
+
+    /// Deviation in cents where both are sounding, by time. Octaves are ignored, men singing an octave below are in tune.
+    pub fn deviations(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.frames
+            .iter()
+            .enumerate()
+            .filter_map(|(index, frame)| match *frame {
+                (Some(sung), Some(reference)) => {
+                    Some((index as f64 * HOP_SECONDS, deviation_cents(sung, reference)))
+                }
+                _ => None,
+            })
+    }
+
+    /// Share of the compared frames that are in tune, [None] if no frames could be compared.
+    pub fn in_tune_share(&self) -> Option<f64> {
+        let (compared, in_tune) =
+            self.deviations()
+                .fold((0, 0), |(compared, in_tune), (_, cents)| {
+                    (
+                        compared + 1,
+                        in_tune + usize::from(cents.abs() <= IN_TUNE_CENTS),
+                    )
+                });
+        (compared > 0).then(|| in_tune as f64 / compared as f64)
+    }
+
+    /// The median deviation in cents: positive if the submission is sharp, negative if it is flat.
+    pub fn median_deviation(&self) -> Option<f64> {
+        let mut deviations = self
+            .deviations()
+            .map(|(_, cents)| cents)
+            .collect::<Vec<_>>();
+        if deviations.is_empty() {
+            return None;
+        }
+        deviations.sort_by(f64::total_cmp);
+        Some(deviations[deviations.len() / 2])
+    }
+}
+
+/// How far the sung pitch is from the reference in cents, in the nearest octave.
+pub fn deviation_cents(sung: f32, reference: f32) -> f64 {
+    let semitones = (sung - reference) as f64;
+    (semitones - 12.0 * (semitones / 12.0).round()) * 100.0
+}
+
+#[cfg(test)]
+mod pitch_test {
+    use std::f32::consts::PI;
+
+    use crate::utilities::score::Note;
+
+    use super::{track_pitch, Intonation, PitchTrack, HOP_SECONDS};
+
+    const SAMPLE_RATE: f32 = 44_100.0;
+
+    /// A tone with a few overtones, more like a voice than a sine.
+    fn tone(frequency: f32, seconds: f32) -> Vec<f32> {
+        (0..(seconds * SAMPLE_RATE) as usize)
+            .map(|index| {
+                let phase = 2.0 * PI * frequency * index as f32 / SAMPLE_RATE;
+                0.3 * phase.sin() + 0.15 * (2.0 * phase).sin() + 0.08 * (3.0 * phase).sin()
+            })
+            .collect()
+    }
+
+    fn assert_pitch(track: &PitchTrack, seconds: f64, expected: f32) {
+        let pitch = track.at(seconds).expect("Pitch expected");
+        assert!(
+            (pitch - expected).abs() < 0.1,
+            "{pitch} instead of {expected}"
+        );
+    }
+
+    #[test]
+    fn pitches_of_all_voices_are_found() {
+        for (frequency, pitch) in [(82.41, 40.0), (220.0, 57.0), (440.0, 69.0), (880.0, 81.0)] {
+            let track = track_pitch(&tone(frequency, 0.5), SAMPLE_RATE);
+            assert_pitch(&track, 0.2, pitch);
+        }
+    }
+
+    #[test]
+    fn silence_has_no_pitch() {
+        let samples = [vec![0.0; 22_050], tone(220.0, 0.5)].concat();
+
+        let track = track_pitch(&samples, SAMPLE_RATE);
+
+        assert_eq!(track.at(0.2), None);
+        assert_pitch(&track, 0.8, 57.0);
+        assert!(((track.frames.len() as f64 * HOP_SECONDS) - 1.0).abs() < HOP_SECONDS * 2.0);
+    }
+
+    #[test]
+    fn notes_become_a_track() {
+        let track = PitchTrack::from_notes(&[
+            Note {
+                start: 0.0,
+                end: 0.5,
+                pitch: 60,
+            },
+            Note {
+                start: 1.0,
+                end: 1.5,
+                pitch: 62,
+            },
+        ]);
+
+        assert_eq!(track.at(0.2), Some(60.0));
+        assert_eq!(track.at(0.7), None);
+        assert_eq!(track.at(1.2), Some(62.0));
+        assert_eq!(track.at(-1.0), None);
+    }
+
+    #[test]
+    fn deviations_are_compared_with_offset() {
+        let sung = PitchTrack {
+            frames: vec![None, Some(60.2), Some(60.1), Some(47.9), Some(61.0)],
+        };
+        let reference = PitchTrack {
+            frames: vec![Some(60.0), Some(60.0), Some(60.0), Some(60.0)],
+        };
+
+        let intonation = Intonation::compare(&sung, &reference, HOP_SECONDS);
+        let deviations = intonation
+            .deviations()
+            .map(|(_, cents)| cents.round())
+            .collect::<Vec<_>>();
+
+        // An octave below is fine, only the 10 cents flat count.
+        assert_eq!(deviations, vec![20.0, 10.0, -10.0, 100.0]);
+        assert_eq!(intonation.in_tune_share(), Some(0.75));
+        assert_eq!(intonation.median_deviation().map(f64::round), Some(20.0));
+    }
+
+    #[test]
+    fn nothing_to_compare_without_reference() {
+        let sung = PitchTrack {
+            frames: vec![Some(60.0)],
+        };
+        let intonation = Intonation::compare(&sung, &PitchTrack { frames: vec![] }, 0.0);
+
+        assert_eq!(intonation.in_tune_share(), None);
+        assert_eq!(intonation.median_deviation(), None);
+    }
+}
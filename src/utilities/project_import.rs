@@ -0,0 +1,412 @@
+//! Reads many projects at once from a CSV or JSON file, e.g. one per piece of a new season.
+//!
+//! CSV files need a header with the columns `title`, `due` and optionally `description`, `time_zone` and
+//! `due:<section>` (e.g. `due:Bass1`) for section deadlines. JSON files contain a list of objects with the same
+//! fields, section deadlines are given as `"section_deadlines": [{ "section": "Bass1", "due": "..." }]`.
+//! Dates are either RFC 3339 timestamps or wall times like `2026-10-18 20:00` (or only the day, meaning 23:59)
+//! in the time zone of the row.
+
+use serde::Deserialize;
+use time::{
+    format_description::well_known::Rfc3339, macros::format_description, Date, OffsetDateTime,
+    PrimitiveDateTime, Time,
+};
+
+use crate::{
+    i18n::{t, t_with},
+    service::section::Section,
+    utilities::date::wall_time_to_instant,
+};
+
+const SECTION_COLUMN_PREFIX: &str = "due:";
+
+/// A project read from the file, with everything that is wrong with it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedProject {
+    /// Line of the project in CSV files, position in the list (starting at 1) in JSON files.
+    pub row: usize,
+    pub title: String,
+    pub description: String,
+    pub due: Option<ImportDate>,
+    pub time_zone: Option<String>,
+    pub section_deadlines: Vec<(Section, ImportDate)>,
+    pub problems: Vec<ImportProblem>,
+}
+
+impl ImportedProject {
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImportDate {
+    /// The date was given with its offset.
+    Instant(OffsetDateTime),
+    /// The date was given as shown on clocks in the time zone of the project.
+    Wall(PrimitiveDateTime),
+}
+
+impl ImportDate {
+    pub fn resolve(self, time_zone: &str) -> OffsetDateTime {
+        match self {
+            ImportDate::Instant(date) => date,
+            ImportDate::Wall(wall) => wall_time_to_instant(wall, time_zone),
+        }
+    }
+}
+
+/// Something wrong with a single project, which is then not created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportProblem {
+    MissingTitle,
+    MissingDue,
+    InvalidDate(String),
+    UnknownSection(String),
+    UnknownTimeZone(String),
+}
+
+impl ImportProblem {
+    pub fn message(&self) -> String {
+        match self {
+            ImportProblem::MissingTitle => t("project-import-missing-title"),
+            ImportProblem::MissingDue => t("project-import-missing-due"),
+            ImportProblem::InvalidDate(date) => {
+                t_with("project-import-invalid-date", &[("date", date)])
+            }
+            ImportProblem::UnknownSection(section) => {
+                t_with("project-import-unknown-section", &[("section", section)])
+            }
+            ImportProblem::UnknownTimeZone(zone) => {
+                t_with("project-import-unknown-time-zone", &[("zone", zone)])
+            }
+        }
+    }
+}
+
+/// The file as a whole could not be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportError {
+    Csv(String),
+    Json(String),
+    UnknownColumn(String),
+    Empty,
+}
+
+impl ImportError {
+    pub fn message(&self) -> String {
+        match self {
+            ImportError::Csv(error) => t_with("project-import-csv-failed", &[("error", error)]),
+            ImportError::Json(error) => t_with("project-import-json-failed", &[("error", error)]),
+            ImportError::UnknownColumn(column) => {
+                t_with("project-import-unknown-column", &[("column", column)])
+            }
+            ImportError::Empty => t("project-import-empty"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonProject {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    description: String,
+    due: Option<String>,
+    time_zone: Option<String>,
+    #[serde(default)]
+    section_deadlines: Vec<JsonSectionDeadline>,
+}
+
+#[derive(Deserialize)]
+struct JsonSectionDeadline {
+    section: String,
+    due: String,
+}
+
+/// Reads the projects of a CSV or JSON file, JSON files are recognized by their first character.
+/// Time zones of the rows have to be one of `known_time_zones`.
+pub fn parse_projects(
+    content: &str,
+    known_time_zones: &[String],
+) -> Result<Vec<ImportedProject>, ImportError> {
+    let projects = if content.trim_start().starts_with('[') {
+        parse_json(content, known_time_zones)?
+    } else {
+        parse_csv(content, known_time_zones)?
+    };
+
+    if projects.is_empty() {
+        return Err(ImportError::Empty);
+    }
+    Ok(projects)
+}
+
+fn parse_json(
+    content: &str,
+    known_time_zones: &[String],
+) -> Result<Vec<ImportedProject>, ImportError> {
+    let projects: Vec<JsonProject> =
+        serde_json::from_str(content).map_err(|error| ImportError::Json(error.to_string()))?;
+
+    Ok(projects
+        .into_iter()
+        .enumerate()
+        .map(|(index, project)| {
+            imported_project(
+                index + 1,
+                project.title,
+                project.description,
+                project.due,
+                project.time_zone,
+                project
+                    .section_deadlines
+                    .into_iter()
+                    .map(|deadline| (deadline.section, deadline.due))
+                    .collect(),
+                known_time_zones,
+            )
+        })
+        .collect())
+}
+
+fn parse_csv(
+    content: &str,
+    known_time_zones: &[String],
+) -> Result<Vec<ImportedProject>, ImportError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|error| ImportError::Csv(error.to_string()))?
+        .clone();
+
+    for header in headers.iter() {
+        let known = matches!(
+            header.to_lowercase().as_str(),
+            "title" | "description" | "due" | "time_zone"
+        ) || header.starts_with(SECTION_COLUMN_PREFIX);
+        if !known {
+            return Err(ImportError::UnknownColumn(header.to_string()));
+        }
+    }
+
+    let mut projects = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|error| ImportError::Csv(error.to_string()))?;
+        let field = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(name))
+                .and_then(|index| record.get(index))
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let section_deadlines = headers
+            .iter()
+            .zip(record.iter())
+            .filter_map(|(header, value)| {
+                let section = header.strip_prefix(SECTION_COLUMN_PREFIX)?;
+                (!value.is_empty()).then(|| (section.to_string(), value.to_string()))
+            })
+            .collect();
+
+        let row = record
+            .position()
+            .map(|position| position.line() as usize)
+            .unwrap_or_default();
+
+        projects.push(imported_project(
+            row,
+            field("title").unwrap_or_default(),
+            field("description").unwrap_or_default(),
+            field("due"),
+            field("time_zone"),
+            section_deadlines,
+            known_time_zones,
+        ));
+    }
+
+    Ok(projects)
+}
+
+fn imported_project(
+    row: usize,
+    title: String,
+    description: String,
+    due: Option<String>,
+    time_zone: Option<String>,
+    section_deadlines: Vec<(String, String)>,
+    known_time_zones: &[String],
+) -> ImportedProject {
+    let mut problems = Vec::new();
+
+    if title.trim().is_empty() {
+        problems.push(ImportProblem::MissingTitle);
+    }
+
+    let due = match due {
+        Some(due) => parse_date(&due).or_else(|| {
+            problems.push(ImportProblem::InvalidDate(due));
+            None
+        }),
+        None => {
+            problems.push(ImportProblem::MissingDue);
+            None
+        }
+    };
+
+    if let Some(zone) = &time_zone {
+        if !known_time_zones.contains(zone) {
+            problems.push(ImportProblem::UnknownTimeZone(zone.clone()));
+        }
+    }
+
+    let section_deadlines = section_deadlines
+        .into_iter()
+        .filter_map(|(section, due)| {
+            let Ok(parsed_section) = Section::try_from(section.as_str()) else {
+                problems.push(ImportProblem::UnknownSection(section));
+                return None;
+            };
+            match parse_date(&due) {
+                Some(date) => Some((parsed_section, date)),
+                None => {
+                    problems.push(ImportProblem::InvalidDate(due));
+                    None
+                }
+            }
+        })
+        .collect();
+
+    ImportedProject {
+        row,
+        title: title.trim().to_string(),
+        description,
+        due,
+        time_zone,
+        section_deadlines,
+        problems,
+    }
+}
+
+fn parse_date(value: &str) -> Option<ImportDate> {
+    if let Ok(date) = OffsetDateTime::parse(value, &Rfc3339) {
+        return Some(ImportDate::Instant(date));
+    }
+
+    let value = value.replacen('T', " ", 1);
+    if let Ok(wall) = PrimitiveDateTime::parse(
+        &value,
+        format_description!("[year]-[month]-[day] [hour]:[minute]:[second]"),
+    ) {
+        return Some(ImportDate::Wall(wall));
+    }
+    if let Ok(wall) = PrimitiveDateTime::parse(
+        &value,
+        format_description!("[year]-[month]-[day] [hour]:[minute]"),
+    ) {
+        return Some(ImportDate::Wall(wall));
+    }
+
+    Date::parse(&value, format_description!("[year]-[month]-[day]"))
+        .ok()
+        .map(|date| ImportDate::Wall(date.with_time(Time::from_hms(23, 59, 0).unwrap())))
+}
+
+#[cfg(test)]
+mod project_import_test {
+    use time::macros::datetime;
+
+    use crate::service::section::Section;
+
+    use super::{parse_projects, ImportDate, ImportError, ImportProblem};
+
+    fn zones() -> Vec<String> {
+        vec!["Europe/Berlin".to_string()]
+    }
+
+    #[test]
+    fn csv_rows_are_read() {
+        let content = "title,description,due,time_zone,due:Bass1\n\
+            Messiah,<p>Part 1</p>,2026-10-18 20:00,Europe/Berlin,2026-10-25\n\
+            Requiem,,2026-11-01T12:00:00+01:00,,\n";
+
+        let projects = parse_projects(content, &zones()).unwrap();
+
+        assert_eq!(projects.len(), 2);
+        assert!(projects.iter().all(|project| project.is_valid()));
+        assert_eq!(projects[0].row, 2);
+        assert_eq!(projects[0].description, "<p>Part 1</p>");
+        assert_eq!(
+            projects[0].due,
+            Some(ImportDate::Wall(datetime!(2026-10-18 20:00)))
+        );
+        assert_eq!(
+            projects[0].section_deadlines,
+            vec![(
+                Section::try_from("Bass1").unwrap(),
+                ImportDate::Wall(datetime!(2026-10-25 23:59))
+            )]
+        );
+        assert_eq!(
+            projects[1].due,
+            Some(ImportDate::Instant(datetime!(2026-11-01 12:00 +1)))
+        );
+        assert!(projects[1].section_deadlines.is_empty());
+    }
+
+    #[test]
+    fn csv_problems_are_collected_per_row() {
+        let content = "title,due,time_zone,due:Bass7\n\
+            ,tomorrow,Mars/Olympus,2026-10-25\n";
+
+        let projects = parse_projects(content, &zones()).unwrap();
+
+        assert_eq!(
+            projects[0].problems,
+            vec![
+                ImportProblem::MissingTitle,
+                ImportProblem::InvalidDate("tomorrow".to_string()),
+                ImportProblem::UnknownTimeZone("Mars/Olympus".to_string()),
+                ImportProblem::UnknownSection("Bass7".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_csv_column_is_rejected() {
+        assert_eq!(
+            parse_projects("titel,due\nMessiah,2026-10-18\n", &zones()),
+            Err(ImportError::UnknownColumn("titel".to_string()))
+        );
+    }
+
+    #[test]
+    fn json_projects_are_read() {
+        let content = r#"[
+            { "title": "Messiah", "due": "2026-10-18T20:00", "section_deadlines": [{ "section": "Bass1", "due": "2026-10-25" }] },
+            { "title": "Requiem" }
+        ]"#;
+
+        let projects = parse_projects(content, &zones()).unwrap();
+
+        assert!(projects[0].is_valid());
+        assert_eq!(projects[0].section_deadlines.len(), 1);
+        assert_eq!(projects[1].row, 2);
+        assert_eq!(projects[1].problems, vec![ImportProblem::MissingDue]);
+    }
+
+    #[test]
+    fn empty_files_are_rejected() {
+        assert_eq!(
+            parse_projects("title,due\n", &zones()),
+            Err(ImportError::Empty)
+        );
+        assert_eq!(parse_projects("[]", &zones()), Err(ImportError::Empty));
+    }
+}
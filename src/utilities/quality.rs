@@ -0,0 +1,420 @@
+//! Checks recordings for problems conductors would otherwise only notice when mixing: clipping, silence,
+//! low sample rates, quiet recordings and stereo channels cancelling each other when mixed to mono.
+
+use std::f64::consts::PI;
+
+use crate::{
+    i18n::{t, t_with},
+    utilities::{
+        audio::{DecodedAudio, CLIPPING_LEVEL, SILENCE_LEVEL},
+        loudness::integrated_loudness,
+    },
+};
+
+/// Clipping is only counted for this many full scale samples in a row, single loud samples are fine.
+const MIN_CLIPPED_RUN: usize = 3;
+
+/// Silence at the start and end is measured in windows of this length, so single clicks don't count.
+const SILENCE_WINDOW_SECONDS: f64 = 0.01;
+
+/// The true peak is searched between samples at this many points.
+const OVERSAMPLING: usize = 4;
+
+/// Samples on each side used to interpolate between samples.
+const INTERPOLATION_TAPS: isize = 8;
+
+/// Files recorded with lower sample rates sound muffled.
+const MIN_SAMPLE_RATE: u32 = 22_050;
+
+/// Quieter recordings are mostly noise once they are turned up.
+const MIN_LOUDNESS_LUFS: f64 = -40.0;
+
+/// Energy of the mono mix relative to the channels below which they cancel each other. 1 for equal channels.
+const MIN_MONO_COMPATIBILITY: f64 = 0.25;
+
+/// Measurements of a recording.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioQuality {
+    pub duration_seconds: f64,
+    /// Sample rate the file was recorded with, if it could be read.
+    pub sample_rate: Option<u32>,
+    pub channels: usize,
+    pub peak_dbfs: f64,
+    /// The peak between the samples, as reached when the file is played or converted.
+    pub true_peak_dbtp: f64,
+    /// Passages of clipped samples.
+    pub clipping_count: usize,
+    /// Integrated loudness after EBU R 128. [None] for silent recordings.
+    pub loudness_lufs: Option<f64>,
+    pub leading_silence_seconds: f64,
+    pub trailing_silence_seconds: f64,
+    /// Energy of the mono mix relative to the channels. Only measured for stereo recordings.
+    pub mono_compatibility: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum QualityWarning {
+    Silent,
+    LowSampleRate(u32),
+    Clipping(usize),
+    TruePeakOver(f64),
+    TooQuiet(f64),
+    LongSilence,
+    MonoCancellation,
+}
+
+impl QualityWarning {
+    /// Problems that most likely make the recording unusable.
+    pub fn is_severe(&self) -> bool {
+        matches!(
+            self,
+            QualityWarning::Silent
+                | QualityWarning::LowSampleRate(_)
+                | QualityWarning::Clipping(_)
+                | QualityWarning::MonoCancellation
+        )
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            QualityWarning::Silent => t("quality-warning-silent"),
+            QualityWarning::LowSampleRate(rate) => t_with(
+                "quality-warning-sample-rate",
+                &[("rate", &format!("{:.1}", *rate as f64 / 1000.0))],
+            ),
+            QualityWarning::Clipping(count) => {
+                t_with("quality-warning-clipping", &[("count", count)])
+            }
+            QualityWarning::TruePeakOver(peak) => {
+                t_with("quality-warning-true-peak", &[("peak", &format_db(*peak))])
+            }
+            QualityWarning::TooQuiet(loudness) => t_with(
+                "quality-warning-quiet",
+                &[("loudness", &format_db(*loudness))],
+            ),
+            QualityWarning::LongSilence => t("quality-warning-long-silence"),
+            QualityWarning::MonoCancellation => t("quality-warning-mono"),
+        }
+    }
+}
+
+/// Summary of the warnings, shown as badge.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum QualityRating {
+    Good,
+    Warnings,
+    Problems,
+}
+
+impl QualityRating {
+    pub fn badge_class(self) -> &'static str {
+        match self {
+            QualityRating::Good => "badge bg-success",
+            QualityRating::Warnings => "badge bg-warning text-dark",
+            QualityRating::Problems => "badge bg-danger",
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            QualityRating::Good => t("quality-good"),
+            QualityRating::Warnings => t("quality-warnings"),
+            QualityRating::Problems => t("quality-problems"),
+        }
+    }
+}
+
+impl AudioQuality {
+    pub fn analyse(audio: &DecodedAudio) -> Self {
+        let channels = &audio.channels;
+        let peak = channels
+            .iter()
+            .flatten()
+            .fold(0f32, |peak, sample| peak.max(sample.abs()));
+        let true_peak = channels
+            .iter()
+            .map(|channel| true_peak(channel, peak))
+            .fold(peak, f32::max);
+        let (leading_silence_seconds, trailing_silence_seconds) = silence(audio);
+
+        Self {
+            duration_seconds: audio.duration_seconds(),
+            sample_rate: audio.source_sample_rate,
+            channels: channels.len(),
+            peak_dbfs: to_db(peak as f64),
+            true_peak_dbtp: to_db(true_peak as f64),
+            clipping_count: channels.iter().map(|channel| clipping_count(channel)).sum(),
+            loudness_lufs: integrated_loudness(channels, audio.sample_rate),
+            leading_silence_seconds,
+            trailing_silence_seconds,
+            mono_compatibility: mono_compatibility(channels),
+        }
+    }
+
+    pub fn warnings(&self) -> Vec<QualityWarning> {
+        let mut warnings = Vec::new();
+
+        let silent = self.loudness_lufs.is_none() || self.peak_dbfs < to_db(SILENCE_LEVEL as f64);
+        if silent {
+            warnings.push(QualityWarning::Silent);
+        }
+        if let Some(rate) = self.sample_rate.filter(|&rate| rate < MIN_SAMPLE_RATE) {
+            warnings.push(QualityWarning::LowSampleRate(rate));
+        }
+        if self.clipping_count > 0 {
+            warnings.push(QualityWarning::Clipping(self.clipping_count));
+        } else if self.true_peak_dbtp > 0.0 {
+            warnings.push(QualityWarning::TruePeakOver(self.true_peak_dbtp));
+        }
+        if silent {
+            return warnings;
+        }
+        if let Some(loudness) = self.loudness_lufs.filter(|&l| l < MIN_LOUDNESS_LUFS) {
+            warnings.push(QualityWarning::TooQuiet(loudness));
+        }
+        if self.leading_silence_seconds + self.trailing_silence_seconds
+            > self.duration_seconds / 2.0
+        {
+            warnings.push(QualityWarning::LongSilence);
+        }
+        if self
+            .mono_compatibility
+            .is_some_and(|compatibility| compatibility < MIN_MONO_COMPATIBILITY)
+        {
+            warnings.push(QualityWarning::MonoCancellation);
+        }
+        warnings
+    }
+
+    pub fn rating(&self) -> QualityRating {
+        let warnings = self.warnings();
+        if warnings.iter().any(QualityWarning::is_severe) {
+            QualityRating::Problems
+        } else if warnings.is_empty() {
+            QualityRating::Good
+        } else {
+            QualityRating::Warnings
+        }
+    }
+}
+
+/// Formats decibels with one decimal, `-∞` for silence.
+pub fn format_db(value: f64) -> String {
+    if value.is_finite() {
+        format!("{value:.1}")
+    } else {
+        "-∞".to_string()
+    }
+}
+
+fn to_db(amplitude: f64) -> f64 {
+    20.0 * amplitude.log10()
+}
+
+fn clipping_count(samples: &[f32]) -> usize {
+    samples
+        .split(|sample| sample.abs() < CLIPPING_LEVEL)
+        .filter(|run| run.len() >= MIN_CLIPPED_RUN)
+        .count()
+}
+
+/// Seconds of silence at the start and the end. A silent recording is silent from the start.
+fn silence(audio: &DecodedAudio) -> (f64, f64) {
+    let window = ((SILENCE_WINDOW_SECONDS * audio.sample_rate as f64) as usize).max(1);
+    let length = audio
+        .channels
+        .iter()
+        .map(Vec::len)
+        .min()
+        .unwrap_or_default();
+    let audible = |index: usize| {
+        let range = index * window..((index + 1) * window).min(length);
+        audio.channels.iter().any(|channel| {
+            channel[range.clone()]
+                .iter()
+                .any(|s| s.abs() >= SILENCE_LEVEL)
+        })
+    };
+
+    let windows = length.div_ceil(window);
+    let to_seconds =
+        |windows: usize| (windows * window).min(length) as f64 / audio.sample_rate as f64;
+    match (0..windows).position(audible) {
+        Some(first) => {
+            let last = (0..windows).rposition(audible).unwrap_or(first);
+            let trailing = length.saturating_sub((last + 1) * window);
+            (
+                to_seconds(first),
+                trailing as f64 / audio.sample_rate as f64,
+            )
+        }
+        None => (audio.duration_seconds(), 0.0),
+    }
+}
+
+/// The highest amplitude between the samples, interpolated with a windowed sinc.
+/// Only samples at least half as loud as the sample peak are interpolated, quieter ones don't reach it.
+fn true_peak(samples: &[f32], sample_peak: f32) -> f32 {
+    let kernels = (1..OVERSAMPLING)
+        .map(|phase| {
+            let fraction = phase as f64 / OVERSAMPLING as f64;
+            (1 - INTERPOLATION_TAPS..=INTERPOLATION_TAPS)
+                .map(|tap| {
+                    let t = tap as f64 - fraction;
+                    let window = 0.5 * (1.0 + (PI * t / INTERPOLATION_TAPS as f64).cos());
+                    ((PI * t).sin() / (PI * t) * window) as f32
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let threshold = sample_peak / 2.0;
+    let mut peak = 0f32;
+    for index in 0..samples.len().saturating_sub(1) {
+        if samples[index].abs().max(samples[index + 1].abs()) < threshold {
+            continue;
+        }
+        for kernel in &kernels {
+            let value: f32 = kernel
+                .iter()
+                .enumerate()
+                .filter_map(|(offset, weight)| {
+                    let position = index as isize + offset as isize + 1 - INTERPOLATION_TAPS;
+                    usize::try_from(position)
+                        .ok()
+                        .and_then(|position| samples.get(position))
+                        .map(|sample| sample * weight)
+                })
+                .sum();
+            peak = peak.max(value.abs());
+        }
+    }
+    peak
+}
+
+fn mono_compatibility(channels: &[Vec<f32>]) -> Option<f64> {
+    if channels.len() < 2 {
+        return None;
+    }
+    let length = channels.iter().map(Vec::len).min()?;
+    let count = channels.len() as f64;
+    let (mut mix_energy, mut channel_energy) = (0.0, 0.0);
+    for index in 0..length {
+        let mix = channels
+            .iter()
+            .map(|channel| channel[index] as f64)
+            .sum::<f64>()
+            / count;
+        mix_energy += mix * mix;
+        channel_energy += channels
+            .iter()
+            .map(|channel| (channel[index] as f64).powi(2))
+            .sum::<f64>()
+            / count;
+    }
+    (channel_energy > 0.0).then(|| mix_energy / channel_energy)
+}
+
+#[cfg(test)]
+mod quality_test {
+    use std::f32::consts::PI;
+
+    use crate::utilities::audio::DecodedAudio;
+
+    use super::{AudioQuality, QualityRating, QualityWarning};
+
+    const SAMPLE_RATE: f32 = 44_100.0;
+
+    fn sine(amplitude: f32, seconds: f32) -> Vec<f32> {
+        (0..(seconds * SAMPLE_RATE) as usize)
+            .map(|index| amplitude * (2.0 * PI * 440.0 * index as f32 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    fn audio(channels: Vec<Vec<f32>>, source_sample_rate: Option<u32>) -> DecodedAudio {
+        DecodedAudio {
+            sample_rate: SAMPLE_RATE,
+            channels,
+            source_sample_rate,
+        }
+    }
+
+    #[test]
+    fn clean_recording_is_good() {
+        let quality = AudioQuality::analyse(&audio(vec![sine(0.5, 5.0)], Some(48_000)));
+
+        assert!(quality.warnings().is_empty(), "{:?}", quality.warnings());
+        assert_eq!(quality.rating(), QualityRating::Good);
+        assert_eq!(quality.duration_seconds, 5.0);
+        assert!((quality.peak_dbfs + 6.02).abs() < 0.05);
+        assert_eq!(quality.clipping_count, 0);
+        assert_eq!(quality.leading_silence_seconds, 0.0);
+    }
+
+    #[test]
+    fn clipped_passages_are_counted() {
+        let clipped = sine(2.0, 1.0)
+            .into_iter()
+            .map(|sample| sample.clamp(-1.0, 1.0))
+            .collect();
+
+        let quality = AudioQuality::analyse(&audio(vec![clipped], None));
+
+        // Two clipped passages per period of 440 Hz.
+        assert_eq!(quality.clipping_count, 880);
+        assert_eq!(quality.rating(), QualityRating::Problems);
+    }
+
+    #[test]
+    fn true_peak_is_found_between_samples() {
+        // A quarter of the sample rate, so the samples miss the peaks by 45 degrees.
+        let samples = (0..4_410)
+            .map(|index| {
+                (std::f64::consts::FRAC_PI_2 * (index % 4) as f64 + PI as f64 / 4.0).sin() as f32
+            })
+            .collect();
+
+        let quality = AudioQuality::analyse(&audio(vec![samples], None));
+
+        assert!((quality.peak_dbfs + 3.01).abs() < 0.05);
+        assert!(
+            quality.true_peak_dbtp.abs() < 0.2,
+            "{}",
+            quality.true_peak_dbtp
+        );
+    }
+
+    #[test]
+    fn silence_is_measured() {
+        let samples = [vec![0.0; 88_200], sine(0.5, 1.0), vec![0.0; 44_100]].concat();
+
+        let quality = AudioQuality::analyse(&audio(vec![samples], None));
+
+        assert!((quality.leading_silence_seconds - 2.0).abs() < 0.02);
+        assert!((quality.trailing_silence_seconds - 1.0).abs() < 0.02);
+        assert_eq!(quality.warnings(), vec![QualityWarning::LongSilence]);
+        assert_eq!(quality.rating(), QualityRating::Warnings);
+    }
+
+    #[test]
+    fn silent_recordings_have_no_other_warnings() {
+        let quality = AudioQuality::analyse(&audio(vec![vec![0.0; 44_100]], Some(8_000)));
+
+        assert_eq!(
+            quality.warnings(),
+            vec![QualityWarning::Silent, QualityWarning::LowSampleRate(8_000)]
+        );
+    }
+
+    #[test]
+    fn inverted_channels_cancel_in_mono() {
+        let left = sine(0.5, 2.0);
+        let right = left.iter().map(|sample| -sample).collect();
+
+        let quality = AudioQuality::analyse(&audio(vec![left.clone(), right], None));
+        assert_eq!(quality.warnings(), vec![QualityWarning::MonoCancellation]);
+
+        let quality = AudioQuality::analyse(&audio(vec![left.clone(), left], None));
+        assert_eq!(quality.mono_compatibility, Some(1.0));
+    }
+}
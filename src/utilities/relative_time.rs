@@ -0,0 +1,147 @@
+use time::{Duration, OffsetDateTime};
+
+use crate::i18n::{translate, Locale};
+
+/// How pressing a deadline is, used to color countdowns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Urgency {
+    /// More than three days left.
+    Relaxed,
+    /// Less than three days left.
+    Soon,
+    /// Less than one day left.
+    Urgent,
+    Overdue,
+}
+
+impl Urgency {
+    pub fn of(remaining: Duration) -> Self {
+        if remaining <= Duration::ZERO {
+            Urgency::Overdue
+        } else if remaining < Duration::DAY {
+            Urgency::Urgent
+        } else if remaining < Duration::days(3) {
+            Urgency::Soon
+        } else {
+            Urgency::Relaxed
+        }
+    }
+
+    /// Bootstrap class of the badge showing the countdown.
+    pub fn css_class(self) -> &'static str {
+        match self {
+            Urgency::Relaxed => "badge bg-success",
+            Urgency::Soon => "badge bg-warning text-dark",
+            Urgency::Urgent => "badge bg-danger",
+            Urgency::Overdue => "badge bg-dark",
+        }
+    }
+}
+
+/// Describes how long it is until the deadline, e.g. `noch 2 Tage 4 Stunden`, or how long it is over, e.g. `überfällig seit 3 Stunden`.
+/// Only the two largest units are shown.
+pub fn format_remaining(due: OffsetDateTime, now: OffsetDateTime, locale: Locale) -> String {
+    let remaining = due - now;
+
+    if remaining.abs() < Duration::MINUTE {
+        let id = if remaining > Duration::ZERO {
+            "relative-less-than-minute-left"
+        } else {
+            "relative-just-overdue"
+        };
+        return translate(locale, id, &[]);
+    }
+
+    let duration = format_duration(remaining.abs(), locale);
+
+    if remaining > Duration::ZERO {
+        translate(locale, "relative-left", &[("duration", &duration)])
+    } else {
+        translate(locale, "relative-overdue", &[("duration", &duration)])
+    }
+}
+
+fn format_duration(duration: Duration, locale: Locale) -> String {
+    let units = [
+        (
+            duration.whole_days(),
+            "relative-day-one",
+            "relative-day-other",
+        ),
+        (
+            duration.whole_hours() % 24,
+            "relative-hour-one",
+            "relative-hour-other",
+        ),
+        (
+            duration.whole_minutes() % 60,
+            "relative-minute-one",
+            "relative-minute-other",
+        ),
+    ];
+
+    units
+        .iter()
+        .skip_while(|(count, _, _)| *count == 0)
+        .take(2)
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, one, other)| {
+            let id = if *count == 1 { one } else { other };
+            translate(locale, id, &[("count", count)])
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod relative_time_test {
+    use time::{macros::datetime, Duration};
+
+    use crate::i18n::Locale;
+
+    use super::{format_remaining, Urgency};
+
+    const NOW: time::OffsetDateTime = datetime!(2026-10-18 12:00 UTC);
+
+    #[test]
+    fn remaining_time_in_german() {
+        let due = NOW + Duration::days(2) + Duration::hours(4) + Duration::minutes(30);
+        assert_eq!(
+            format_remaining(due, NOW, Locale::German),
+            "noch 2 Tage 4 Stunden"
+        );
+    }
+
+    #[test]
+    fn singular_and_skipped_units_in_english() {
+        let due = NOW + Duration::days(1) + Duration::minutes(5);
+        assert_eq!(format_remaining(due, NOW, Locale::English), "1 day left");
+
+        let due = NOW + Duration::hours(1) + Duration::minutes(1);
+        assert_eq!(
+            format_remaining(due, NOW, Locale::English),
+            "1 hour 1 minute left"
+        );
+    }
+
+    #[test]
+    fn overdue() {
+        let due = NOW - Duration::hours(3);
+        assert_eq!(
+            format_remaining(due, NOW, Locale::German),
+            "überfällig seit 3 Stunden"
+        );
+        assert_eq!(
+            format_remaining(NOW - Duration::seconds(10), NOW, Locale::English),
+            "just overdue"
+        );
+    }
+
+    #[test]
+    fn urgency_levels() {
+        assert_eq!(Urgency::of(Duration::days(5)), Urgency::Relaxed);
+        assert_eq!(Urgency::of(Duration::days(2)), Urgency::Soon);
+        assert_eq!(Urgency::of(Duration::hours(2)), Urgency::Urgent);
+        assert_eq!(Urgency::of(Duration::ZERO), Urgency::Overdue);
+    }
+}
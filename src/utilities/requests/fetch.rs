@@ -4,11 +4,14 @@ use std::{
 };
 
 use gloo_utils::window;
+use js_sys::Uint8Array;
 use serde::Serialize;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{Request, RequestCredentials, RequestInit, RequestMode, Response};
 
+use crate::components::view_as::is_viewing_as;
+
 /// Something wrong has occurred while fetching an external resource.
 #[derive(Debug)]
 pub enum FetchError {
@@ -16,6 +19,8 @@ pub enum FetchError {
     SerdeError(serde_json::error::Error),
     WrongContentType,
     StatusCode(u16),
+    /// A changing request was refused, because an admin views the portal as another member.
+    ReadOnly,
 }
 impl Display for FetchError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
@@ -36,7 +41,6 @@ impl From<serde_json::error::Error> for FetchError {
     }
 }
 
-#[allow(dead_code)]
 pub async fn get_request_string(url: String) -> Result<String, FetchError> {
     let opts = RequestInit::new();
     opts.set_method("GET");
@@ -57,6 +61,24 @@ pub async fn get_request_string(url: String) -> Result<String, FetchError> {
     }
 }
 
+/// Loads a file, e.g. material that is analysed in the browser.
+pub async fn get_request_bytes(url: &str) -> Result<Vec<u8>, FetchError> {
+    let opts = RequestInit::new();
+    opts.set_method("GET");
+    opts.set_mode(RequestMode::Cors);
+    opts.set_credentials(RequestCredentials::Include);
+
+    let request = Request::new_with_str_and_init(url, &opts)?;
+
+    let resp = send_request(&request).await?;
+
+    check_status(&resp)?;
+
+    let buffer = JsFuture::from(resp.array_buffer()?).await?;
+
+    Ok(Uint8Array::new(&buffer).to_vec())
+}
+
 pub async fn get_request_struct<T: for<'a> serde::de::Deserialize<'a>>(
     url: &str,
 ) -> Result<T, FetchError> {
@@ -85,6 +107,8 @@ pub async fn post_request_struct<
     url: &str,
     payload: PAYLOAD,
 ) -> Result<RESPONSE, FetchError> {
+    check_writable()?;
+
     let opts = RequestInit::new();
     opts.set_method("POST");
     opts.set_mode(RequestMode::Cors);
@@ -105,6 +129,8 @@ pub async fn post_request_struct<
 }
 
 pub async fn delete_request(url: &str) -> Result<(), FetchError> {
+    check_writable()?;
+
     let opts = RequestInit::new();
     opts.set_method("DELETE");
     opts.set_mode(RequestMode::Cors);
@@ -142,6 +168,15 @@ async fn parse_result<T: for<'a> serde::de::Deserialize<'a>>(
     Ok(result)
 }
 
+/// Refuses changing requests while an admin views the portal as another member.
+fn check_writable() -> Result<(), FetchError> {
+    if is_viewing_as() {
+        return Err(FetchError::ReadOnly);
+    }
+
+    Ok(())
+}
+
 fn check_status(response: &Response) -> Result<(), FetchError> {
     let status = response.status();
 
@@ -0,0 +1,555 @@
+//! Reads the notes of the parts of a Standard MIDI File or an uncompressed MusicXML file, as reference for singers.
+//! Repeats are not expanded, the notes are read as written.
+
+use std::collections::HashMap;
+
+use roxmltree::{Document, Node};
+
+use crate::i18n::{t, t_with};
+
+/// Tempo if the file sets none, in quarter notes per minute.
+const DEFAULT_TEMPO: f64 = 120.0;
+
+/// A note of a part, with times in seconds from the start of the score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Note {
+    pub start: f64,
+    pub end: f64,
+    /// MIDI note number, 60 is the middle C.
+    pub pitch: u8,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Part {
+    pub name: String,
+    pub notes: Vec<Note>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScoreError {
+    InvalidMidi(String),
+    /// MIDI files timed in SMPTE frames instead of beats.
+    UnsupportedTiming,
+    InvalidXml(String),
+    NoNotes,
+}
+
+impl ScoreError {
+    pub fn message(&self) -> String {
+        match self {
+            ScoreError::InvalidMidi(reason) => t_with("score-invalid-midi", &[("reason", reason)]),
+            ScoreError::UnsupportedTiming => t("score-unsupported-timing"),
+            ScoreError::InvalidXml(reason) => t_with("score-invalid-xml", &[("reason", reason)]),
+            ScoreError::NoNotes => t("score-no-notes"),
+        }
+    }
+}
+
+/// Whether the file is read by [parse_score], by its name.
+pub fn is_score_file(file_name: &str) -> bool {
+    let name = file_name.to_lowercase();
+    [".mid", ".midi", ".musicxml", ".xml"]
+        .iter()
+        .any(|extension| name.ends_with(extension))
+}
+
+/// The parts with notes of a MIDI or MusicXML file, told apart by their content.
+pub fn parse_score(content: &[u8]) -> Result<Vec<Part>, ScoreError> {
+    let parts = if content.starts_with(b"MThd") {
+        parse_midi(content)?
+    } else {
+        let text = String::from_utf8_lossy(content);
+        parse_musicxml(text.trim_start_matches('\u{feff}'))?
+    };
+
+    let parts = parts
+        .into_iter()
+        .filter(|part| !part.notes.is_empty())
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        return Err(ScoreError::NoNotes);
+    }
+    Ok(parts)
+}
+
+/// Converts positions in ticks (or divisions) to seconds along the tempo changes.
+struct TempoMap {
+    /// Position and seconds per tick from there on, sorted by position.
+    changes: Vec<(u64, f64)>,
+}
+
+impl TempoMap {
+    fn new(mut changes: Vec<(u64, f64)>, default_seconds_per_tick: f64) -> Self {
+        changes.sort_by_key(|(position, _)| *position);
+        if changes.first().is_none_or(|(position, _)| *position > 0) {
+            changes.insert(0, (0, default_seconds_per_tick));
+        }
+        Self { changes }
+    }
+
+    fn seconds(&self, position: u64) -> f64 {
+        let mut seconds = 0.0;
+        for (index, &(start, seconds_per_tick)) in self.changes.iter().enumerate() {
+            if start >= position {
+                break;
+            }
+            let end = self
+                .changes
+                .get(index + 1)
+                .map_or(position, |(next, _)| (*next).min(position));
+            seconds += (end - start) as f64 * seconds_per_tick;
+        }
+        seconds
+    }
+}
+
+fn parse_midi(content: &[u8]) -> Result<Vec<Part>, ScoreError> {
+    let invalid = |reason: &str| ScoreError::InvalidMidi(reason.to_string());
+    let mut reader = Reader {
+        content,
+        position: 0,
+    };
+
+    if reader.bytes(4)? != b"MThd" {
+        return Err(invalid("header"));
+    }
+    let header_length = reader.u32()? as usize;
+    let header = reader.bytes(header_length)?;
+    if header.len() < 6 {
+        return Err(invalid("header"));
+    }
+    let track_count = u16::from_be_bytes([header[2], header[3]]);
+    let division = u16::from_be_bytes([header[4], header[5]]);
+    if division & 0x8000 != 0 {
+        return Err(ScoreError::UnsupportedTiming);
+    }
+    let ticks_per_quarter = division.max(1) as f64;
+
+    let mut tempo_changes = Vec::new();
+    // Name and notes in ticks of each track.
+    let mut tracks = Vec::new();
+    for _ in 0..track_count {
+        if reader.bytes(4)? != b"MTrk" {
+            return Err(invalid("track"));
+        }
+        let length = reader.u32()? as usize;
+        let mut track = Reader {
+            content: reader.bytes(length)?,
+            position: 0,
+        };
+
+        let mut name = String::new();
+        let mut notes = Vec::new();
+        // Start and velocity of the sounding notes, by channel and pitch.
+        let mut sounding: HashMap<(u8, u8), u64> = HashMap::new();
+        let mut tick = 0u64;
+        let mut running_status = 0u8;
+        while !track.is_at_end() {
+            tick += track.variable_length()? as u64;
+            let mut status = track.byte()?;
+            if status < 0x80 {
+                // Running status: the byte is the first data byte of the same kind of event.
+                track.position -= 1;
+                status = running_status;
+            }
+            match status {
+                0xff => {
+                    let kind = track.byte()?;
+                    let length = track.variable_length()? as usize;
+                    let data = track.bytes(length)?;
+                    match kind {
+                        0x03 => name = String::from_utf8_lossy(data).trim().to_string(),
+                        0x51 if data.len() == 3 => {
+                            let micros = u32::from_be_bytes([0, data[0], data[1], data[2]]);
+                            tempo_changes
+                                .push((tick, micros as f64 / 1_000_000.0 / ticks_per_quarter));
+                        }
+                        _ => {}
+                    }
+                }
+                0xf0 | 0xf7 => {
+                    let length = track.variable_length()? as usize;
+                    track.bytes(length)?;
+                }
+                0x80..=0xef => {
+                    running_status = status;
+                    let channel = status & 0x0f;
+                    match status & 0xf0 {
+                        0x80 | 0x90 => {
+                            let pitch = track.byte()?;
+                            let velocity = track.byte()?;
+                            if let Some(start) = sounding.remove(&(channel, pitch)) {
+                                notes.push((start, tick, pitch));
+                            }
+                            if status & 0xf0 == 0x90 && velocity > 0 {
+                                sounding.insert((channel, pitch), tick);
+                            }
+                        }
+                        0xc0 | 0xd0 => {
+                            track.byte()?;
+                        }
+                        _ => {
+                            track.bytes(2)?;
+                        }
+                    }
+                }
+                _ => return Err(invalid("event")),
+            }
+        }
+        tracks.push((name, notes));
+    }
+
+    let tempo = TempoMap::new(tempo_changes, 60.0 / DEFAULT_TEMPO / ticks_per_quarter);
+    Ok(tracks
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, notes))| {
+            let mut notes = notes
+                .into_iter()
+                .map(|(start, end, pitch)| Note {
+                    start: tempo.seconds(start),
+                    end: tempo.seconds(end),
+                    pitch,
+                })
+                .collect::<Vec<_>>();
+            notes.sort_by(|a, b| a.start.total_cmp(&b.start));
+            Part {
+                name: if name.is_empty() {
+                    format!("{}", index + 1)
+                } else {
+                    name
+                },
+                notes,
+            }
+        })
+        .collect())
+}
+
+struct Reader<'a> {
+    content: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn is_at_end(&self) -> bool {
+        self.position >= self.content.len()
+    }
+
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], ScoreError> {
+        let bytes = self
+            .content
+            .get(self.position..self.position + count)
+            .ok_or_else(|| ScoreError::InvalidMidi("end of file".to_string()))?;
+        self.position += count;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, ScoreError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, ScoreError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// A number stored with 7 bits per byte, the highest bit set on all bytes but the last.
+    fn variable_length(&mut self) -> Result<u32, ScoreError> {
+        let mut value = 0u32;
+        for _ in 0..4 {
+            let byte = self.byte()?;
+            value = value << 7 | (byte & 0x7f) as u32;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ScoreError::InvalidMidi("number".to_string()))
+    }
+}
+
+/// Reads partwise MusicXML. Only the first voice of each part is read, and only the first note of chords.
+fn parse_musicxml(text: &str) -> Result<Vec<Part>, ScoreError> {
+    let document =
+        Document::parse(text).map_err(|error| ScoreError::InvalidXml(error.to_string()))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "score-partwise" {
+        return Err(ScoreError::InvalidXml(root.tag_name().name().to_string()));
+    }
+
+    let names = root
+        .descendants()
+        .filter(|node| node.has_tag_name("score-part"))
+        .filter_map(|node| {
+            let name = child_text(node, "part-name").unwrap_or_default();
+            Some((node.attribute("id")?.to_string(), name.trim().to_string()))
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Positions are counted in a common unit, as the divisions per quarter may change between measures.
+    const UNITS_PER_QUARTER: u64 = 3 * 5 * 7 * 64;
+    let mut tempo_changes = Vec::new();
+    let mut parts = Vec::new();
+    for part in root.children().filter(|node| node.has_tag_name("part")) {
+        let id = part.attribute("id").unwrap_or_default();
+        let mut divisions = 1u64;
+        let mut position = 0u64;
+        let mut first_voice = None;
+        // Start, end and pitch in units.
+        let mut notes: Vec<(u64, u64, u8)> = Vec::new();
+
+        for element in part
+            .children()
+            .filter(|node| node.has_tag_name("measure"))
+            .flat_map(|measure| measure.children())
+            .filter(Node::is_element)
+        {
+            let duration = || {
+                child_text(element, "duration")
+                    .and_then(|duration| duration.trim().parse::<u64>().ok())
+                    .unwrap_or_default()
+                    * UNITS_PER_QUARTER
+                    / divisions
+            };
+            match element.tag_name().name() {
+                "attributes" => {
+                    if let Some(value) = child_text(element, "divisions")
+                        .and_then(|value| value.trim().parse::<u64>().ok())
+                    {
+                        divisions = value.max(1);
+                    }
+                }
+                "backup" => position = position.saturating_sub(duration()),
+                "forward" => position += duration(),
+                "direction" | "sound" => {
+                    let tempo = element
+                        .descendants()
+                        .filter(|node| node.has_tag_name("sound"))
+                        .find_map(|sound| sound.attribute("tempo")?.parse::<f64>().ok());
+                    if let Some(tempo) = tempo.filter(|&tempo| tempo > 0.0) {
+                        tempo_changes.push((position, 60.0 / tempo / UNITS_PER_QUARTER as f64));
+                    }
+                }
+                "note" => {
+                    let has = |name: &str| element.children().any(|child| child.has_tag_name(name));
+                    // Chord notes sound together with the previous note, grace notes take no time.
+                    if has("chord") || has("grace") {
+                        continue;
+                    }
+                    let duration = duration();
+                    let voice = child_text(element, "voice")
+                        .unwrap_or("1")
+                        .trim()
+                        .to_string();
+                    let voice_matches = first_voice.get_or_insert_with(|| voice.clone()) == &voice;
+                    let pitch = element
+                        .children()
+                        .find(|child| child.has_tag_name("pitch"))
+                        .and_then(pitch_of);
+                    if let (true, Some(pitch)) = (voice_matches, pitch) {
+                        let tied = element.children().any(|child| {
+                            child.has_tag_name("tie") && child.attribute("type") == Some("stop")
+                        });
+                        match notes.last_mut() {
+                            // Tied notes are sung as one.
+                            Some(last) if tied && last.1 == position && last.2 == pitch => {
+                                last.1 = position + duration;
+                            }
+                            _ => notes.push((position, position + duration, pitch)),
+                        }
+                    }
+                    position += duration;
+                }
+                _ => {}
+            }
+        }
+
+        parts.push((
+            names
+                .get(id)
+                .filter(|name| !name.is_empty())
+                .cloned()
+                .unwrap_or_else(|| id.to_string()),
+            notes,
+        ));
+    }
+
+    let tempo = TempoMap::new(
+        tempo_changes,
+        60.0 / DEFAULT_TEMPO / UNITS_PER_QUARTER as f64,
+    );
+    Ok(parts
+        .into_iter()
+        .map(|(name, notes)| Part {
+            name,
+            notes: notes
+                .into_iter()
+                .map(|(start, end, pitch)| Note {
+                    start: tempo.seconds(start),
+                    end: tempo.seconds(end),
+                    pitch,
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.children()
+        .find(|child| child.has_tag_name(name))
+        .and_then(|child| child.text())
+}
+
+fn pitch_of(pitch: Node) -> Option<u8> {
+    let step = match child_text(pitch, "step")?.trim() {
+        "C" => 0,
+        "D" => 2,
+        "E" => 4,
+        "F" => 5,
+        "G" => 7,
+        "A" => 9,
+        "B" => 11,
+        _ => return None,
+    };
+    let alter = child_text(pitch, "alter")
+        .and_then(|alter| alter.trim().parse::<f64>().ok())
+        .unwrap_or_default()
+        .round() as i32;
+    let octave = child_text(pitch, "octave")?.trim().parse::<i32>().ok()?;
+    u8::try_from((octave + 1) * 12 + step + alter).ok()
+}
+
+#[cfg(test)]
+mod score_test {
+    use super::{is_score_file, parse_score, Note, ScoreError};
+
+    fn note(start: f64, end: f64, pitch: u8) -> Note {
+        Note { start, end, pitch }
+    }
+
+    /// The notes with times rounded to milliseconds, as the tempo is applied in floating point.
+    fn rounded(notes: &[Note]) -> Vec<Note> {
+        let round = |seconds: f64| (seconds * 1000.0).round() / 1000.0;
+        notes
+            .iter()
+            .map(|n| note(round(n.start), round(n.end), n.pitch))
+            .collect()
+    }
+
+    /// A MIDI file of format 1 with 480 ticks per quarter note.
+    fn midi(tracks: &[Vec<u8>]) -> Vec<u8> {
+        let mut content = b"MThd\0\0\0\x06\0\x01".to_vec();
+        content.extend((tracks.len() as u16).to_be_bytes());
+        content.extend(480u16.to_be_bytes());
+        for track in tracks {
+            content.extend(b"MTrk");
+            content.extend((track.len() as u32).to_be_bytes());
+            content.extend(track);
+        }
+        content
+    }
+
+    #[test]
+    fn midi_notes_follow_the_tempo() {
+        let content = midi(&[
+            // 60 quarter notes per minute, changing to 120 after two quarters.
+            vec![
+                0x00, 0xff, 0x51, 0x03, 0x0f, 0x42, 0x40, // 1 000 000 µs per quarter
+                0x87, 0x40, 0xff, 0x51, 0x03, 0x07, 0xa1, 0x20, // 500 000 µs after 960 ticks
+                0x00, 0xff, 0x2f, 0x00,
+            ],
+            vec![
+                0x00, 0xff, 0x03, 0x04, b'A', b'l', b't', b'o', // name
+                0x00, 0x90, 60, 64, // C4 on
+                0x87, 0x40, 62, 64, // after two quarters, running status: D4 on
+                0x00, 0x80, 60, 0, // C4 off
+                0x83, 0x60, 0x90, 62, 0, // a quarter later D4 off by velocity 0
+                0x00, 0xff, 0x2f, 0x00,
+            ],
+        ]);
+
+        let parts = parse_score(&content).unwrap();
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].name, "Alto");
+        assert_eq!(
+            rounded(&parts[0].notes),
+            vec![note(0.0, 2.0, 60), note(2.0, 2.5, 62)]
+        );
+    }
+
+    #[test]
+    fn broken_midi_is_reported() {
+        let mut content = midi(&[vec![0x00, 0x90, 60]]);
+        assert!(matches!(
+            parse_score(&content),
+            Err(ScoreError::InvalidMidi(_))
+        ));
+
+        content[12] = 0xe7;
+        assert_eq!(parse_score(&content), Err(ScoreError::UnsupportedTiming));
+    }
+
+    #[test]
+    fn musicxml_parts_are_read() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <score-partwise version="4.0">
+              <part-list>
+                <score-part id="P1"><part-name>Soprano</part-name></score-part>
+                <score-part id="P2"><part-name>Piano</part-name></score-part>
+              </part-list>
+              <part id="P1">
+                <measure number="1">
+                  <attributes><divisions>2</divisions></attributes>
+                  <direction><sound tempo="60"/></direction>
+                  <note><pitch><step>A</step><octave>4</octave></pitch><duration>2</duration><voice>1</voice></note>
+                  <note><pitch><step>F</step><alter>1</alter><octave>4</octave></pitch><duration>2</duration><voice>1</voice><tie type="start"/></note>
+                  <note><pitch><step>F</step><alter>1</alter><octave>4</octave></pitch><duration>1</duration><voice>1</voice><tie type="stop"/></note>
+                  <note><rest/><duration>1</duration><voice>1</voice></note>
+                  <note><pitch><step>B</step><alter>-1</alter><octave>3</octave></pitch><duration>2</duration><voice>1</voice></note>
+                  <note><chord/><pitch><step>D</step><octave>4</octave></pitch><duration>2</duration><voice>1</voice></note>
+                  <backup><duration>8</duration></backup>
+                  <note><pitch><step>C</step><octave>3</octave></pitch><duration>8</duration><voice>2</voice></note>
+                </measure>
+              </part>
+              <part id="P2">
+                <measure number="1">
+                  <attributes><divisions>1</divisions></attributes>
+                  <note><rest/><duration>4</duration></note>
+                </measure>
+              </part>
+            </score-partwise>"#;
+
+        let parts = parse_score(content.as_bytes()).unwrap();
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].name, "Soprano");
+        assert_eq!(
+            rounded(&parts[0].notes),
+            vec![note(0.0, 1.0, 69), note(1.0, 2.5, 66), note(3.0, 4.0, 58)]
+        );
+    }
+
+    #[test]
+    fn scores_without_notes_are_refused() {
+        assert!(matches!(
+            parse_score(b"<opus/>"),
+            Err(ScoreError::InvalidXml(_))
+        ));
+        assert!(matches!(
+            parse_score(b"not a score"),
+            Err(ScoreError::InvalidXml(_))
+        ));
+        assert_eq!(
+            parse_score(br#"<score-partwise><part id="P1"/></score-partwise>"#),
+            Err(ScoreError::NoNotes)
+        );
+    }
+
+    #[test]
+    fn score_files_are_recognised_by_name() {
+        assert!(is_score_file("Alto.MID"));
+        assert!(is_score_file("alto.musicxml"));
+        assert!(!is_score_file("alto.mxl"));
+        assert!(!is_score_file("alto.mp3"));
+    }
+}
@@ -0,0 +1,14 @@
+pub use ::select_enum::SelectEnum;
+
+/// A type whose values are chosen with an HTML `<select>`, see [crate::components::enum_select::EnumSelect].
+/// Usually derived for enums with `#[derive(SelectEnum)]`.
+pub trait SelectEnum: Clone + PartialEq + for<'a> TryFrom<&'a str, Error = ()> + 'static {
+    /// All options in the order they are shown.
+    fn options() -> Vec<Self>;
+
+    /// The stable value of the `<option>` that is also sent to the backend.
+    fn value(&self) -> String;
+
+    /// The text shown to the user.
+    fn label(&self) -> String;
+}
@@ -0,0 +1,207 @@
+//! Peaks of audio at several zoom levels, so waveforms can be drawn at any width without going through all samples again.
+
+use crate::utilities::audio::{CLIPPING_LEVEL, SILENCE_LEVEL};
+
+/// Samples summarised by a peak of the finest level. Each coarser level summarises twice as many.
+const BASE_SAMPLES_PER_PEAK: usize = 128;
+
+/// Peaks a column of the finest zoom level summarises at least.
+const PEAKS_PER_COLUMN: f64 = 8.0;
+
+/// The lowest and highest sample of a part of the audio.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Peak {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Peak {
+    /// Beyond the end of the audio.
+    const NONE: Peak = Peak { min: 0.0, max: 0.0 };
+
+    fn merge(self, other: Peak) -> Peak {
+        Peak {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    pub fn is_clipped(&self) -> bool {
+        self.max >= CLIPPING_LEVEL || self.min <= -CLIPPING_LEVEL
+    }
+
+    pub fn is_silent(&self) -> bool {
+        self.max.abs() < SILENCE_LEVEL && self.min.abs() < SILENCE_LEVEL
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Waveform {
+    sample_rate: f32,
+    sample_count: usize,
+    /// Level `i` has a peak for every `BASE_SAMPLES_PER_PEAK << i` samples.
+    levels: Vec<Vec<Peak>>,
+}
+
+impl Waveform {
+    pub fn new(samples: &[f32], sample_rate: f32) -> Self {
+        let base = samples
+            .chunks(BASE_SAMPLES_PER_PEAK)
+            .map(|chunk| {
+                chunk.iter().fold(
+                    Peak {
+                        min: f32::INFINITY,
+                        max: f32::NEG_INFINITY,
+                    },
+                    |peak, &sample| Peak {
+                        min: peak.min.min(sample),
+                        max: peak.max.max(sample),
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut levels = vec![base];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let coarser = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| pair.iter().copied().reduce(Peak::merge).unwrap())
+                .collect();
+            levels.push(coarser);
+        }
+
+        Self {
+            sample_rate,
+            sample_count: samples.len(),
+            levels,
+        }
+    }
+
+    pub fn duration(&self) -> f64 {
+        if self.sample_rate > 0.0 {
+            self.sample_count as f64 / self.sample_rate as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// The peaks of `columns` equally long parts of the audio from `start` to `end` seconds, e.g. one per pixel.
+    pub fn columns(&self, start: f64, end: f64, columns: usize) -> Vec<Peak> {
+        if columns == 0 || end <= start || self.sample_count == 0 {
+            return vec![Peak::NONE; columns];
+        }
+
+        let samples_per_column = (end - start) * self.sample_rate as f64 / columns as f64;
+        // The coarsest level with several peaks per column, so peaks reaching into the next column hardly matter.
+        let level = (0..self.levels.len())
+            .take_while(|level| {
+                (BASE_SAMPLES_PER_PEAK << level) as f64 * PEAKS_PER_COLUMN <= samples_per_column
+            })
+            .last()
+            .unwrap_or(0);
+        let peaks = &self.levels[level];
+        let samples_per_peak = (BASE_SAMPLES_PER_PEAK << level) as f64;
+
+        (0..columns)
+            .map(|column| {
+                let from = start * self.sample_rate as f64 + column as f64 * samples_per_column;
+                let to = from + samples_per_column;
+                if from < 0.0 || from >= self.sample_count as f64 {
+                    return Peak::NONE;
+                }
+                // The peaks starting in the column, or the one it lies in when zoomed in further than the finest level.
+                let first = ((from / samples_per_peak).ceil() as usize).min(peaks.len());
+                let last = ((to / samples_per_peak).ceil() as usize).min(peaks.len());
+                if first >= last {
+                    return peaks[(from / samples_per_peak) as usize];
+                }
+                peaks[first..last]
+                    .iter()
+                    .copied()
+                    .reduce(Peak::merge)
+                    .unwrap_or(Peak::NONE)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod waveform_test {
+    use super::{Peak, Waveform, BASE_SAMPLES_PER_PEAK};
+
+    const SAMPLE_RATE: f32 = 1280.0;
+
+    /// One second each of silence, a quiet tone and a clipped tone.
+    fn samples() -> Vec<f32> {
+        let second = SAMPLE_RATE as usize;
+        let tone = |amplitude: f32| {
+            (0..second).map(move |index| {
+                if index % 2 == 0 {
+                    amplitude
+                } else {
+                    -amplitude
+                }
+            })
+        };
+        std::iter::repeat_n(0.0, second)
+            .chain(tone(0.3))
+            .chain(tone(1.0))
+            .collect()
+    }
+
+    #[test]
+    fn levels_halve_until_one_peak_is_left() {
+        let waveform = Waveform::new(&samples(), SAMPLE_RATE);
+
+        assert_eq!(
+            waveform.levels[0].len(),
+            3 * SAMPLE_RATE as usize / BASE_SAMPLES_PER_PEAK
+        );
+        assert_eq!(waveform.levels.last().unwrap().len(), 1);
+        assert_eq!(waveform.duration(), 3.0);
+    }
+
+    #[test]
+    fn columns_summarise_their_part() {
+        let waveform = Waveform::new(&samples(), SAMPLE_RATE);
+
+        let columns = waveform.columns(0.0, 3.0, 3);
+
+        assert!(columns[0].is_silent());
+        assert_eq!(
+            columns[1],
+            Peak {
+                min: -0.3,
+                max: 0.3
+            }
+        );
+        assert!(!columns[1].is_clipped());
+        assert!(columns[2].is_clipped());
+    }
+
+    #[test]
+    fn zoomed_columns_use_finer_peaks() {
+        let waveform = Waveform::new(&samples(), SAMPLE_RATE);
+
+        let columns = waveform.columns(0.9, 1.1, 20);
+
+        assert!(columns[..9].iter().all(Peak::is_silent));
+        assert!(columns[11..].iter().all(|peak| peak.max == 0.3));
+    }
+
+    #[test]
+    fn columns_beyond_the_end_are_empty() {
+        let waveform = Waveform::new(&samples(), SAMPLE_RATE);
+
+        let columns = waveform.columns(2.0, 4.0, 4);
+
+        assert!(columns[0].is_clipped());
+        assert_eq!(columns[3], Peak { min: 0.0, max: 0.0 });
+        assert_eq!(
+            Waveform::new(&[], SAMPLE_RATE).columns(0.0, 1.0, 2).len(),
+            2
+        );
+    }
+}